@@ -1,3 +1,4 @@
+use flare::rise_set::SunRiseSet;
 use flare::{Observer, Time};
 
 fn main() {
@@ -7,27 +8,36 @@ fn main() {
     let time = Time::new(2024, 9, 10, 3, 0, 0);
     println!("{}", time);
 
-    let (sunrise, sunset) = observer.sun_set_time(Some(&time), None);
-
-    println!("Next sunrise: {}", sunrise);
-    println!("Next sunset: {}", sunset);
+    // the Sun may also stay up (polar day) or down (polar night)
+    match observer.sun_set_time(Some(&time), None) {
+        SunRiseSet::Normal(sunrise, sunset) => {
+            println!("Next sunrise: {}", sunrise);
+            println!("Next sunset: {}", sunset);
+        }
+        SunRiseSet::AlwaysUp => println!("The Sun does not set"),
+        SunRiseSet::AlwaysDown => println!("The Sun does not rise"),
+    }
 
     // the time is optional, in which case the current time is used
-    let (sunrise, sunset) = observer.sun_set_time(None, None);
-    println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+    if let SunRiseSet::Normal(sunrise, sunset) = observer.sun_set_time(None, None) {
+        println!("Sunrise: {}, Sunset: {}", sunrise, sunset);
+    }
 
     // You can also specify at what altitude the sun should be considered to have risen/set, as an angle in degrees
-    let (sunrise, sunset) = observer.sun_set_time(Some(&time), Some(0.0));
-
-    println!("Sunrise: {}, Sunset: {} (at 0.0 deg)", sunrise, sunset);
+    if let SunRiseSet::Normal(sunrise, sunset) = observer.sun_set_time(Some(&time), Some(0.0)) {
+        println!("Sunrise: {}, Sunset: {} (at 0.0 deg)", sunrise, sunset);
+    }
 
     // Otherwise, you can get astronomical, nautical, and civil twilight times:
-    let (sunrise, sunset) = observer.twilight_astronomical(Some(&time));
-    println!("Sunrise: {}, Sunset: {} (astronomical)", sunrise, sunset);
-
-    let (sunrise, sunset) = observer.twilight_nautical(Some(&time));
-    println!("Sunrise: {}, Sunset: {} (nautical)", sunrise, sunset);
-
-    let (sunrise, sunset) = observer.twilight_civil(Some(&time));
-    println!("Sunrise: {}, Sunset: {} (civil)", sunrise, sunset);
-}
\ No newline at end of file
+    if let SunRiseSet::Normal(sunrise, sunset) = observer.twilight_astronomical(Some(&time)) {
+        println!("Sunrise: {}, Sunset: {} (astronomical)", sunrise, sunset);
+    }
+
+    if let SunRiseSet::Normal(sunrise, sunset) = observer.twilight_nautical(Some(&time)) {
+        println!("Sunrise: {}, Sunset: {} (nautical)", sunrise, sunset);
+    }
+
+    if let SunRiseSet::Normal(sunrise, sunset) = observer.twilight_civil(Some(&time)) {
+        println!("Sunrise: {}, Sunset: {} (civil)", sunrise, sunset);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::corrections::refraction;
+use crate::spatial::DEGRA;
+
+/// Lowest altitude (in degrees) where the polynomial of Hardie (1962) is valid
+const HARDIE_MIN_ALTITUDE: f64 = 5.0;
+
+/// Altitude (in degrees) above which the polynomial of Hardie (1962) is used alone, without blending it with Young (1994)
+const HARDIE_BLEND_ALTITUDE: f64 = 10.0;
+
+/// AirmassModel enum
+///
+/// This enum represents the formulas available to compute the airmass from the altitude of a target.
+///
+/// # Variants
+///
+/// * `PlaneParallel` - Secant of the zenith distance, for a plane-parallel atmosphere (only accurate close to the zenith)
+/// * `Hardie` - Polynomial in (sec z - 1) of Hardie (1962), accurate down to a zenith distance of about 85 degrees
+///   (below an altitude of 5 degrees, `Young1994` is used instead, and between 5 and 10 degrees the two are blended
+///   linearly: they differ by 1.5% at 5 degrees, and the airmass would jump there otherwise)
+/// * `Young1994` - Rational function of cos z of Young (1994), accurate down to the horizon
+/// * `KastenYoung` - Formula of Kasten & Young (1989), accurate down to the horizon
+/// * `Pickering2002` - Formula of Pickering (2002) on the apparent altitude, accurate down to the horizon (the default)
+///
+/// # Examples
+///
+/// ```
+/// use flare::airmass::AirmassModel;
+///
+/// // all the models agree close to the zenith
+/// let models = [
+///     AirmassModel::PlaneParallel,
+///     AirmassModel::Hardie,
+///     AirmassModel::Young1994,
+///     AirmassModel::KastenYoung,
+///     AirmassModel::Pickering2002,
+/// ];
+/// for model in models.iter() {
+///     assert!((model.airmass(60.0) - 1.1547).abs() < 2e-3);
+/// }
+///
+/// // but not close to the horizon, where the curvature of the atmosphere matters
+/// assert!((AirmassModel::Young1994.airmass(1.0) - 23.46).abs() < 0.01);
+/// assert!((AirmassModel::PlaneParallel.airmass(1.0) - 57.30).abs() < 0.01);
+/// // and the polynomial of Hardie is not valid anymore: it falls back to Young (1994), without a jump
+/// assert_eq!(AirmassModel::Hardie.airmass(1.0), AirmassModel::Young1994.airmass(1.0));
+/// for alt in [5.0, 10.0] {
+///     assert!((AirmassModel::Hardie.airmass(alt + 1e-9) - AirmassModel::Hardie.airmass(alt - 1e-9)).abs() < 1e-6);
+/// }
+///
+/// // below the (geometric) horizon, the airmass is infinite
+/// assert_eq!(AirmassModel::KastenYoung.airmass(-5.0), f64::INFINITY);
+/// // even just below it, where the refraction would lift the target above the apparent horizon
+/// assert_eq!(AirmassModel::Pickering2002.airmass(-0.3), f64::INFINITY);
+/// ```
+///
+/// # References
+///
+/// * Hardie, R. H. 1962, in "Astronomical Techniques", ed. W. A. Hiltner (University of Chicago Press), 184
+/// * Young, A. T. 1994, Applied Optics, 33, 1108
+/// * Kasten, F. & Young, A. T. 1989, Applied Optics, 28, 4735
+/// * Pickering, K. A. 2002, DIO, 12, 3
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AirmassModel {
+    PlaneParallel,
+    Hardie,
+    Young1994,
+    KastenYoung,
+    #[default]
+    Pickering2002,
+}
+
+impl AirmassModel {
+    /// Calculate the airmass at a given altitude
+    ///
+    /// # Arguments
+    ///
+    /// * `alt` - True (geometric) altitude in degrees, without refraction
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The airmass, or `f64::INFINITY` if the altitude is not above the horizon
+    ///
+    /// # Notes
+    ///
+    /// Below the horizon there is no line of sight through the atmosphere, so every model returns `f64::INFINITY`:
+    /// such positions are never selected by an airmass limit, and sort after all the visible ones.
+    /// The horizon is the geometric one: a target less than about half a degree below it, which the refraction
+    /// lifts above the apparent horizon, still has an infinite airmass (like `corrections::refraction`, which is 0
+    /// below the geometric horizon).
+    /// The polynomial of Hardie (1962) diverges (and turns negative) close to the horizon, so below its validity
+    /// limit (an altitude of 5 degrees) the formula of Young (1994) is used instead: the airmass is always positive.
+    /// Between 5 and 10 degrees, the two formulas are blended linearly so that the airmass is continuous.
+    /// The formula of Pickering (2002) is written for the apparent altitude, so the refraction
+    /// (`corrections::refraction`) is added to the altitude before using it.
+    pub fn airmass(&self, alt: f64) -> f64 {
+        if alt <= 0.0 {
+            return f64::INFINITY;
+        }
+        let sin_alt = (alt * DEGRA).sin();
+        match self {
+            AirmassModel::PlaneParallel => 1.0 / sin_alt,
+            AirmassModel::Hardie if alt < HARDIE_MIN_ALTITUDE => AirmassModel::Young1994.airmass(alt),
+            AirmassModel::Hardie if alt < HARDIE_BLEND_ALTITUDE => {
+                let weight = (alt - HARDIE_MIN_ALTITUDE) / (HARDIE_BLEND_ALTITUDE - HARDIE_MIN_ALTITUDE);
+                weight * hardie(sin_alt) + (1.0 - weight) * AirmassModel::Young1994.airmass(alt)
+            }
+            AirmassModel::Hardie => hardie(sin_alt),
+            AirmassModel::Young1994 => {
+                (1.002432 * sin_alt.powi(2) + 0.148386 * sin_alt + 0.0096467)
+                    / (sin_alt.powi(3) + 0.149864 * sin_alt.powi(2) + 0.0102963 * sin_alt + 0.000303978)
+            }
+            AirmassModel::KastenYoung => 1.0 / (sin_alt + 0.50572 * (alt + 6.07995).powf(-1.6364)),
+            AirmassModel::Pickering2002 => {
+                let alt = alt + refraction(alt);
+                1.0 / ((alt + 244.0 / (165.0 + 47.0 * alt.powf(1.1))) * DEGRA).sin()
+            }
+        }
+    }
+}
+
+/// Polynomial in (sec z - 1) of Hardie (1962), from the sine of the altitude
+fn hardie(sin_alt: f64) -> f64 {
+    let x = 1.0 / sin_alt - 1.0;
+    1.0 + x - 0.0018167 * x - 0.002875 * x.powi(2) - 0.0008083 * x.powi(3)
+}
@@ -0,0 +1,270 @@
+use crate::corrections::{refraction, Atmosphere};
+use crate::ecliptic::true_equator_rotation;
+use crate::observer::Observer;
+use crate::spatial::DEGRA;
+use crate::target::Target;
+use crate::time::Time;
+
+/// AltAz struct
+///
+/// This struct represents a position in the horizontal frame of an observer.
+///
+/// # Attributes
+///
+/// * `alt` - Apparent altitude above the horizon in degrees (including atmospheric refraction)
+/// * `az` - Azimuth in degrees, measured from the North towards the East, in [0, 360)
+///
+/// # Methods
+///
+/// * `new` - Create a new AltAz
+/// * `zenith_distance` - Calculate the zenith distance
+/// * `to_radec` - Convert the position to RA and DEC
+/// * `to_radec_with_atmosphere` - Convert the position to RA and DEC, for given atmospheric conditions
+/// * `to_target` - Convert the position to a Target
+///
+/// # Examples
+///
+/// ```
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let time = Time::new(2024, 8, 24, 6, 35, 34);
+///
+/// let altaz = target.altaz(&observer, &time);
+/// assert!((altaz.alt - target.altitude(&observer, &time)).abs() < 1e-12);
+/// assert!((altaz.zenith_distance() - (90.0 - altaz.alt)).abs() < 1e-12);
+///
+/// // and back to RA and DEC
+/// let (ra, dec) = altaz.to_radec(&observer, &time);
+/// assert!((ra - target.ra).abs() < 1e-6 && (dec - target.dec).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AltAz {
+    pub alt: f64,
+    pub az: f64,
+}
+
+impl AltAz {
+    /// Create a new AltAz
+    ///
+    /// # Arguments
+    ///
+    /// * `alt` - Apparent altitude in degrees
+    /// * `az` - Azimuth in degrees, measured from the North towards the East
+    ///
+    /// # Returns
+    ///
+    /// * `AltAz` - A new AltAz object, with the azimuth normalized to [0, 360)
+    pub fn new(alt: f64, az: f64) -> AltAz {
+        AltAz { alt, az: az.rem_euclid(360.0) }
+    }
+
+    /// Calculate the zenith distance
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The apparent zenith distance (90 degrees minus the altitude) in degrees
+    pub fn zenith_distance(&self) -> f64 {
+        90.0 - self.alt
+    }
+
+    /// Convert the position to RA and DEC, for a given observer and time
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time of the observation
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - J2000 Right Ascension (in [0, 360)) and Declination in degrees, like those of a Target
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::altaz::AltAz;
+    /// use flare::{Observer, Target, Time};
+    ///
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    ///
+    /// // a position due South is on the meridian
+    /// let (ra, dec) = AltAz::new(60.0, 180.0).to_radec(&observer, &time);
+    /// let target = Target::new(ra, dec, None);
+    /// assert!(target.hour_angle(&observer, &time).abs() < 1e-9);
+    /// assert!((target.altitude(&observer, &time) - 60.0).abs() < 1e-9);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The altitude is corrected for refraction (with `corrections::refraction`) before the conversion.
+    pub fn to_radec(&self, observer: &Observer, time: &Time) -> (f64, f64) {
+        let (ha, dec) = altaz2hadec(remove_refraction(self.alt), self.az, observer.lat);
+        of_date_to_j2000(observer.local_sidereal_time(time) - ha, dec, time)
+    }
+
+    /// Convert the position to RA and DEC, for a given observer, time and atmospheric conditions
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time of the observation
+    /// * `atmosphere` - Atmospheric conditions at the observer, used to remove the refraction
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - J2000 Right Ascension (in [0, 360)) and Declination in degrees, like those of a Target
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    ///
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// let atmosphere = observer.atmosphere(5.0, 0.3, 0.62);
+    ///
+    /// let altaz = target.altaz_with_atmosphere(&observer, &time, &atmosphere);
+    /// let (ra, dec) = altaz.to_radec_with_atmosphere(&observer, &time, &atmosphere);
+    /// assert!((ra - target.ra).abs() < 1e-6 && (dec - target.dec).abs() < 1e-6);
+    /// ```
+    pub fn to_radec_with_atmosphere(&self, observer: &Observer, time: &Time, atmosphere: &Atmosphere) -> (f64, f64) {
+        let (ha, dec) = altaz2hadec(atmosphere.true_altitude(self.alt), self.az, observer.lat);
+        of_date_to_j2000(observer.local_sidereal_time(time) - ha, dec, time)
+    }
+
+    /// Convert the position to a Target, for a given observer and time
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time of the observation
+    ///
+    /// # Returns
+    ///
+    /// * `Target<'static>` - A Target (with no name) at the position
+    pub fn to_target(&self, observer: &Observer, time: &Time) -> Target<'static> {
+        let (ra, dec) = self.to_radec(observer, time);
+        Target::new(ra, dec, None)
+    }
+}
+
+/// Bring a position in the true equator and equinox of a date back to J2000, the frame of a Target
+fn of_date_to_j2000(ra: f64, dec: f64, time: &Time) -> (f64, f64) {
+    let (ra, dec) = true_equator_rotation(time).inverse().apply_radec(ra, dec);
+    (ra.rem_euclid(360.0), dec)
+}
+
+/// Invert `corrections::refraction`: find the true altitude of a position from its apparent altitude
+fn remove_refraction(apparent_alt: f64) -> f64 {
+    let mut alt = apparent_alt;
+    for _ in 0..20 {
+        let next = apparent_alt - refraction(alt);
+        if (next - alt).abs() < 1e-12 {
+            return next;
+        }
+        alt = next;
+    }
+    alt
+}
+
+/// Convert an hour angle and a declination to an altitude and an azimuth (geometric, without refraction)
+///
+/// # Arguments
+///
+/// * `ha` - Hour angle in degrees
+/// * `dec` - Declination in degrees
+/// * `lat` - Latitude of the observer in degrees
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Altitude and azimuth (from the North towards the East, in [0, 360)) in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::altaz::hadec2altaz;
+///
+/// // Venus seen from Washington, example 13.b of Meeus
+/// let (alt, az) = hadec2altaz(64.352133, -6.719892, 38.921389);
+/// assert!((alt - 15.1249).abs() < 1e-4);
+/// assert!((az - (68.0337 + 180.0)).abs() < 1e-4);
+/// ```
+pub fn hadec2altaz(ha: f64, dec: f64, lat: f64) -> (f64, f64) {
+    let ha = ha * DEGRA;
+    let dec = dec * DEGRA;
+    let lat = lat * DEGRA;
+    let alt = (dec.sin() * lat.sin() + dec.cos() * lat.cos() * ha.cos()).clamp(-1.0, 1.0).asin();
+    let az = (-dec.cos() * ha.sin()).atan2(dec.sin() * lat.cos() - dec.cos() * ha.cos() * lat.sin());
+    (alt / DEGRA, (az / DEGRA).rem_euclid(360.0))
+}
+
+/// Convert an altitude and an azimuth to an hour angle and a declination (geometric, without refraction)
+///
+/// # Arguments
+///
+/// * `alt` - Altitude in degrees
+/// * `az` - Azimuth (from the North towards the East) in degrees
+/// * `lat` - Latitude of the observer in degrees
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Hour angle (in [-180, 180)) and declination in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::altaz::{altaz2hadec, hadec2altaz};
+///
+/// let (alt, az) = hadec2altaz(-30.0, 20.0, 33.36);
+/// let (ha, dec) = altaz2hadec(alt, az, 33.36);
+/// assert!((ha + 30.0).abs() < 1e-9 && (dec - 20.0).abs() < 1e-9);
+/// ```
+pub fn altaz2hadec(alt: f64, az: f64, lat: f64) -> (f64, f64) {
+    let alt = alt * DEGRA;
+    let az = az * DEGRA;
+    let lat = lat * DEGRA;
+    let dec = (alt.sin() * lat.sin() + alt.cos() * lat.cos() * az.cos()).clamp(-1.0, 1.0).asin();
+    let ha = (-alt.cos() * az.sin()).atan2(alt.sin() * lat.cos() - alt.cos() * az.cos() * lat.sin());
+    (normalize_hour_angle(ha / DEGRA), dec / DEGRA)
+}
+
+/// Calculate the parallactic angle of a position: the position angle of the zenith, seen from that position
+///
+/// # Arguments
+///
+/// * `ha` - Hour angle in degrees
+/// * `dec` - Declination in degrees
+/// * `lat` - Latitude of the observer in degrees
+///
+/// # Returns
+///
+/// * `f64` - The parallactic angle in degrees, in [-180, 180], negative East of the meridian
+///
+/// # Examples
+///
+/// ```
+/// use flare::altaz::parallactic_angle;
+///
+/// // on the meridian, South of the zenith, the zenith is due North
+/// assert!(parallactic_angle(0.0, 10.0, 33.0).abs() < 1e-12);
+/// // one hour West of the meridian, the zenith is towards the East
+/// assert!(parallactic_angle(15.0, 10.0, 33.0) > 0.0);
+/// ```
+///
+/// # References
+///
+/// formula 14.1 of "Astronomical Algorithms" 2nd edition by Jean Meeus (Willmann-Bell, Richmond) 1998.
+pub fn parallactic_angle(ha: f64, dec: f64, lat: f64) -> f64 {
+    let ha = ha * DEGRA;
+    let dec = dec * DEGRA;
+    let lat = lat * DEGRA;
+    (ha.sin() * lat.cos()).atan2(lat.sin() * dec.cos() - lat.cos() * dec.sin() * ha.cos()) / DEGRA
+}
+
+/// Wrap an hour angle (in degrees) to [-180, 180)
+pub(crate) fn normalize_hour_angle(ha: f64) -> f64 {
+    (ha + 180.0).rem_euclid(360.0) - 180.0
+}
@@ -0,0 +1,532 @@
+use crate::region::SkyPolygon;
+use crate::spatial::{sky2tangent, tangent2sky, DEGRA};
+use crate::target::Target;
+
+/// Detector struct
+///
+/// This struct represents a detector (CCD) of a camera, as a polygon in focal-plane coordinates.
+/// Focal-plane coordinates are positions on the plane tangent to the sky at the pointing center,
+/// in degrees, with `x` towards the East and `y` towards the North when the rotator angle is 0.
+///
+/// # Attributes
+///
+/// * `id` - Identifier of the detector
+/// * `name` - Name of the detector
+/// * `corners` - Corners of the detector in focal-plane coordinates, in degrees
+/// * `segments` - Optional subdivisions of the detector (quadrants, amplifiers), as polygons in focal-plane coordinates
+///
+/// # Examples
+///
+/// ```
+/// use flare::camera::Detector;
+///
+/// let detector = Detector::rectangle(1, "CCD1", (0.0, 0.0), 1.0, 0.5, 2, 1);
+/// assert_eq!(detector.corners.len(), 4);
+/// assert_eq!(detector.segments.len(), 2);
+/// assert_eq!(detector.segment_at(0.25, 0.0), Some(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Detector {
+    pub id: u32,
+    pub name: String,
+    pub corners: Vec<(f64, f64)>,
+    pub segments: Vec<Vec<(f64, f64)>>,
+}
+
+impl Detector {
+    /// Create a new Detector
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Identifier of the detector
+    /// * `name` - Name of the detector
+    /// * `corners` - Corners of the detector in focal-plane coordinates, in degrees
+    /// * `segments` - Subdivisions of the detector as polygons in focal-plane coordinates (can be empty)
+    ///
+    /// # Returns
+    ///
+    /// * `Detector` - A new Detector object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Detector;
+    ///
+    /// let detector = Detector::new(7, "triangle", vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)], vec![]);
+    /// assert!(detector.contains(0.2, 0.2));
+    /// assert!(!detector.contains(0.8, 0.8));
+    /// ```
+    pub fn new(id: u32, name: &str, corners: Vec<(f64, f64)>, segments: Vec<Vec<(f64, f64)>>) -> Detector {
+        Detector { id, name: name.to_string(), corners, segments }
+    }
+
+    /// Create a new rectangular Detector, split into a grid of segments
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Identifier of the detector
+    /// * `name` - Name of the detector
+    /// * `center` - Center of the detector in focal-plane coordinates, in degrees
+    /// * `width` - Size of the detector along `x`, in degrees
+    /// * `height` - Size of the detector along `y`, in degrees
+    /// * `nx` - Number of segments along `x`
+    /// * `ny` - Number of segments along `y`
+    ///
+    /// # Returns
+    ///
+    /// * `Detector` - A new Detector object. Segments are numbered row by row, starting from
+    ///   the `-x, -y` corner. A 1x1 grid creates no segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Detector;
+    ///
+    /// let detector = Detector::rectangle(0, "CCD", (1.0, 1.0), 2.0, 2.0, 2, 2);
+    /// assert_eq!(detector.segment_at(0.5, 0.5), Some(0));
+    /// assert_eq!(detector.segment_at(1.5, 0.5), Some(1));
+    /// assert_eq!(detector.segment_at(0.5, 1.5), Some(2));
+    /// assert_eq!(detector.segment_at(3.0, 3.0), None);
+    /// ```
+    pub fn rectangle(id: u32, name: &str, center: (f64, f64), width: f64, height: f64, nx: usize, ny: usize) -> Detector {
+        let (x0, y0) = (center.0 - width / 2.0, center.1 - height / 2.0);
+        let rect = |x: f64, y: f64, w: f64, h: f64| vec![(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+        let mut segments = Vec::new();
+        if nx * ny > 1 {
+            let (w, h) = (width / nx as f64, height / ny as f64);
+            for j in 0..ny {
+                for i in 0..nx {
+                    segments.push(rect(x0 + i as f64 * w, y0 + j as f64 * h, w, h));
+                }
+            }
+        }
+        Detector::new(id, name, rect(x0, y0, width, height), segments)
+    }
+
+    /// Check if a focal-plane position falls on the detector
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Focal-plane `x` coordinate in degrees
+    /// * `y` - Focal-plane `y` coordinate in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the position is on the detector, false otherwise
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        planar_contains(&self.corners, x, y)
+    }
+
+    /// Find the segment of the detector a focal-plane position falls on
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Focal-plane `x` coordinate in degrees
+    /// * `y` - Focal-plane `y` coordinate in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - Index of the segment, or None if the position is not on any segment
+    pub fn segment_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.segments.iter().position(|segment| planar_contains(segment, x, y))
+    }
+}
+
+/// Check if a point is inside a planar polygon (ray casting)
+fn planar_contains(polygon: &[(f64, f64)], x: f64, y: f64) -> bool {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (xi, yi) = polygon[i];
+        let (xj, yj) = polygon[(i + n - 1) % n];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Camera struct
+///
+/// This struct represents the geometry of a camera, as a list of detectors in focal-plane coordinates.
+///
+/// # Attributes
+///
+/// * `name` - Name of the camera
+/// * `detectors` - Detectors of the camera
+///
+/// # Methods
+///
+/// * `new` - Create a new Camera
+/// * `ztf` - Create the Zwicky Transient Facility camera
+/// * `lsstcam` - Create the Vera C. Rubin Observatory LSST camera
+/// * `detector_at` - Find the detector (and segment) at a focal-plane position
+/// * `point` - Point the camera at a position on the sky
+///
+/// # Examples
+///
+/// ```
+/// use flare::camera::Camera;
+///
+/// let camera = Camera::ztf();
+/// assert_eq!(camera.detectors.len(), 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Camera {
+    pub name: String,
+    pub detectors: Vec<Detector>,
+}
+
+impl Camera {
+    /// Create a new Camera
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the camera
+    /// * `detectors` - Detectors of the camera
+    ///
+    /// # Returns
+    ///
+    /// * `Camera` - A new Camera object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::{Camera, Detector};
+    ///
+    /// let camera = Camera::new("single", vec![Detector::rectangle(1, "CCD", (0.0, 0.0), 1.0, 1.0, 1, 1)]);
+    /// assert_eq!(camera.name, "single");
+    /// ```
+    pub fn new(name: &str, detectors: Vec<Detector>) -> Camera {
+        Camera { name: name.to_string(), detectors }
+    }
+
+    /// Create the Zwicky Transient Facility (ZTF) camera
+    ///
+    /// # Returns
+    ///
+    /// * `Camera` - The ZTF camera, with 16 CCDs (ids 1 to 16) split in 4 readout quadrants each
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Camera;
+    ///
+    /// let camera = Camera::ztf();
+    /// let (ccd, quadrant) = camera.detector_at(0.5, 0.5).unwrap();
+    /// assert_eq!(ccd.id, 6);
+    /// assert_eq!(quadrant, Some(2)); // South-West quadrant, ZTF quadrant id 3
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The layout is the nominal one: a 4x4 mosaic of 6144x6160 pixel CCDs at 1.012"/pixel, covering
+    /// about 7.5 x 7.3 degrees. CCDs are numbered row by row from the North-East corner (at zero rotation),
+    /// going West then South. Quadrant indices 0 to 3 correspond to ZTF quadrant ids 1 to 4, in
+    /// counter-clockwise order starting from the North-East quadrant of each CCD, so that the ZTF readout
+    /// channel is `4 * (ccd - 1) + quadrant`.
+    pub fn ztf() -> Camera {
+        let width = 6144.0 * 1.012 / 3600.0;
+        let height = 6160.0 * 1.012 / 3600.0;
+        let (gap_x, gap_y) = ((7.5 - 4.0 * width) / 3.0, (7.3 - 4.0 * height) / 3.0);
+        let mut detectors = Vec::with_capacity(16);
+        for row in 0..4 {
+            for col in 0..4 {
+                let x = (1.5 - col as f64) * (width + gap_x);
+                let y = (1.5 - row as f64) * (height + gap_y);
+                let id = 4 * row + col + 1;
+                let (x0, y0) = (x - width / 2.0, y - height / 2.0);
+                let (w, h) = (width / 2.0, height / 2.0);
+                let quadrant = |qx: f64, qy: f64| vec![(qx, qy), (qx + w, qy), (qx + w, qy + h), (qx, qy + h)];
+                let segments = vec![
+                    quadrant(x, y),
+                    quadrant(x0, y),
+                    quadrant(x0, y0),
+                    quadrant(x, y0),
+                ];
+                let corners = vec![(x0, y0), (x0 + width, y0), (x0 + width, y0 + height), (x0, y0 + height)];
+                detectors.push(Detector::new(id, &format!("CCD{:02}", id), corners, segments));
+            }
+        }
+        Camera::new("ZTF", detectors)
+    }
+
+    /// Create the Vera C. Rubin Observatory LSST camera (LSSTCam)
+    ///
+    /// # Returns
+    ///
+    /// * `Camera` - LSSTCam, with its 189 science CCDs (ids 0 to 188) split in 16 amplifiers each
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Camera;
+    ///
+    /// let camera = Camera::lsstcam();
+    /// assert_eq!(camera.detectors.len(), 189);
+    /// let (ccd, _) = camera.detector_at(0.0, 0.0).unwrap();
+    /// assert_eq!(ccd.name, "R22_S11");
+    /// assert_eq!(ccd.id, 94);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The layout is the nominal one: 21 rafts of 3x3 CCDs of 4072x4000 pixels at 0.2"/pixel, on a
+    /// 0.7056 degree raft pitch, for a total of about 9.6 square degrees. Detectors are named `Rxy_Sxy`
+    /// and numbered as in the Rubin software. Wavefront and guider sensors are not included.
+    /// Amplifiers are numbered row by row from the `-x, -y` corner, 8 along `x` and 2 along `y`.
+    pub fn lsstcam() -> Camera {
+        let raft_pitch = 0.7056;
+        let ccd_pitch = raft_pitch / 3.0;
+        let width = 4072.0 * 0.2 / 3600.0;
+        let height = 4000.0 * 0.2 / 3600.0;
+        let mut detectors = Vec::with_capacity(189);
+        let mut id = 0;
+        for raft_row in 0..5 {
+            for raft_col in 0..5 {
+                if (raft_row == 0 || raft_row == 4) && (raft_col == 0 || raft_col == 4) {
+                    continue;
+                }
+                for sensor_row in 0..3 {
+                    for sensor_col in 0..3 {
+                        let x = (raft_col as f64 - 2.0) * raft_pitch + (sensor_col as f64 - 1.0) * ccd_pitch;
+                        let y = (raft_row as f64 - 2.0) * raft_pitch + (sensor_row as f64 - 1.0) * ccd_pitch;
+                        let name = format!("R{}{}_S{}{}", raft_row, raft_col, sensor_row, sensor_col);
+                        detectors.push(Detector::rectangle(id, &name, (x, y), width, height, 8, 2));
+                        id += 1;
+                    }
+                }
+            }
+        }
+        Camera::new("LSSTCam", detectors)
+    }
+
+    /// Find the detector (and segment) at a focal-plane position
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Focal-plane `x` coordinate in degrees
+    /// * `y` - Focal-plane `y` coordinate in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(&Detector, Option<usize>)>` - The detector and the index of the segment, or None
+    ///   if the position falls in a gap or outside of the field of view
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Camera;
+    ///
+    /// let camera = Camera::ztf();
+    /// assert!(camera.detector_at(0.0, 0.0).is_none()); // the center of ZTF's field is a gap
+    /// assert!(camera.detector_at(5.0, 0.5).is_none()); // outside of the field of view
+    /// ```
+    pub fn detector_at(&self, x: f64, y: f64) -> Option<(&Detector, Option<usize>)> {
+        self.detectors
+            .iter()
+            .find(|detector| detector.contains(x, y))
+            .map(|detector| (detector, detector.segment_at(x, y)))
+    }
+
+    /// Point the camera at a position on the sky
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the pointing center in degrees
+    /// * `dec` - Declination of the pointing center in degrees
+    /// * `rotation` - Rotator angle in degrees: position angle (East of North) of the focal-plane `y` axis
+    ///
+    /// # Returns
+    ///
+    /// * `Pointing` - A Pointing object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Camera;
+    ///
+    /// let camera = Camera::ztf();
+    /// let pointing = camera.point(150.0, 2.0, 0.0);
+    /// assert_eq!(pointing.footprint().len(), 16);
+    /// ```
+    pub fn point(&self, ra: f64, dec: f64, rotation: f64) -> Pointing<'_> {
+        Pointing { camera: self, ra, dec, rotation }
+    }
+}
+
+/// ChipPosition struct
+///
+/// This struct represents the position of a target on a camera's focal plane.
+///
+/// # Attributes
+///
+/// * `detector` - The detector the target falls on
+/// * `segment` - The index of the detector segment (quadrant, amplifier) the target falls on, if any
+/// * `x` - Focal-plane `x` coordinate of the target in degrees
+/// * `y` - Focal-plane `y` coordinate of the target in degrees
+#[derive(Debug, Clone)]
+pub struct ChipPosition<'a> {
+    pub detector: &'a Detector,
+    pub segment: Option<usize>,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Pointing struct
+///
+/// This struct represents a camera pointed at a position on the sky, with a given rotator angle.
+///
+/// # Attributes
+///
+/// * `camera` - The camera
+/// * `ra` - Right ascension of the pointing center in degrees
+/// * `dec` - Declination of the pointing center in degrees
+/// * `rotation` - Rotator angle in degrees: position angle (East of North) of the focal-plane `y` axis
+///
+/// # Methods
+///
+/// * `focal_plane_position` - Project a position on the sky onto the focal plane
+/// * `sky_position` - Project a focal-plane position onto the sky
+/// * `footprint` - Project the detectors onto the sky
+/// * `which_chip` - Find the detector a target falls on
+///
+/// # Examples
+///
+/// ```
+/// use flare::camera::Camera;
+/// use flare::Target;
+///
+/// let camera = Camera::ztf();
+/// let pointing = camera.point(359.0, 0.0, 0.0);
+///
+/// // a target on the other side of RA = 0, North-East of the pointing center
+/// let target = Target::new(0.3, 0.5, None);
+/// let position = pointing.which_chip(&target).unwrap();
+/// assert_eq!(position.detector.id, 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pointing<'a> {
+    pub camera: &'a Camera,
+    pub ra: f64,
+    pub dec: f64,
+    pub rotation: f64,
+}
+
+impl<'a> Pointing<'a> {
+    /// Project a position on the sky onto the focal plane
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the position in degrees
+    /// * `dec` - Declination of the position in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(f64, f64)>` - Focal-plane coordinates in degrees, or None if the position
+    ///   is more than 90 degrees away from the pointing center
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Camera;
+    ///
+    /// let camera = Camera::ztf();
+    /// // with a 90 degree rotation, the focal-plane y axis points East
+    /// let pointing = camera.point(10.0, 0.0, 90.0);
+    /// let (x, y) = pointing.focal_plane_position(11.0, 0.0).unwrap();
+    /// assert!(x.abs() < 1e-9);
+    /// assert!((y - 1.0).abs() < 1e-3);
+    /// ```
+    pub fn focal_plane_position(&self, ra: f64, dec: f64) -> Option<(f64, f64)> {
+        let (xi, eta) = sky2tangent(self.ra, self.dec, ra, dec)?;
+        let (sin, cos) = (self.rotation * DEGRA).sin_cos();
+        Some((xi * cos - eta * sin, xi * sin + eta * cos))
+    }
+
+    /// Project a focal-plane position onto the sky
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Focal-plane `x` coordinate in degrees
+    /// * `y` - Focal-plane `y` coordinate in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - RA and DEC of the position in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Camera;
+    ///
+    /// let camera = Camera::ztf();
+    /// let pointing = camera.point(10.0, 89.0, 30.0);
+    /// let (ra, dec) = pointing.sky_position(1.0, -2.0);
+    /// let (x, y) = pointing.focal_plane_position(ra, dec).unwrap();
+    /// assert!((x - 1.0).abs() < 1e-9 && (y + 2.0).abs() < 1e-9);
+    /// ```
+    pub fn sky_position(&self, x: f64, y: f64) -> (f64, f64) {
+        let (sin, cos) = (self.rotation * DEGRA).sin_cos();
+        tangent2sky(self.ra, self.dec, x * cos + y * sin, -x * sin + y * cos)
+    }
+
+    /// Project the detectors onto the sky
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SkyPolygon>` - One polygon per detector, in the same order as the camera's detectors
+    ///
+    /// # Notes
+    ///
+    /// Straight lines on the focal plane project onto great circles, so the polygons are exact
+    /// for the (distortion-free) gnomonic projection used here.
+    pub fn footprint(&self) -> Vec<SkyPolygon> {
+        self.camera
+            .detectors
+            .iter()
+            .map(|detector| {
+                let corners = detector
+                    .corners
+                    .iter()
+                    .map(|&(x, y)| self.sky_position(x, y))
+                    .collect::<Vec<(f64, f64)>>();
+                SkyPolygon::new(&corners)
+            })
+            .collect()
+    }
+
+    /// Find the detector a target falls on
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target
+    ///
+    /// # Returns
+    ///
+    /// * `Option<ChipPosition>` - The detector, segment and focal-plane position of the target,
+    ///   or None if the target falls in a gap or outside of the field of view
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::camera::Camera;
+    /// use flare::Target;
+    ///
+    /// let camera = Camera::lsstcam();
+    /// let pointing = camera.point(53.1, -28.1, 0.0);
+    ///
+    /// let target = Target::new(53.1, -28.1, None);
+    /// let position = pointing.which_chip(&target).unwrap();
+    /// assert_eq!(position.detector.name, "R22_S11");
+    ///
+    /// let target = Target::new(60.0, -28.1, None);
+    /// assert!(pointing.which_chip(&target).is_none());
+    /// ```
+    pub fn which_chip(&self, target: &Target) -> Option<ChipPosition<'a>> {
+        let (x, y) = self.focal_plane_position(target.ra, target.dec)?;
+        self.camera
+            .detector_at(x, y)
+            .map(|(detector, segment)| ChipPosition { detector, segment, x, y })
+    }
+}
@@ -1,3 +1,5 @@
+use crate::spatial::DEGRA;
+
 /// Calculate the refraction correction for a given true altitude.
 /// 
 /// # Arguments
@@ -28,4 +30,186 @@ pub fn refraction(h: f64) -> f64 {
     }
 
     (1.02 / (h + (10.3 / (h + 5.11))).to_radians().tan()) / 60.0
-}
\ No newline at end of file
+}
+/// Atmosphere struct
+///
+/// This struct represents the atmospheric conditions at an observing site, used to compute the refraction.
+///
+/// # Attributes
+///
+/// * `pressure` - Pressure at the observer in hPa
+/// * `temperature` - Ambient temperature at the observer in degrees Celsius
+/// * `humidity` - Relative humidity at the observer, in [0, 1]
+/// * `wavelength` - Effective wavelength of the observations in micrometers
+///
+/// # Examples
+///
+/// ```
+/// use flare::corrections::Atmosphere;
+///
+/// let atmosphere = Atmosphere::new(800.0, 10.0, 0.9, 0.4);
+/// let (a, b) = atmosphere.refraction_constants();
+/// assert!((a - 0.2264949956241415009e-3).abs() < 1e-15);
+/// assert!((b + 0.2598658261729343970e-6).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atmosphere {
+    pub pressure: f64,
+    pub temperature: f64,
+    pub humidity: f64,
+    pub wavelength: f64,
+}
+
+impl Atmosphere {
+    /// Create a new Atmosphere
+    ///
+    /// # Arguments
+    ///
+    /// * `pressure` - Pressure at the observer in hPa (0 to ignore refraction)
+    /// * `temperature` - Ambient temperature at the observer in degrees Celsius
+    /// * `humidity` - Relative humidity at the observer, in [0, 1]
+    /// * `wavelength` - Effective wavelength of the observations in micrometers (above 100 for radio)
+    ///
+    /// # Returns
+    ///
+    /// * `Atmosphere` - A new Atmosphere object
+    pub fn new(pressure: f64, temperature: f64, humidity: f64, wavelength: f64) -> Atmosphere {
+        Atmosphere { pressure, temperature, humidity, wavelength }
+    }
+
+    /// Create a new Atmosphere, estimating the pressure from the elevation of the site
+    ///
+    /// # Arguments
+    ///
+    /// * `elevation` - Elevation of the observer in meters
+    /// * `temperature` - Ambient temperature at the observer in degrees Celsius
+    /// * `humidity` - Relative humidity at the observer, in [0, 1]
+    /// * `wavelength` - Effective wavelength of the observations in micrometers
+    ///
+    /// # Returns
+    ///
+    /// * `Atmosphere` - A new Atmosphere object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::corrections::Atmosphere;
+    ///
+    /// let sea_level = Atmosphere::from_elevation(0.0, 10.0, 0.5, 0.55);
+    /// assert_eq!(sea_level.pressure, 1013.25);
+    ///
+    /// // Palomar, at 1870 m
+    /// let palomar = Atmosphere::from_elevation(1870.0, 10.0, 0.5, 0.55);
+    /// assert!((palomar.pressure - 808.8).abs() < 0.1);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The pressure is 1013.25 hPa * exp(-elevation / (29.3 * T)), with T the temperature in Kelvin,
+    /// the approximation recommended by the ERFA/SOFA `refco` documentation.
+    pub fn from_elevation(elevation: f64, temperature: f64, humidity: f64, wavelength: f64) -> Atmosphere {
+        let pressure = 1013.25 * (-elevation / (29.3 * (temperature + 273.15))).exp();
+        Atmosphere::new(pressure, temperature, humidity, wavelength)
+    }
+
+    /// Calculate the refraction constants A and B of the model ζ_true - ζ_apparent = A tan ζ_apparent + B tan³ ζ_apparent
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - The refraction constants A and B, in radians
+    ///
+    /// # References
+    ///
+    /// This is the model of the ERFA/SOFA `refco` function (Green 1987, "Spherical Astronomy", with
+    /// the refractive index of Stone 1996 for optical/IR wavelengths, and of Rueger 2002 for radio wavelengths).
+    pub fn refraction_constants(&self) -> (f64, f64) {
+        let optic = self.wavelength <= 100.0;
+        let t = self.temperature.clamp(-150.0, 200.0);
+        let p = self.pressure.clamp(0.0, 10000.0);
+        let r = self.humidity.clamp(0.0, 1.0);
+        let w = self.wavelength.clamp(0.1, 1e6);
+
+        // water vapour pressure at the observer
+        let pw = if p > 0.0 {
+            let ps = 10f64.powf((0.7859 + 0.03477 * t) / (1.0 + 0.00412 * t)) * (1.0 + p * (4.5e-6 + 6e-10 * t * t));
+            r * ps / (1.0 - (1.0 - r) * ps / p)
+        } else {
+            0.0
+        };
+
+        // refractive index minus 1 at the observer
+        let tk = t + 273.15;
+        let gamma = if optic {
+            let wlsq = w * w;
+            ((77.53484e-6 + (4.39108e-7 + 3.666e-9 / wlsq) / wlsq) * p - 11.2684e-6 * pw) / tk
+        } else {
+            (77.6890e-6 * p - (6.3938e-6 - 0.375463 / tk) * pw) / tk
+        };
+
+        // formula for beta from Stone, with empirical adjustments
+        let mut beta = 4.4474e-6 * tk;
+        if !optic {
+            beta -= 0.0074 * pw * beta;
+        }
+
+        (gamma * (1.0 - beta), -gamma * (beta - gamma / 2.0))
+    }
+
+    /// Convert a true (geometric) altitude to an apparent (refracted) altitude
+    ///
+    /// # Arguments
+    ///
+    /// * `alt` - True altitude in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Apparent altitude in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::corrections::Atmosphere;
+    ///
+    /// let atmosphere = Atmosphere::new(1013.25, 10.0, 0.0, 0.55);
+    /// let apparent = atmosphere.apparent_altitude(45.0);
+    /// // about one arcminute of refraction at 45 degrees
+    /// assert!(((apparent - 45.0) * 60.0 - 0.98).abs() < 0.02);
+    /// assert!((atmosphere.true_altitude(apparent) - 45.0).abs() < 1e-6);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The model is accurate for altitudes above about 15 degrees, and remains well-behaved down to and below the horizon,
+    /// where the tangent of the zenith distance is capped as in ERFA (but the refraction is then underestimated).
+    pub fn apparent_altitude(&self, alt: f64) -> f64 {
+        let (a, b) = self.refraction_constants();
+        let (tz, cz) = capped_zenith_tangent(alt);
+        let w = b * tz * tz;
+        // one Newton-Raphson step of the inverse of the apparent -> true model
+        let delta = (a + w) * tz / (1.0 + (a + 3.0 * w) / (cz * cz));
+        alt + delta / DEGRA
+    }
+
+    /// Convert an apparent (refracted) altitude to a true (geometric) altitude
+    ///
+    /// # Arguments
+    ///
+    /// * `alt` - Apparent altitude in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - True altitude in degrees
+    pub fn true_altitude(&self, alt: f64) -> f64 {
+        let (a, b) = self.refraction_constants();
+        let (tz, _) = capped_zenith_tangent(alt);
+        alt - (a + b * tz * tz) * tz / DEGRA
+    }
+}
+
+/// Tangent and cosine of the zenith distance for an altitude in degrees,
+/// with the cosine kept above 0.05 (altitude of about 2.9 degrees) to avoid the divergence at the horizon
+fn capped_zenith_tangent(alt: f64) -> (f64, f64) {
+    let (sin_alt, cos_alt) = (alt * DEGRA).sin_cos();
+    let cz = sin_alt.max(0.05);
+    (cos_alt.max(1e-6) / cz, cz)
+}
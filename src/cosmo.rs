@@ -111,10 +111,9 @@ impl <'a> Cosmo<'a> {
             1.0 / (self.omega_m * (1.0 + z).powi(3) + self.omega_k * (1.0 + z).powi(2) + self.omega_lambda).sqrt()
         };
         let d_h = C / self.h0;
-        let d_c = d_h * integrate(&integrand, 0.0, redshift, 1000);
+        let d_c = d_h * integrate(integrand, 0.0, redshift, 1000);
         let d_m = d_c / (1.0 + redshift);
-        let d_lum = (1.0 + redshift).powi(2) * d_m;
-        d_lum
+        (1.0 + redshift).powi(2) * d_m
     }
 
     /// Calculate the distance modulus from the redshift
@@ -0,0 +1,256 @@
+use crate::altaz::hadec2altaz;
+use crate::corrections::Atmosphere;
+use crate::observer::Observer;
+use crate::target::Target;
+use crate::time::Time;
+
+/// Band struct
+///
+/// This struct represents a photometric band, approximated by a top-hat throughput.
+///
+/// # Attributes
+///
+/// * `name` - Name of the band
+/// * `min_wavelength` - Blue edge of the band in micrometers
+/// * `max_wavelength` - Red edge of the band in micrometers
+///
+/// # Examples
+///
+/// ```
+/// use flare::dcr::{Band, ZTF_R};
+///
+/// let band = Band::new("custom", 0.5, 0.6);
+/// assert!((band.center() - 0.55).abs() < 1e-12);
+/// assert_eq!(ZTF_R.name, "ztfr");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Band<'a> {
+    pub name: &'a str,
+    pub min_wavelength: f64,
+    pub max_wavelength: f64,
+}
+
+/// ZTF g band (top-hat approximation)
+pub const ZTF_G: Band<'static> = Band { name: "ztfg", min_wavelength: 0.410, max_wavelength: 0.555 };
+/// ZTF r band (top-hat approximation)
+pub const ZTF_R: Band<'static> = Band { name: "ztfr", min_wavelength: 0.560, max_wavelength: 0.730 };
+/// ZTF i band (top-hat approximation)
+pub const ZTF_I: Band<'static> = Band { name: "ztfi", min_wavelength: 0.700, max_wavelength: 0.880 };
+/// Rubin (LSST) u band (top-hat approximation)
+pub const LSST_U: Band<'static> = Band { name: "lsstu", min_wavelength: 0.320, max_wavelength: 0.400 };
+/// Rubin (LSST) g band (top-hat approximation)
+pub const LSST_G: Band<'static> = Band { name: "lsstg", min_wavelength: 0.400, max_wavelength: 0.552 };
+/// Rubin (LSST) r band (top-hat approximation)
+pub const LSST_R: Band<'static> = Band { name: "lsstr", min_wavelength: 0.552, max_wavelength: 0.691 };
+/// Rubin (LSST) i band (top-hat approximation)
+pub const LSST_I: Band<'static> = Band { name: "lssti", min_wavelength: 0.691, max_wavelength: 0.818 };
+/// Rubin (LSST) z band (top-hat approximation)
+pub const LSST_Z: Band<'static> = Band { name: "lsstz", min_wavelength: 0.818, max_wavelength: 0.922 };
+/// Rubin (LSST) y band (top-hat approximation)
+pub const LSST_Y: Band<'static> = Band { name: "lssty", min_wavelength: 0.948, max_wavelength: 1.060 };
+
+impl<'a> Band<'a> {
+    /// Create a new Band
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the band
+    /// * `min_wavelength` - Blue edge of the band in micrometers
+    /// * `max_wavelength` - Red edge of the band in micrometers
+    ///
+    /// # Returns
+    ///
+    /// * `Band` - A new Band object
+    pub fn new(name: &'a str, min_wavelength: f64, max_wavelength: f64) -> Band<'a> {
+        if min_wavelength <= 0.0 || min_wavelength >= max_wavelength {
+            panic!("Invalid wavelength range: [{}, {}]", min_wavelength, max_wavelength);
+        }
+        Band { name, min_wavelength, max_wavelength }
+    }
+
+    /// Get the central wavelength of the band, in micrometers
+    pub fn center(&self) -> f64 {
+        (self.min_wavelength + self.max_wavelength) / 2.0
+    }
+
+    /// Calculate the effective wavelength of a source in the band, weighted by the number of detected photons
+    ///
+    /// # Arguments
+    ///
+    /// * `spectral_index` - Index β of the spectrum of the source, f_λ ∝ λ^β
+    ///   (-2 for a flat spectrum in f_ν, which has a color of 0 in AB magnitudes)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The effective wavelength in micrometers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::dcr::LSST_G;
+    ///
+    /// // a blue source is bluer than a red one in the same band
+    /// assert!(LSST_G.effective_wavelength(-3.0) < LSST_G.effective_wavelength(0.0));
+    /// ```
+    pub fn effective_wavelength(&self, spectral_index: f64) -> f64 {
+        // photon counts are proportional to f_λ λ, the effective wavelength is their mean wavelength
+        let integral = |power: f64| {
+            if power.abs() < 1e-12 {
+                (self.max_wavelength / self.min_wavelength).ln()
+            } else {
+                (self.max_wavelength.powf(power) - self.min_wavelength.powf(power)) / power
+            }
+        };
+        integral(spectral_index + 3.0) / integral(spectral_index + 2.0)
+    }
+}
+
+/// Calculate the spectral index of a source from a color, assuming a power-law spectrum
+///
+/// # Arguments
+///
+/// * `color` - Color of the source (blue band minus red band) in AB magnitudes
+/// * `blue` - The blue band
+/// * `red` - The red band
+///
+/// # Returns
+///
+/// * `f64` - The spectral index β of the source, f_λ ∝ λ^β
+///
+/// # Examples
+///
+/// ```
+/// use flare::dcr::{spectral_index, ZTF_G, ZTF_R};
+///
+/// // a color of 0 (AB) is a flat spectrum in f_ν
+/// assert!((spectral_index(0.0, &ZTF_G, &ZTF_R) + 2.0).abs() < 1e-12);
+/// // red sources have larger spectral indices
+/// assert!(spectral_index(1.0, &ZTF_G, &ZTF_R) > -2.0);
+/// ```
+pub fn spectral_index(color: f64, blue: &Band, red: &Band) -> f64 {
+    // f_ν ∝ λ^(β + 2), and color = -2.5 log10(f_ν(blue) / f_ν(red))
+    -color / (2.5 * (blue.center() / red.center()).log10()) - 2.0
+}
+
+/// DcrOffset struct
+///
+/// This struct represents the differential chromatic refraction (DCR) offset of a source with respect to a reference.
+///
+/// # Attributes
+///
+/// * `magnitude` - Offset along the direction of the zenith in arcseconds (positive if the source is closer to the zenith)
+/// * `parallactic_angle` - Parallactic angle of the source in degrees: the position angle (East of North) of the direction of the zenith
+/// * `ra_offset` - Offset towards the East in arcseconds (ΔRA cos DEC)
+/// * `dec_offset` - Offset towards the North in arcseconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct DcrOffset {
+    pub magnitude: f64,
+    pub parallactic_angle: f64,
+    pub ra_offset: f64,
+    pub dec_offset: f64,
+}
+
+/// Calculate the differential chromatic refraction (DCR) offset between two effective wavelengths
+///
+/// # Arguments
+///
+/// * `target` - The source
+/// * `observer` - Observer object representing the observer
+/// * `time` - Time object representing the time of the observation
+/// * `atmosphere` - Atmospheric conditions at the observer (its wavelength is ignored)
+/// * `wavelength` - Effective wavelength of the source in micrometers
+/// * `reference_wavelength` - Effective wavelength of the reference (e.g. the template) in micrometers
+///
+/// # Returns
+///
+/// * `DcrOffset` - The offset of the source with respect to the reference
+///
+/// # Examples
+///
+/// ```
+/// use flare::dcr::dcr_offset;
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(-30.2446, -70.7494, 2663.0, Some("Rubin"));
+/// let atmosphere = observer.atmosphere(10.0, 0.2, 0.5);
+/// let target = Target::new(150.0, -20.0, None);
+/// let time = Time::new(2025, 3, 1, 3, 0, 0);
+///
+/// // blue light is refracted more than red light
+/// let offset = dcr_offset(&target, &observer, &time, &atmosphere, 0.45, 0.55);
+/// assert!(offset.magnitude > 0.0 && offset.magnitude < 1.0);
+/// let length = (offset.ra_offset.powi(2) + offset.dec_offset.powi(2)).sqrt();
+/// assert!((length - offset.magnitude).abs() < 1e-12);
+/// ```
+pub fn dcr_offset(
+    target: &Target,
+    observer: &Observer,
+    time: &Time,
+    atmosphere: &Atmosphere,
+    wavelength: f64,
+    reference_wavelength: f64,
+) -> DcrOffset {
+    let (alt, _) = hadec2altaz(target.hour_angle(observer, time), target.dec, observer.lat);
+    let at_wavelength = |wavelength: f64| Atmosphere { wavelength, ..atmosphere.clone() }.apparent_altitude(alt);
+    let magnitude = (at_wavelength(wavelength) - at_wavelength(reference_wavelength)) * 3600.0;
+    let parallactic_angle = target.parallactic_angle(observer, time);
+    let (sin_q, cos_q) = parallactic_angle.to_radians().sin_cos();
+    DcrOffset {
+        magnitude,
+        parallactic_angle,
+        ra_offset: magnitude * sin_q,
+        dec_offset: magnitude * cos_q,
+    }
+}
+
+/// Calculate the differential chromatic refraction (DCR) offset in a band between a source and a reference of different colors
+///
+/// # Arguments
+///
+/// * `target` - The source
+/// * `observer` - Observer object representing the observer
+/// * `time` - Time object representing the time of the observation
+/// * `atmosphere` - Atmospheric conditions at the observer (its wavelength is ignored)
+/// * `band` - The band of the observation
+/// * `spectral_index` - Spectral index of the source (see `spectral_index`)
+/// * `reference_spectral_index` - Spectral index of the reference (e.g. the template, or the stars used for the astrometry)
+///
+/// # Returns
+///
+/// * `DcrOffset` - The offset of the source with respect to the reference
+///
+/// # Examples
+///
+/// ```
+/// use flare::dcr::{band_dcr_offset, spectral_index, LSST_G, LSST_R};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(-30.2446, -70.7494, 2663.0, Some("Rubin"));
+/// let atmosphere = observer.atmosphere(10.0, 0.2, 0.5);
+/// let target = Target::new(150.0, -20.0, None);
+/// let time = Time::new(2025, 3, 1, 3, 0, 0);
+///
+/// // a blue transient (g - r = -0.3) on a red galaxy (g - r = 0.8), observed in g
+/// let source = spectral_index(-0.3, &LSST_G, &LSST_R);
+/// let template = spectral_index(0.8, &LSST_G, &LSST_R);
+/// let offset = band_dcr_offset(&target, &observer, &time, &atmosphere, &LSST_G, source, template);
+/// assert!(offset.magnitude > 0.0 && offset.magnitude < 0.1);
+/// ```
+pub fn band_dcr_offset(
+    target: &Target,
+    observer: &Observer,
+    time: &Time,
+    atmosphere: &Atmosphere,
+    band: &Band,
+    spectral_index: f64,
+    reference_spectral_index: f64,
+) -> DcrOffset {
+    dcr_offset(
+        target,
+        observer,
+        time,
+        atmosphere,
+        band.effective_wavelength(spectral_index),
+        band.effective_wavelength(reference_spectral_index),
+    )
+}
@@ -0,0 +1,289 @@
+use crate::spatial::{great_circle_distance, DEGRA};
+use crate::sun::position_j2000;
+use crate::time::Time;
+use crate::vector::Rotation3;
+
+/// Obliquity of the ecliptic at J2000.0 (IAU 1980), in degrees
+pub const OBLIQUITY_J2000: f64 = 23.4392911;
+
+/// EclipticFrame enum
+///
+/// This enum represents the ecliptic coordinate frames supported by flare.
+///
+/// # Variants
+///
+/// * `J2000` - Mean ecliptic and equinox of J2000.0
+/// * `MeanOfDate` - Mean ecliptic and equinox of a given date (precession only)
+/// * `TrueOfDate` - True ecliptic and equinox of a given date (precession and nutation)
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::{EclipticFrame, radec2ecliptic};
+/// use flare::Time;
+///
+/// let frame = EclipticFrame::MeanOfDate(Time::new(2050, 1, 1, 0, 0, 0));
+/// let (lon, _) = radec2ecliptic(0.0, 0.0, &frame);
+/// // the equinox moves by about 50" per year
+/// assert!((lon - 50.0 * 50.29 / 3600.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone)]
+pub enum EclipticFrame {
+    J2000,
+    MeanOfDate(Time),
+    TrueOfDate(Time),
+}
+
+/// Julian centuries since J2000.0
+pub(crate) fn centuries(time: &Time) -> f64 {
+    (time.to_jd() - 2451545.0) / 36525.0
+}
+
+/// Calculate the mean obliquity of the ecliptic at a given time
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `f64` - The mean obliquity of the ecliptic in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::mean_obliquity;
+/// use flare::Time;
+///
+/// let time = Time::new(1987, 4, 10, 0, 0, 0);
+/// let epsilon = mean_obliquity(&time);
+/// assert!((epsilon - 23.440946).abs() < 1e-6);
+/// ```
+///
+/// # References
+///
+/// formula 22.2 of "Astronomical Algorithms" 2nd edition by Jean Meeus (Willmann-Bell, Richmond) 1998.
+pub fn mean_obliquity(time: &Time) -> f64 {
+    let t = centuries(time);
+    OBLIQUITY_J2000 + (-46.8150 * t - 0.00059 * t * t + 0.001813 * t * t * t) / 3600.0
+}
+
+/// Calculate the nutation in longitude and in obliquity at a given time
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `(f64, f64)` - The nutation in longitude and in obliquity, in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::nutation;
+/// use flare::Time;
+///
+/// let time = Time::new(1987, 4, 10, 0, 0, 0);
+/// let (dpsi, deps) = nutation(&time);
+/// assert!((dpsi * 3600.0 + 3.788).abs() < 0.5);
+/// assert!((deps * 3600.0 - 9.443).abs() < 0.1);
+/// ```
+///
+/// # Notes
+///
+/// This uses the 4 main terms of the IAU 1980 theory, accurate to 0.5" in longitude and 0.1" in obliquity
+/// (chapter 22 of "Astronomical Algorithms" 2nd edition by Jean Meeus).
+pub fn nutation(time: &Time) -> (f64, f64) {
+    let t = centuries(time);
+    let omega = (125.04452 - 1934.136261 * t + 0.0020708 * t * t + t * t * t / 450000.0) * DEGRA;
+    let l_sun = (280.4665 + 36000.7698 * t) * DEGRA;
+    let l_moon = (218.3165 + 481267.8813 * t) * DEGRA;
+    let dpsi = -17.20 * omega.sin() - 1.32 * (2.0 * l_sun).sin() - 0.23 * (2.0 * l_moon).sin()
+        + 0.21 * (2.0 * omega).sin();
+    let deps = 9.20 * omega.cos() + 0.57 * (2.0 * l_sun).cos() + 0.10 * (2.0 * l_moon).cos()
+        - 0.09 * (2.0 * omega).cos();
+    (dpsi / 3600.0, deps / 3600.0)
+}
+
+/// Calculate the true obliquity of the ecliptic (mean obliquity plus nutation) at a given time
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `f64` - The true obliquity of the ecliptic in degrees
+pub fn true_obliquity(time: &Time) -> f64 {
+    mean_obliquity(time) + nutation(time).1
+}
+
+/// Rotation from the J2000 mean equator and equinox to the mean equator and equinox of a date
+/// (IAU 1976 precession, formulas 21.3 of Meeus)
+pub fn precession(time: &Time) -> Rotation3 {
+    let t = centuries(time);
+    let zeta = (2306.2181 * t + 0.30188 * t * t + 0.017998 * t * t * t) / 3600.0;
+    let z = (2306.2181 * t + 1.09468 * t * t + 0.018203 * t * t * t) / 3600.0;
+    let theta = (2004.3109 * t - 0.42665 * t * t - 0.041833 * t * t * t) / 3600.0;
+    Rotation3::about_z(-zeta)
+        .then(&Rotation3::about_y(theta))
+        .then(&Rotation3::about_z(-z))
+}
+
+/// Rotation from J2000 equatorial coordinates to an ecliptic frame
+pub fn ecliptic_rotation(frame: &EclipticFrame) -> Rotation3 {
+    match frame {
+        EclipticFrame::J2000 => Rotation3::about_x(OBLIQUITY_J2000),
+        EclipticFrame::MeanOfDate(time) => precession(time).then(&Rotation3::about_x(mean_obliquity(time))),
+        EclipticFrame::TrueOfDate(time) => {
+            // nutation in longitude moves the equinox along the ecliptic
+            precession(time)
+                .then(&Rotation3::about_x(mean_obliquity(time)))
+                .then(&Rotation3::about_z(-nutation(time).0))
+        }
+    }
+}
+
+/// Rotation from the J2000 mean equator and equinox to the true equator and equinox of a date
+/// (precession and nutation), the frame of the apparent positions of the Sun and the Moon
+pub(crate) fn true_equator_rotation(time: &Time) -> Rotation3 {
+    ecliptic_rotation(&EclipticFrame::TrueOfDate(time.clone())).then(&Rotation3::about_x(-true_obliquity(time)))
+}
+
+/// Precess equatorial coordinates from J2000 to the mean equator and equinox of a date
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension (J2000) in degrees
+/// * `dec` - Declination (J2000) in degrees
+/// * `time` - Time object representing the date
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Right Ascension and Declination of date in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::precess;
+/// use flare::Time;
+///
+/// // theta Persei, example 21.b of Meeus
+/// let time = Time::from_jd(2462088.69);
+/// let (ra, dec) = precess(41.054063, 49.227750, &time);
+/// assert!((ra - 41.547214).abs() < 1e-5);
+/// assert!((dec - 49.348483).abs() < 1e-5);
+/// ```
+pub fn precess(ra: f64, dec: f64, time: &Time) -> (f64, f64) {
+    precession(time).apply_radec(ra, dec)
+}
+
+/// Convert equatorial coordinates (J2000) to ecliptic coordinates
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension (J2000) in degrees
+/// * `dec` - Declination (J2000) in degrees
+/// * `frame` - The ecliptic frame
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Ecliptic longitude (in [0, 360)) and latitude in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::{EclipticFrame, radec2ecliptic};
+///
+/// // Pollux, example 13.a of Meeus
+/// let (lon, lat) = radec2ecliptic(116.328942, 28.026183, &EclipticFrame::J2000);
+/// assert!((lon - 113.215630).abs() < 1e-6);
+/// assert!((lat - 6.684170).abs() < 1e-6);
+/// ```
+pub fn radec2ecliptic(ra: f64, dec: f64, frame: &EclipticFrame) -> (f64, f64) {
+    ecliptic_rotation(frame).apply_radec(ra, dec)
+}
+
+/// Convert ecliptic coordinates to equatorial coordinates (J2000)
+///
+/// # Arguments
+///
+/// * `lon` - Ecliptic longitude in degrees
+/// * `lat` - Ecliptic latitude in degrees
+/// * `frame` - The ecliptic frame
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Right Ascension (J2000, in [0, 360)) and Declination (J2000) in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::{EclipticFrame, ecliptic2radec, radec2ecliptic};
+/// use flare::Time;
+///
+/// let frame = EclipticFrame::TrueOfDate(Time::new(2024, 9, 10, 3, 0, 0));
+/// let (lon, lat) = radec2ecliptic(280.0, -60.0, &frame);
+/// let (ra, dec) = ecliptic2radec(lon, lat, &frame);
+/// assert!((ra - 280.0).abs() < 1e-9 && (dec + 60.0).abs() < 1e-9);
+/// ```
+pub fn ecliptic2radec(lon: f64, lat: f64, frame: &EclipticFrame) -> (f64, f64) {
+    ecliptic_rotation(frame).inverse().apply_radec(lon, lat)
+}
+
+/// Calculate the solar elongation of a position: its angular distance to the Sun
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension (J2000) in degrees
+/// * `dec` - Declination (J2000) in degrees
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `f64` - The solar elongation in degrees, in [0, 180]
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::solar_elongation;
+/// use flare::Time;
+///
+/// // around the September equinox, the Sun is close to RA = 180 degrees
+/// let time = Time::new(2024, 9, 22, 12, 0, 0);
+/// assert!(solar_elongation(180.0, 0.0, &time) < 1.0);
+/// assert!(solar_elongation(0.0, 0.0, &time) > 179.0);
+/// ```
+///
+/// # Notes
+///
+/// The position of the Sun is computed with `sun::position_j2000`, accurate to about 0.01 degree.
+pub fn solar_elongation(ra: f64, dec: f64, time: &Time) -> f64 {
+    let sun = position_j2000(time);
+    great_circle_distance(ra, dec, sun.ra, sun.dec)
+}
+
+/// Check if a position is close to the ecliptic, e.g. to flag candidates likely to be solar system objects
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension (J2000) in degrees
+/// * `dec` - Declination (J2000) in degrees
+/// * `max_latitude` - Maximum absolute ecliptic latitude in degrees
+///
+/// # Returns
+///
+/// * `bool` - True if the absolute ecliptic latitude (J2000) of the position is at most `max_latitude`
+///
+/// # Examples
+///
+/// ```
+/// use flare::ecliptic::is_near_ecliptic;
+///
+/// assert!(is_near_ecliptic(90.0, 23.4, 5.0));
+/// assert!(!is_near_ecliptic(90.0, -10.0, 5.0));
+/// ```
+pub fn is_near_ecliptic(ra: f64, dec: f64, max_latitude: f64) -> bool {
+    radec2ecliptic(ra, dec, &EclipticFrame::J2000).1.abs() <= max_latitude
+}
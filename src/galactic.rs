@@ -0,0 +1,192 @@
+use crate::spatial::{lb2radec, radec2lb, DEGRA};
+use crate::target::Target;
+
+/// Half-width, in Galactic longitude and latitude, of the box used to define the Galactic bulge, in degrees
+pub const BULGE_HALF_WIDTH: f64 = 10.0;
+
+/// Maximum absolute Galactic latitude used to define the Galactic disk, in degrees
+pub const DISK_MAX_LATITUDE: f64 = 10.0;
+
+/// Calculate the angular distance of a position to the Galactic plane
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension in degrees
+/// * `dec` - Declination in degrees
+///
+/// # Returns
+///
+/// * `f64` - The absolute Galactic latitude of the position, in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::galactic::plane_distance;
+/// use flare::spatial::lb2radec;
+///
+/// let (ra, dec) = lb2radec(120.0, -15.0);
+/// assert!((plane_distance(ra, dec) - 15.0).abs() < 1e-6);
+/// ```
+pub fn plane_distance(ra: f64, dec: f64) -> f64 {
+    radec2lb(ra, dec).1.abs()
+}
+
+/// Calculate the angular separation between a position and the Galactic center (l = 0, b = 0)
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension in degrees
+/// * `dec` - Declination in degrees
+///
+/// # Returns
+///
+/// * `f64` - The angular separation in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::galactic::center_separation;
+/// use flare::spatial::lb2radec;
+///
+/// let (ra, dec) = lb2radec(180.0, 0.0);
+/// assert!((center_separation(ra, dec) - 180.0).abs() < 1e-6);
+/// let (ra, dec) = lb2radec(3.0, 4.0);
+/// assert!((center_separation(ra, dec) - 4.998).abs() < 1e-3);
+/// ```
+pub fn center_separation(ra: f64, dec: f64) -> f64 {
+    let (l, b) = radec2lb(ra, dec);
+    let cos_separation = (l * DEGRA).cos() * (b * DEGRA).cos();
+    cos_separation.clamp(-1.0, 1.0).acos() / DEGRA
+}
+
+/// Check if a position is within a given angular distance of the Galactic plane
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension in degrees
+/// * `dec` - Declination in degrees
+/// * `max_latitude` - Maximum absolute Galactic latitude in degrees
+///
+/// # Returns
+///
+/// * `bool` - True if the absolute Galactic latitude of the position is at most `max_latitude`
+///
+/// # Examples
+///
+/// ```
+/// use flare::galactic::is_in_plane;
+///
+/// // the Galactic center is in the plane, the north celestial pole is not
+/// assert!(is_in_plane(266.405, -28.936, 5.0));
+/// assert!(!is_in_plane(0.0, 90.0, 5.0));
+/// ```
+pub fn is_in_plane(ra: f64, dec: f64, max_latitude: f64) -> bool {
+    plane_distance(ra, dec) <= max_latitude
+}
+
+/// Check if a position falls in the Galactic bulge
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension in degrees
+/// * `dec` - Declination in degrees
+///
+/// # Returns
+///
+/// * `bool` - True if |l| and |b| are both at most `BULGE_HALF_WIDTH`
+///
+/// # Examples
+///
+/// ```
+/// use flare::galactic::is_in_bulge;
+/// use flare::spatial::lb2radec;
+///
+/// let (ra, dec) = lb2radec(-5.0, 3.0);
+/// assert!(is_in_bulge(ra, dec));
+/// let (ra, dec) = lb2radec(30.0, 3.0);
+/// assert!(!is_in_bulge(ra, dec));
+/// ```
+pub fn is_in_bulge(ra: f64, dec: f64) -> bool {
+    let (l, b) = radec2lb(ra, dec);
+    l.abs() <= BULGE_HALF_WIDTH && b.abs() <= BULGE_HALF_WIDTH
+}
+
+/// Check if a position falls in the Galactic disk, outside of the bulge
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension in degrees
+/// * `dec` - Declination in degrees
+///
+/// # Returns
+///
+/// * `bool` - True if |b| is at most `DISK_MAX_LATITUDE` and the position is not in the bulge
+///
+/// # Examples
+///
+/// ```
+/// use flare::galactic::is_in_disk;
+/// use flare::spatial::lb2radec;
+///
+/// let (ra, dec) = lb2radec(30.0, 3.0);
+/// assert!(is_in_disk(ra, dec));
+/// let (ra, dec) = lb2radec(0.0, 3.0);
+/// assert!(!is_in_disk(ra, dec));
+/// let (ra, dec) = lb2radec(30.0, 45.0);
+/// assert!(!is_in_disk(ra, dec));
+/// ```
+pub fn is_in_disk(ra: f64, dec: f64) -> bool {
+    !is_in_bulge(ra, dec) && is_in_plane(ra, dec, DISK_MAX_LATITUDE)
+}
+
+/// Generate targets on a grid of Galactic coordinates
+///
+/// The grid is made of rings of constant Galactic latitude, spaced by `step` degrees,
+/// with the longitude spacing of each ring widened by 1 / cos(b) so that neighbouring
+/// points are about `step` degrees apart over the whole sky.
+///
+/// # Arguments
+///
+/// * `step` - Approximate spacing between grid points, in degrees
+/// * `b_min` - Minimum Galactic latitude of the grid, in degrees
+/// * `b_max` - Maximum Galactic latitude of the grid, in degrees
+///
+/// # Returns
+///
+/// * `Vec<Target<'static>>` - The grid points, ring by ring from `b_min` to `b_max`, with increasing longitude
+///
+/// # Examples
+///
+/// ```
+/// use flare::galactic::{grid, plane_distance};
+///
+/// // a grid covering the Galactic plane
+/// let targets = grid(1.0, -2.0, 2.0);
+/// assert_eq!(targets.len(), 5 * 360);
+/// assert!(targets.iter().all(|t| plane_distance(t.ra, t.dec) <= 2.0 + 1e-6));
+///
+/// // the poles are single points
+/// assert_eq!(grid(10.0, 90.0, 90.0).len(), 1);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `step` is not positive, or if `b_min` is greater than `b_max`.
+pub fn grid(step: f64, b_min: f64, b_max: f64) -> Vec<Target<'static>> {
+    assert!(step > 0.0, "step must be positive");
+    assert!(b_min <= b_max, "b_min must be smaller than b_max");
+    let b_min = b_min.max(-90.0);
+    let b_max = b_max.min(90.0);
+    let n_rings = ((b_max - b_min) / step + 1e-9).floor() as usize + 1;
+    let mut targets = Vec::new();
+    for i in 0..n_rings {
+        let b = b_min + i as f64 * step;
+        let n_points = ((360.0 * (b * DEGRA).cos() / step).round() as usize).max(1);
+        for j in 0..n_points {
+            let l = j as f64 * 360.0 / n_points as f64;
+            let (ra, dec) = lb2radec(l, b);
+            targets.push(Target::new(ra, dec, None));
+        }
+    }
+    targets
+}
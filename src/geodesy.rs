@@ -0,0 +1,86 @@
+use crate::spatial::DEGRA;
+use crate::vector::Cartesian3;
+
+/// Equatorial radius of the Earth (WGS84), in km
+pub const EARTH_RADIUS: f64 = 6378.137;
+
+/// Flattening of the Earth (WGS84)
+pub const EARTH_FLATTENING: f64 = 1.0 / 298.257223563;
+
+/// Square of the eccentricity of the WGS84 ellipsoid
+const ECCENTRICITY_SQUARED: f64 = EARTH_FLATTENING * (2.0 - EARTH_FLATTENING);
+
+/// Convert geodetic coordinates (WGS84) to geocentric Cartesian coordinates (Earth-fixed, ITRS)
+///
+/// # Arguments
+///
+/// * `lat` - Geodetic latitude in degrees
+/// * `lon` - Longitude in degrees (positive East)
+/// * `height` - Height above the WGS84 ellipsoid in meters
+///
+/// # Returns
+///
+/// * `Cartesian3` - The position in km, with x towards (lat, lon) = (0, 0), y towards (0, 90) and z towards the North pole
+///
+/// # Examples
+///
+/// ```
+/// use flare::geodesy::geodetic_to_geocentric;
+///
+/// let equator = geodetic_to_geocentric(0.0, 0.0, 0.0);
+/// assert!((equator.x - 6378.137).abs() < 1e-9 && equator.y.abs() < 1e-9 && equator.z.abs() < 1e-9);
+///
+/// // the polar radius is 21 km smaller than the equatorial radius
+/// let pole = geodetic_to_geocentric(90.0, 0.0, 1000.0);
+/// assert!((pole.z - 6357.752314).abs() < 1e-6);
+/// ```
+pub fn geodetic_to_geocentric(lat: f64, lon: f64, height: f64) -> Cartesian3 {
+    let (lat, lon, height) = (lat * DEGRA, lon * DEGRA, height / 1000.0);
+    // radius of curvature in the prime vertical
+    let n = EARTH_RADIUS / (1.0 - ECCENTRICITY_SQUARED * lat.sin().powi(2)).sqrt();
+    Cartesian3::new(
+        (n + height) * lat.cos() * lon.cos(),
+        (n + height) * lat.cos() * lon.sin(),
+        (n * (1.0 - ECCENTRICITY_SQUARED) + height) * lat.sin(),
+    )
+}
+
+/// Convert geocentric Cartesian coordinates (Earth-fixed, ITRS) to geodetic coordinates (WGS84)
+///
+/// # Arguments
+///
+/// * `position` - The position in km (see `geodetic_to_geocentric`)
+///
+/// # Returns
+///
+/// * `(f64, f64, f64)` - The geodetic latitude and the longitude (in [-180, 180]) in degrees,
+///   and the height above the WGS84 ellipsoid in meters
+///
+/// # Examples
+///
+/// ```
+/// use flare::geodesy::{geocentric_to_geodetic, geodetic_to_geocentric};
+///
+/// let position = geodetic_to_geocentric(-30.244639, -70.749417, 2663.0);
+/// let (lat, lon, height) = geocentric_to_geodetic(&position);
+/// assert!((lat + 30.244639).abs() < 1e-9 && (lon + 70.749417).abs() < 1e-9);
+/// assert!((height - 2663.0).abs() < 1e-6);
+/// ```
+///
+/// # Notes
+///
+/// The latitude is found by fixed-point iterations, which converge to well below a micro-arcsecond
+/// for points close to the surface of the Earth, including at the poles.
+pub fn geocentric_to_geodetic(position: &Cartesian3) -> (f64, f64, f64) {
+    let p = position.x.hypot(position.y);
+    let lon = position.y.atan2(position.x);
+    let mut lat = position.z.atan2(p * (1.0 - ECCENTRICITY_SQUARED));
+    let mut n = EARTH_RADIUS;
+    for _ in 0..10 {
+        n = EARTH_RADIUS / (1.0 - ECCENTRICITY_SQUARED * lat.sin().powi(2)).sqrt();
+        lat = (position.z + ECCENTRICITY_SQUARED * n * lat.sin()).atan2(p);
+    }
+    // stable at all latitudes, unlike p / cos(lat) - n
+    let height = p * lat.cos() + position.z * lat.sin() - EARTH_RADIUS * EARTH_RADIUS / n;
+    (lat / DEGRA, lon / DEGRA, height * 1000.0)
+}
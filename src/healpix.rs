@@ -0,0 +1,308 @@
+use std::f64::consts::PI;
+
+use crate::region::{Region, SkyCircle, SQDEG_PER_SR};
+use crate::spatial::{great_circle_distance, DEGRA};
+
+/// Longitude index (in units of pi / 4) of the first corner of each base face
+const JPLL: [i64; 12] = [1, 3, 5, 7, 0, 2, 4, 6, 1, 3, 5, 7];
+
+/// Ring index (in units of nside) of the southernmost corner of each base face
+const JRLL: [i64; 12] = [2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4];
+
+fn check_nside(nside: u64) {
+    if nside == 0 || !nside.is_power_of_two() || nside > 1 << 29 {
+        panic!("Invalid nside: {} (must be a power of 2, up to 2^29)", nside);
+    }
+}
+
+/// Interleave the bits of x with zeros (bit i of x goes to bit 2i)
+fn spread_bits(x: u64) -> u64 {
+    (0..32).fold(0, |acc, i| acc | (((x >> i) & 1) << (2 * i)))
+}
+
+/// Inverse of `spread_bits`: keep the even bits of x
+fn compress_bits(x: u64) -> u64 {
+    (0..32).fold(0, |acc, i| acc | (((x >> (2 * i)) & 1) << i))
+}
+
+/// Position of a point of a base face, given by continuous coordinates (x, y) in [0, nside] on that face
+fn face_position(nside: u64, face: usize, x: f64, y: f64) -> (f64, f64) {
+    let n = nside as f64;
+    let jr = (JRLL[face] as f64) * n - x - y;
+    let (nr, z) = if jr < n {
+        (jr, 1.0 - jr * jr / (3.0 * n * n))
+    } else if jr > 3.0 * n {
+        let nr = 4.0 * n - jr;
+        (nr, nr * nr / (3.0 * n * n) - 1.0)
+    } else {
+        (n, (2.0 * n - jr) * 2.0 / (3.0 * n))
+    };
+    let phi = if nr <= 0.0 { 0.0 } else { PI / 4.0 * (JPLL[face] as f64 * nr + x - y) / nr };
+    ((phi / DEGRA).rem_euclid(360.0), z.clamp(-1.0, 1.0).asin() / DEGRA)
+}
+
+/// Calculate the number of pixels of a HEALPix map
+///
+/// # Arguments
+///
+/// * `nside` - Resolution parameter of the map (a power of 2)
+///
+/// # Returns
+///
+/// * `u64` - The number of pixels, 12 * nside^2
+///
+/// # Examples
+///
+/// ```
+/// use flare::healpix::nside2npix;
+///
+/// assert_eq!(nside2npix(1), 12);
+/// assert_eq!(nside2npix(64), 49152);
+/// ```
+pub fn nside2npix(nside: u64) -> u64 {
+    check_nside(nside);
+    12 * nside * nside
+}
+
+/// Calculate the area of the pixels of a HEALPix map
+///
+/// # Arguments
+///
+/// * `nside` - Resolution parameter of the map (a power of 2)
+///
+/// # Returns
+///
+/// * `f64` - The area of a pixel in square degrees (all the pixels have the same area)
+///
+/// # Examples
+///
+/// ```
+/// use flare::healpix::pixel_area;
+///
+/// assert!((pixel_area(1) - 3437.746771).abs() < 1e-6);
+/// ```
+pub fn pixel_area(nside: u64) -> f64 {
+    4.0 * PI * SQDEG_PER_SR / nside2npix(nside) as f64
+}
+
+/// Find the HEALPix pixel (NESTED scheme) containing a position
+///
+/// # Arguments
+///
+/// * `nside` - Resolution parameter of the map (a power of 2)
+/// * `ra` - Right Ascension in degrees
+/// * `dec` - Declination in degrees
+///
+/// # Returns
+///
+/// * `u64` - Index of the pixel in the NESTED scheme
+///
+/// # Examples
+///
+/// ```
+/// use flare::healpix::{ang2pix, pix2ang};
+///
+/// let ipix = ang2pix(64, 150.0, 2.0);
+/// let (ra, dec) = pix2ang(64, ipix);
+/// assert!((ra - 150.0).abs() < 1.0 && (dec - 2.0).abs() < 1.0);
+/// assert_eq!(ang2pix(64, ra, dec), ipix);
+///
+/// // the centers of the NESTED pixels 1 and 3 at nside 2
+/// assert_eq!(ang2pix(2, 67.5, 41.8103), 1);
+/// assert_eq!(ang2pix(2, 45.0, 66.4435), 3);
+/// // the Crab Nebula and the Galactic center
+/// assert_eq!(ang2pix(64, 83.6331, 22.0145), 24185);
+/// assert_eq!(ang2pix(64, 266.4168, -29.0078), 28830);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `nside` is not a power of 2 (up to 2^29).
+///
+/// # References
+///
+/// Górski, K. M. et al. 2005, ApJ, 622, 759
+pub fn ang2pix(nside: u64, ra: f64, dec: f64) -> u64 {
+    check_nside(nside);
+    let n = nside as i64;
+    let z = (dec * DEGRA).sin();
+    let za = z.abs();
+    // longitude in units of pi / 2, in [0, 4)
+    let tt = (ra.rem_euclid(360.0) / 90.0).rem_euclid(4.0);
+    let (face, ix, iy) = if za <= 2.0 / 3.0 {
+        let temp1 = nside as f64 * (0.5 + tt);
+        let temp2 = nside as f64 * z * 0.75;
+        let jp = (temp1 - temp2) as i64;
+        let jm = (temp1 + temp2) as i64;
+        let ifp = jp / n;
+        let ifm = jm / n;
+        let face = if ifp == ifm {
+            ifp | 4
+        } else if ifp < ifm {
+            ifp
+        } else {
+            ifm + 8
+        };
+        (face, jm & (n - 1), n - (jp & (n - 1)) - 1)
+    } else {
+        let ntt = (tt as i64).min(3);
+        let tp = tt - ntt as f64;
+        let tmp = nside as f64 * (3.0 * (1.0 - za)).sqrt();
+        let jp = ((tp * tmp) as i64).min(n - 1);
+        let jm = (((1.0 - tp) * tmp) as i64).min(n - 1);
+        if z >= 0.0 {
+            (ntt, n - jm - 1, n - jp - 1)
+        } else {
+            (ntt + 8, jp, jm)
+        }
+    };
+    face as u64 * nside * nside + spread_bits(ix as u64) + (spread_bits(iy as u64) << 1)
+}
+
+/// Find the center of a HEALPix pixel (NESTED scheme)
+///
+/// # Arguments
+///
+/// * `nside` - Resolution parameter of the map (a power of 2)
+/// * `ipix` - Index of the pixel in the NESTED scheme
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Right Ascension (in [0, 360)) and Declination of the center of the pixel in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::healpix::pix2ang;
+///
+/// // the first pixels of the first base face are around RA = 45 degrees
+/// let (ra, dec) = pix2ang(1, 0);
+/// assert!((ra - 45.0).abs() < 1e-9);
+/// assert!((dec - 41.810315).abs() < 1e-6);
+/// let (ra, dec) = pix2ang(1, 4);
+/// assert!(ra.abs() < 1e-9 && dec.abs() < 1e-9);
+///
+/// // at nside 2, the pixels 1 and 3 are the eastern and northern children of the pixel 0 of nside 1
+/// let (ra, dec) = pix2ang(2, 1);
+/// assert!((ra - 67.5).abs() < 1e-9 && (dec - 41.8103).abs() < 1e-4);
+/// let (ra, dec) = pix2ang(2, 3);
+/// assert!((ra - 45.0).abs() < 1e-9 && (dec - 66.4435).abs() < 1e-4);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `nside` is not a power of 2 (up to 2^29), or if `ipix` is not a valid pixel index.
+///
+/// # References
+///
+/// Górski, K. M. et al. 2005, ApJ, 622, 759
+pub fn pix2ang(nside: u64, ipix: u64) -> (f64, f64) {
+    let (face, ix, iy) = pix2xyf(nside, ipix);
+    face_position(nside, face, ix as f64 + 0.5, iy as f64 + 0.5)
+}
+
+fn pix2xyf(nside: u64, ipix: u64) -> (usize, u64, u64) {
+    let npix = nside2npix(nside);
+    if ipix >= npix {
+        panic!("Invalid pixel index: {} (nside {} has {} pixels)", ipix, nside, npix);
+    }
+    let face = (ipix / (nside * nside)) as usize;
+    let ipf = ipix % (nside * nside);
+    (face, compress_bits(ipf), compress_bits(ipf >> 1))
+}
+
+/// HealpixPixel struct
+///
+/// This struct represents a pixel of a HEALPix map (NESTED scheme) as a region of the sky.
+///
+/// # Attributes
+///
+/// * `nside` - Resolution parameter of the map (a power of 2)
+/// * `ipix` - Index of the pixel in the NESTED scheme
+///
+/// # Examples
+///
+/// ```
+/// use flare::healpix::{ang2pix, HealpixPixel};
+/// use flare::region::Region;
+///
+/// let pixel = HealpixPixel::new(16, ang2pix(16, 150.0, 2.0));
+/// assert!(pixel.contains(150.0, 2.0));
+/// assert!((pixel.area() - 13.428698).abs() < 1e-6);
+/// assert_eq!(pixel.corners().len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealpixPixel {
+    pub nside: u64,
+    pub ipix: u64,
+}
+
+impl HealpixPixel {
+    /// Create a new HealpixPixel
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nside` is not a power of 2 (up to 2^29), or if `ipix` is not a valid pixel index.
+    pub fn new(nside: u64, ipix: u64) -> HealpixPixel {
+        pix2xyf(nside, ipix);
+        HealpixPixel { nside, ipix }
+    }
+
+    /// Get the center of the pixel, as RA and DEC in degrees
+    pub fn center(&self) -> (f64, f64) {
+        pix2ang(self.nside, self.ipix)
+    }
+
+    /// Get the 4 corners of the pixel (North, West, South, East), as RA and DEC in degrees
+    pub fn corners(&self) -> Vec<(f64, f64)> {
+        let (face, ix, iy) = pix2xyf(self.nside, self.ipix);
+        let (x, y) = (ix as f64, iy as f64);
+        [(1.0, 1.0), (0.0, 1.0), (0.0, 0.0), (1.0, 0.0)]
+            .iter()
+            .map(|(dx, dy)| face_position(self.nside, face, x + dx, y + dy))
+            .collect()
+    }
+}
+
+impl Region for HealpixPixel {
+    fn contains(&self, ra: f64, dec: f64) -> bool {
+        ang2pix(self.nside, ra, dec) == self.ipix
+    }
+
+    fn area(&self) -> f64 {
+        pixel_area(self.nside)
+    }
+
+    fn bounding_cap(&self) -> SkyCircle {
+        let (ra, dec) = self.center();
+        // pixel edges are not great circles, so the corners are not enough: sample the boundary
+        let radius = self
+            .boundary(64)
+            .iter()
+            .map(|&(ra_b, dec_b)| great_circle_distance(ra, dec, ra_b, dec_b))
+            .fold(0.0, f64::max);
+        SkyCircle::new(ra, dec, radius * 1.01)
+    }
+
+    fn boundary(&self, n: usize) -> Vec<(f64, f64)> {
+        let (face, ix, iy) = pix2xyf(self.nside, self.ipix);
+        let (x, y) = (ix as f64, iy as f64);
+        let per_edge = n.div_ceil(4).max(1);
+        let starts = [(1.0, 1.0), (0.0, 1.0), (0.0, 0.0), (1.0, 0.0)];
+        let mut points = Vec::with_capacity(4 * per_edge);
+        for k in 0..4 {
+            let (x0, y0) = starts[k];
+            let (x1, y1) = starts[(k + 1) % 4];
+            for i in 0..per_edge {
+                let t = i as f64 / per_edge as f64;
+                points.push(face_position(
+                    self.nside,
+                    face,
+                    x + x0 + t * (x1 - x0),
+                    y + y0 + t * (y1 - y0),
+                ));
+            }
+        }
+        points
+    }
+}
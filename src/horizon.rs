@@ -0,0 +1,288 @@
+/// HorizonError enum
+///
+/// This enum represents the errors that can occur when reading a horizon mask.
+///
+/// # Variants
+///
+/// * `Io` - The file can't be read
+/// * `InvalidLine` - A line (numbered from 1) can't be parsed as an azimuth and an altitude
+/// * `Empty` - The mask has no points
+#[derive(Debug, Clone, PartialEq)]
+pub enum HorizonError {
+    Io(String),
+    InvalidLine(usize, String),
+    Empty,
+}
+
+impl std::fmt::Display for HorizonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HorizonError::Io(error) => write!(f, "Can't read the horizon mask: {}", error),
+            HorizonError::InvalidLine(number, line) => write!(f, "Invalid line {}: {}", number, line),
+            HorizonError::Empty => write!(f, "The horizon mask has no points"),
+        }
+    }
+}
+
+impl std::error::Error for HorizonError {}
+
+/// HorizonMask struct
+///
+/// This struct represents the obstructed horizon of a site (mountains, buildings, the dome...), as a table of
+/// minimum altitudes per azimuth, interpolated linearly in between (and across North, at azimuth 0/360).
+///
+/// # Attributes
+///
+/// * `points` - The azimuths (East of North, in [0, 360)) and minimum apparent altitudes of the mask in degrees,
+///   sorted by azimuth
+///
+/// # Methods
+///
+/// * `new` - Create a new HorizonMask from a list of points
+/// * `from_text` - Read a HorizonMask from a text table
+/// * `from_file` - Read a HorizonMask from a text file
+/// * `min_altitude` - Get the minimum altitude at an azimuth
+/// * `is_visible` - Check if a position is above the mask
+///
+/// # Examples
+///
+/// ```
+/// use flare::horizon::HorizonMask;
+///
+/// // a mountain range in the East, up to 15 degrees high
+/// let mask = HorizonMask::new(&[(0.0, 5.0), (90.0, 15.0), (180.0, 5.0)]);
+/// assert_eq!(mask.min_altitude(45.0), 10.0);
+/// assert_eq!(mask.min_altitude(270.0), 5.0);
+/// assert!(!mask.is_visible(12.0, 90.0));
+/// assert!(mask.is_visible(12.0, 270.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HorizonMask {
+    pub points: Vec<(f64, f64)>,
+}
+
+impl HorizonMask {
+    /// Create a new HorizonMask
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The azimuths (East of North) and minimum apparent altitudes of the mask in degrees, in any order
+    ///
+    /// # Returns
+    ///
+    /// * `HorizonMask` - A new HorizonMask object
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no points, if an azimuth is not finite, or if an altitude is not in [-90, 90].
+    pub fn new(points: &[(f64, f64)]) -> HorizonMask {
+        if points.is_empty() {
+            panic!("a horizon mask needs at least one point");
+        }
+        if points.iter().any(|&(az, _)| !az.is_finite()) {
+            panic!("the azimuths of a horizon mask must be finite");
+        }
+        if points.iter().any(|&(_, alt)| !(-90.0..=90.0).contains(&alt)) {
+            panic!("the altitudes of a horizon mask must be in [-90, 90]");
+        }
+        let mut points = points.iter().map(|&(az, alt)| (az.rem_euclid(360.0), alt)).collect::<Vec<(f64, f64)>>();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        HorizonMask { points }
+    }
+
+    /// Read a HorizonMask from a text table
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - One point per line: an azimuth and a minimum altitude in degrees, separated by spaces, tabs or
+    ///   a comma. Empty lines and comments (starting with `#`) are ignored.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HorizonMask, HorizonError>` - The HorizonMask, or an error if a line can't be parsed
+    ///   or if there are no points
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::horizon::{HorizonError, HorizonMask};
+    ///
+    /// let mask = HorizonMask::from_text("# az alt\n0 10\n180, 20 # the dome slit\n").unwrap();
+    /// assert_eq!(mask.points, vec![(0.0, 10.0), (180.0, 20.0)]);
+    /// assert_eq!(mask.min_altitude(90.0), 15.0);
+    ///
+    /// let error = HorizonMask::from_text("0 10\n90\n").unwrap_err();
+    /// assert_eq!(error, HorizonError::InvalidLine(2, "90".to_string()));
+    ///
+    /// let error = HorizonMask::from_text("inf 10\n").unwrap_err();
+    /// assert_eq!(error, HorizonError::InvalidLine(1, "inf 10".to_string()));
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The azimuths must be finite, and the altitudes in [-90, 90] (see `new`).
+    pub fn from_text(text: &str) -> Result<HorizonMask, HorizonError> {
+        let mut points = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let content = line.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            let invalid = || HorizonError::InvalidLine(i + 1, line.trim().to_string());
+            let values = content
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|value| !value.is_empty())
+                .map(|value| value.parse::<f64>().map_err(|_| invalid()))
+                .collect::<Result<Vec<f64>, HorizonError>>()?;
+            if values.len() != 2 || !values[0].is_finite() || !(-90.0..=90.0).contains(&values[1]) {
+                return Err(invalid());
+            }
+            points.push((values[0], values[1]));
+        }
+        if points.is_empty() {
+            return Err(HorizonError::Empty);
+        }
+        Ok(HorizonMask::new(&points))
+    }
+
+    /// Read a HorizonMask from a text file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the file, with the format of `from_text`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HorizonMask, HorizonError>` - The HorizonMask, or an error if the file can't be read or parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::horizon::HorizonMask;
+    ///
+    /// let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/horizons/example.txt");
+    /// let mask = HorizonMask::from_file(path).unwrap();
+    /// assert_eq!(mask.min_altitude(0.0), 15.0);
+    /// assert!(HorizonMask::from_file("missing.txt").is_err());
+    /// ```
+    pub fn from_file(path: &str) -> Result<HorizonMask, HorizonError> {
+        let text = std::fs::read_to_string(path).map_err(|error| HorizonError::Io(error.to_string()))?;
+        HorizonMask::from_text(&text)
+    }
+
+    /// Get the minimum altitude of the mask at an azimuth
+    ///
+    /// # Arguments
+    ///
+    /// * `az` - Azimuth (East of North) in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The minimum apparent altitude in degrees, interpolated linearly between the points of the mask
+    pub fn min_altitude(&self, az: f64) -> f64 {
+        let az = az.rem_euclid(360.0);
+        let n = self.points.len();
+        // the points before and after the azimuth, wrapping around North
+        let after = self.points.iter().position(|&(a, _)| a >= az).unwrap_or(n);
+        let (az0, alt0) = if after == 0 { (self.points[n - 1].0 - 360.0, self.points[n - 1].1) } else { self.points[after - 1] };
+        let (az1, alt1) = if after == n { (self.points[0].0 + 360.0, self.points[0].1) } else { self.points[after] };
+        if az1 - az0 <= 0.0 {
+            return alt1;
+        }
+        alt0 + (alt1 - alt0) * (az - az0) / (az1 - az0)
+    }
+
+    /// Check if a position is above the mask
+    ///
+    /// # Arguments
+    ///
+    /// * `alt` - Apparent altitude in degrees
+    /// * `az` - Azimuth (East of North) in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the altitude is at least the minimum altitude of the mask at this azimuth
+    pub fn is_visible(&self, alt: f64, az: f64) -> bool {
+        alt >= self.min_altitude(az)
+    }
+}
+
+/// MountLimits struct
+///
+/// This struct represents the pointing limits of an equatorial (or fork) mount, in hour angle and declination.
+///
+/// # Attributes
+///
+/// * `ha_min` - Minimum hour angle in degrees, in [-180, 180] (negative East of the meridian)
+/// * `ha_max` - Maximum hour angle in degrees, in [-180, 180]
+/// * `dec_min` - Minimum declination in degrees
+/// * `dec_max` - Maximum declination in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::horizon::MountLimits;
+///
+/// // +/- 4.5 hours from the meridian, and down to a declination of -30 degrees
+/// let limits = MountLimits::new(-67.5, 67.5, -30.0, 90.0);
+/// assert!(limits.allows(-60.0, 20.0));
+/// assert!(!limits.allows(-70.0, 20.0));
+/// assert!(!limits.allows(0.0, -35.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountLimits {
+    pub ha_min: f64,
+    pub ha_max: f64,
+    pub dec_min: f64,
+    pub dec_max: f64,
+}
+
+impl MountLimits {
+    /// Create a new MountLimits
+    ///
+    /// # Arguments
+    ///
+    /// * `ha_min` - Minimum hour angle in degrees, in [-180, 180]
+    /// * `ha_max` - Maximum hour angle in degrees, in [-180, 180]
+    /// * `dec_min` - Minimum declination in degrees, in [-90, 90]
+    /// * `dec_max` - Maximum declination in degrees, in [-90, 90]
+    ///
+    /// # Returns
+    ///
+    /// * `MountLimits` - A new MountLimits object
+    ///
+    /// # Panics
+    ///
+    /// Panics if a minimum is greater than its maximum, or if the limits are out of range.
+    pub fn new(ha_min: f64, ha_max: f64, dec_min: f64, dec_max: f64) -> MountLimits {
+        if !(-180.0..=180.0).contains(&ha_min) || !(-180.0..=180.0).contains(&ha_max) {
+            panic!("the hour angle limits must be in [-180, 180]");
+        }
+        if !(-90.0..=90.0).contains(&dec_min) || !(-90.0..=90.0).contains(&dec_max) {
+            panic!("the declination limits must be in [-90, 90]");
+        }
+        if ha_min > ha_max || dec_min > dec_max {
+            panic!("the minimum limits must be less than or equal to the maximum limits");
+        }
+        MountLimits { ha_min, ha_max, dec_min, dec_max }
+    }
+
+    /// Check if the mount can point at a position
+    ///
+    /// # Arguments
+    ///
+    /// * `ha` - Hour angle in degrees, in [-180, 180)
+    /// * `dec` - Declination in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the position is within the limits, false otherwise
+    pub fn allows(&self, ha: f64, dec: f64) -> bool {
+        self.margin(ha, dec) >= 0.0
+    }
+
+    /// Distance to the closest limit in degrees, negative outside of the limits
+    pub(crate) fn margin(&self, ha: f64, dec: f64) -> f64 {
+        (ha - self.ha_min).min(self.ha_max - ha).min(dec - self.dec_min).min(self.dec_max - dec)
+    }
+}
@@ -0,0 +1,80 @@
+use crate::region::SkyEllipse;
+use crate::spatial::great_circle_distance;
+use crate::target::Target;
+
+/// HostMatch struct
+///
+/// This struct represents a candidate host galaxy of a transient, ranked by its normalized elliptical distance.
+///
+/// # Attributes
+///
+/// * `index` - Index of the galaxy in the list of candidates
+/// * `separation` - Angular separation between the transient and the center of the galaxy in degrees
+/// * `dlr` - Directional light radius of the galaxy in the direction of the transient, in degrees
+/// * `normalized_distance` - Separation in units of the DLR (d_DLR)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostMatch {
+    pub index: usize,
+    pub separation: f64,
+    pub dlr: f64,
+    pub normalized_distance: f64,
+}
+
+/// Rank candidate host galaxies of a transient by their normalized elliptical distance (d_DLR)
+///
+/// # Arguments
+///
+/// * `target` - The transient
+/// * `galaxies` - The candidate host galaxies, as ellipses (typically their isophotal or Kron ellipses)
+/// * `max_normalized_distance` - Optional cut on the normalized distance, candidates further away are dropped
+///   (4.0 is a common choice)
+///
+/// # Returns
+///
+/// * `Vec<HostMatch>` - The candidates, from the most to the least likely host
+///
+/// # Examples
+///
+/// ```
+/// use flare::host::rank_hosts;
+/// use flare::region::SkyEllipse;
+/// use flare::Target;
+///
+/// let transient = Target::new(150.0, 2.004, Some("SN"));
+/// let galaxies = vec![
+///     // a small galaxy, closer to the transient
+///     SkyEllipse::new(150.0012, 2.004, 0.0005, 0.0004, 0.0),
+///     // a large galaxy elongated towards the transient
+///     SkyEllipse::new(150.0, 2.0, 0.005, 0.001, 0.0),
+///     // a far away galaxy
+///     SkyEllipse::new(151.0, 2.0, 0.005, 0.001, 0.0),
+/// ];
+///
+/// let hosts = rank_hosts(&transient, &galaxies, Some(4.0));
+/// assert_eq!(hosts.len(), 2);
+/// assert_eq!(hosts[0].index, 1);
+/// assert!((hosts[0].normalized_distance - 0.8).abs() < 1e-6);
+/// assert_eq!(hosts[1].index, 0);
+/// ```
+///
+/// # Notes
+///
+/// This is the directional light radius method of Sullivan et al. 2006 and Gupta et al. 2016,
+/// which favors large galaxies oriented towards the transient over the nearest galaxy in angular separation.
+pub fn rank_hosts(target: &Target, galaxies: &[SkyEllipse], max_normalized_distance: Option<f64>) -> Vec<HostMatch> {
+    let mut matches = galaxies
+        .iter()
+        .enumerate()
+        .map(|(index, galaxy)| {
+            let separation = great_circle_distance(galaxy.ra, galaxy.dec, target.ra, target.dec);
+            let dlr = galaxy.dlr(target.ra, target.dec);
+            HostMatch { index, separation, dlr, normalized_distance: separation / dlr }
+        })
+        .filter(|host| match max_normalized_distance {
+            Some(max) => host.normalized_distance <= max,
+            None => true,
+        })
+        .collect::<Vec<HostMatch>>();
+    matches.sort_by(|a, b| a.normalized_distance.total_cmp(&b.normalized_distance));
+    matches
+}
@@ -5,6 +5,30 @@ pub mod target;
 pub mod cosmo;
 pub mod phot;
 pub mod corrections;
+pub mod region;
+pub mod camera;
+pub mod projection;
+pub mod wcs;
+pub mod host;
+pub mod ecliptic;
+pub mod galactic;
+pub mod vector;
+pub mod healpix;
+pub mod sampling;
+pub mod query;
+pub mod altaz;
+pub mod airmass;
+pub mod dcr;
+pub mod sun;
+pub mod moon;
+pub mod rise_set;
+pub mod night;
+pub mod observability;
+pub mod horizon;
+pub mod sites;
+pub mod geodesy;
+pub mod planets;
+mod vsop87;
 
 pub use cosmo::Cosmo;
 pub use observer::Observer;
@@ -0,0 +1,505 @@
+use crate::ecliptic::{centuries, nutation, true_equator_rotation, true_obliquity};
+use crate::observer::Observer;
+use crate::spatial::{great_circle_distance, DEGRA};
+use crate::sun;
+use crate::target::SkyObject;
+use crate::time::Time;
+use crate::vector::{Rotation3, UnitVector};
+
+/// Mean length of the synodic month, in days
+pub const SYNODIC_MONTH: f64 = 29.530588861;
+
+/// Periodic terms for the longitude (Σl, in 1e-6 degrees) and distance (Σr, in m) of the Moon:
+/// multiples of D, M, M' and F, and coefficients of the sine (Σl) and cosine (Σr) (table 47.A of Meeus)
+const LONGITUDE_DISTANCE_TERMS: [(i8, i8, i8, i8, f64, f64); 60] = [
+    (0, 0, 1, 0, 6288774.0, -20905355.0),
+    (2, 0, -1, 0, 1274027.0, -3699111.0),
+    (2, 0, 0, 0, 658314.0, -2955968.0),
+    (0, 0, 2, 0, 213618.0, -569925.0),
+    (0, 1, 0, 0, -185116.0, 48888.0),
+    (0, 0, 0, 2, -114332.0, -3149.0),
+    (2, 0, -2, 0, 58793.0, 246158.0),
+    (2, -1, -1, 0, 57066.0, -152138.0),
+    (2, 0, 1, 0, 53322.0, -170733.0),
+    (2, -1, 0, 0, 45758.0, -204586.0),
+    (0, 1, -1, 0, -40923.0, -129620.0),
+    (1, 0, 0, 0, -34720.0, 108743.0),
+    (0, 1, 1, 0, -30383.0, 104755.0),
+    (2, 0, 0, -2, 15327.0, 10321.0),
+    (0, 0, 1, 2, -12528.0, 0.0),
+    (0, 0, 1, -2, 10980.0, 79661.0),
+    (4, 0, -1, 0, 10675.0, -34782.0),
+    (0, 0, 3, 0, 10034.0, -23210.0),
+    (4, 0, -2, 0, 8548.0, -21636.0),
+    (2, 1, -1, 0, -7888.0, 24208.0),
+    (2, 1, 0, 0, -6766.0, 30824.0),
+    (1, 0, -1, 0, -5163.0, -8379.0),
+    (1, 1, 0, 0, 4987.0, -16675.0),
+    (2, -1, 1, 0, 4036.0, -12831.0),
+    (2, 0, 2, 0, 3994.0, -10445.0),
+    (4, 0, 0, 0, 3861.0, -11650.0),
+    (2, 0, -3, 0, 3665.0, 14403.0),
+    (0, 1, -2, 0, -2689.0, -7003.0),
+    (2, 0, -1, 2, -2602.0, 0.0),
+    (2, -1, -2, 0, 2390.0, 10056.0),
+    (1, 0, 1, 0, -2348.0, 6322.0),
+    (2, -2, 0, 0, 2236.0, -9884.0),
+    (0, 1, 2, 0, -2120.0, 5751.0),
+    (0, 2, 0, 0, -2069.0, 0.0),
+    (2, -2, -1, 0, 2048.0, -4950.0),
+    (2, 0, 1, -2, -1773.0, 4130.0),
+    (2, 0, 0, 2, -1595.0, 0.0),
+    (4, -1, -1, 0, 1215.0, -3958.0),
+    (0, 0, 2, 2, -1110.0, 0.0),
+    (3, 0, -1, 0, -892.0, 3258.0),
+    (2, 1, 1, 0, -810.0, 2616.0),
+    (4, -1, -2, 0, 759.0, -1897.0),
+    (0, 2, -1, 0, -713.0, -2117.0),
+    (2, 2, -1, 0, -700.0, 2354.0),
+    (2, 1, -2, 0, 691.0, 0.0),
+    (2, -1, 0, -2, 596.0, 0.0),
+    (4, 0, 1, 0, 549.0, -1423.0),
+    (0, 0, 4, 0, 537.0, -1117.0),
+    (4, -1, 0, 0, 520.0, -1571.0),
+    (1, 0, -2, 0, -487.0, -1739.0),
+    (2, 1, 0, -2, -399.0, 0.0),
+    (0, 0, 2, -2, -381.0, -4421.0),
+    (1, 1, 1, 0, 351.0, 0.0),
+    (3, 0, -2, 0, -340.0, 0.0),
+    (4, 0, -3, 0, 330.0, 0.0),
+    (2, -1, 2, 0, 327.0, 0.0),
+    (0, 2, 1, 0, -323.0, 1165.0),
+    (1, 1, -1, 0, 299.0, 0.0),
+    (2, 0, 3, 0, 294.0, 0.0),
+    (2, 0, -1, -2, 0.0, 8752.0),
+];
+
+/// Periodic terms for the latitude of the Moon (Σb, in 1e-6 degrees):
+/// multiples of D, M, M' and F, and coefficient of the sine (table 47.B of Meeus)
+const LATITUDE_TERMS: [(i8, i8, i8, i8, f64); 60] = [
+    (0, 0, 0, 1, 5128122.0),
+    (0, 0, 1, 1, 280602.0),
+    (0, 0, 1, -1, 277693.0),
+    (2, 0, 0, -1, 173237.0),
+    (2, 0, -1, 1, 55413.0),
+    (2, 0, -1, -1, 46271.0),
+    (2, 0, 0, 1, 32573.0),
+    (0, 0, 2, 1, 17198.0),
+    (2, 0, 1, -1, 9266.0),
+    (0, 0, 2, -1, 8822.0),
+    (2, -1, 0, -1, 8216.0),
+    (2, 0, -2, -1, 4324.0),
+    (2, 0, 1, 1, 4200.0),
+    (2, 1, 0, -1, -3359.0),
+    (2, -1, -1, 1, 2463.0),
+    (2, -1, 0, 1, 2211.0),
+    (2, -1, -1, -1, 2065.0),
+    (0, 1, -1, -1, -1870.0),
+    (4, 0, -1, -1, 1828.0),
+    (0, 1, 0, 1, -1794.0),
+    (0, 0, 0, 3, -1749.0),
+    (0, 1, -1, 1, -1565.0),
+    (1, 0, 0, 1, -1491.0),
+    (0, 1, 1, 1, -1475.0),
+    (0, 1, 1, -1, -1410.0),
+    (0, 1, 0, -1, -1344.0),
+    (1, 0, 0, -1, -1335.0),
+    (0, 0, 3, 1, 1107.0),
+    (4, 0, 0, -1, 1021.0),
+    (4, 0, -1, 1, 833.0),
+    (0, 0, 1, -3, 777.0),
+    (4, 0, -2, 1, 671.0),
+    (2, 0, 0, -3, 607.0),
+    (2, 0, 2, -1, 596.0),
+    (2, -1, 1, -1, 491.0),
+    (2, 0, -2, 1, -451.0),
+    (0, 0, 3, -1, 439.0),
+    (2, 0, 2, 1, 422.0),
+    (2, 0, -3, -1, 421.0),
+    (2, 1, -1, 1, -366.0),
+    (2, 1, 0, 1, -351.0),
+    (4, 0, 0, 1, 331.0),
+    (2, -1, 1, 1, 315.0),
+    (2, -2, 0, -1, 302.0),
+    (0, 0, 1, 3, -283.0),
+    (2, 1, 1, -1, -229.0),
+    (1, 1, 0, -1, 223.0),
+    (1, 1, 0, 1, 223.0),
+    (0, 1, -2, -1, -220.0),
+    (2, 1, -1, -1, -220.0),
+    (1, 0, 1, 1, -185.0),
+    (2, -1, -2, -1, 181.0),
+    (0, 1, 2, 1, -177.0),
+    (4, 0, -2, -1, 176.0),
+    (4, -1, -1, -1, 166.0),
+    (1, 0, 1, -1, -164.0),
+    (4, 0, 1, -1, 132.0),
+    (1, 0, -1, -1, -119.0),
+    (4, -1, 0, -1, 115.0),
+    (2, -2, 0, 1, 107.0),
+];
+
+/// Moon struct
+///
+/// This struct represents the Moon as a `SkyObject`, e.g. to compute its rise & set times with `rise_set`.
+/// Its position is its apparent topocentric position (`topocentric_position`),
+/// and it rises and sets when its upper limb is on the apparent horizon
+/// (at a geometric altitude of -34' minus its angular radius).
+/// Rises and sets are searched up to a synodic month away, as the Moon can stay up or down for days
+/// close to the poles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moon;
+
+impl SkyObject for Moon {
+    fn radec(&self, observer: &Observer, time: &Time) -> (f64, f64) {
+        let moon = topocentric_position(observer, time);
+        (moon.ra, moon.dec)
+    }
+
+    fn horizon_altitude(&self, observer: &Observer, time: &Time) -> f64 {
+        -34.0 / 60.0 - topocentric_position(observer, time).angular_radius()
+    }
+
+    fn search_window(&self) -> f64 {
+        // close to the poles, the Moon can stay above (or below) the horizon for days
+        SYNODIC_MONTH
+    }
+}
+
+/// MoonPosition struct
+///
+/// This struct represents the position of the Moon.
+///
+/// # Attributes
+///
+/// * `ra` - Right Ascension in degrees, in [0, 360)
+/// * `dec` - Declination in degrees
+/// * `distance` - Distance to the center of the Moon in km
+///
+/// # Methods
+///
+/// * `angular_radius` - Calculate the apparent angular radius (semi-diameter) of the Moon
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoonPosition {
+    pub ra: f64,
+    pub dec: f64,
+    pub distance: f64,
+}
+
+impl MoonPosition {
+    /// Calculate the apparent angular radius (semi-diameter) of the Moon
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The angular radius in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::moon::position;
+    /// use flare::Time;
+    ///
+    /// let moon = position(&Time::new(2024, 9, 10, 0, 0, 0));
+    /// let radius = moon.angular_radius();
+    /// assert!(radius > 14.6 / 60.0 && radius < 16.8 / 60.0);
+    /// ```
+    pub fn angular_radius(&self) -> f64 {
+        (1737.4 / self.distance).asin() / DEGRA
+    }
+}
+
+/// Geocentric ecliptic longitude and latitude of the Moon (mean equinox of date) in degrees, and its distance in km
+/// (chapter 47 of Meeus)
+fn ecliptic_position(time: &Time) -> (f64, f64, f64) {
+    let t = centuries(time);
+    let l = 218.3164477 + 481267.88123421 * t - 0.0015786 * t * t + t.powi(3) / 538841.0 - t.powi(4) / 65194000.0;
+    let d = 297.8501921 + 445267.1114034 * t - 0.0018819 * t * t + t.powi(3) / 545868.0 - t.powi(4) / 113065000.0;
+    let m = 357.5291092 + 35999.0502909 * t - 0.0001536 * t * t + t.powi(3) / 24490000.0;
+    let mp = 134.9633964 + 477198.8675055 * t + 0.0087414 * t * t + t.powi(3) / 69699.0 - t.powi(4) / 14712000.0;
+    let f = 93.2720950 + 483202.0175233 * t - 0.0036539 * t * t - t.powi(3) / 3526000.0 + t.powi(4) / 863310000.0;
+    let a1 = 119.75 + 131.849 * t;
+    let a2 = 53.09 + 479264.290 * t;
+    let a3 = 313.45 + 481266.484 * t;
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t * t;
+
+    let argument = |cd: i8, cm: i8, cmp: i8, cf: i8| {
+        (cd as f64 * d + cm as f64 * m + cmp as f64 * mp + cf as f64 * f) * DEGRA
+    };
+    let eccentricity = |cm: i8| e.powi(cm.abs() as i32);
+
+    let mut sum_l = 0.0;
+    let mut sum_r = 0.0;
+    for &(cd, cm, cmp, cf, coef_l, coef_r) in LONGITUDE_DISTANCE_TERMS.iter() {
+        let arg = argument(cd, cm, cmp, cf);
+        sum_l += coef_l * eccentricity(cm) * arg.sin();
+        sum_r += coef_r * eccentricity(cm) * arg.cos();
+    }
+    let mut sum_b = 0.0;
+    for &(cd, cm, cmp, cf, coef_b) in LATITUDE_TERMS.iter() {
+        sum_b += coef_b * eccentricity(cm) * argument(cd, cm, cmp, cf).sin();
+    }
+
+    // additive terms, due to Venus, Jupiter and the flattening of the Earth
+    sum_l += 3958.0 * (a1 * DEGRA).sin() + 1962.0 * ((l - f) * DEGRA).sin() + 318.0 * (a2 * DEGRA).sin();
+    sum_b += -2235.0 * (l * DEGRA).sin()
+        + 382.0 * (a3 * DEGRA).sin()
+        + 175.0 * ((a1 - f) * DEGRA).sin()
+        + 175.0 * ((a1 + f) * DEGRA).sin()
+        + 127.0 * ((l - mp) * DEGRA).sin()
+        - 115.0 * ((l + mp) * DEGRA).sin();
+
+    (
+        (l + sum_l / 1e6).rem_euclid(360.0),
+        sum_b / 1e6,
+        385000.56 + sum_r / 1000.0,
+    )
+}
+
+/// Calculate the apparent geocentric position of the Moon at a given time
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `MoonPosition` - The position of the Moon, in the true equator and equinox of date
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::position;
+/// use flare::Time;
+///
+/// // example 47.a of Meeus
+/// let moon = position(&Time::new(1992, 4, 12, 0, 0, 0));
+/// assert!((moon.ra - 134.688470).abs() < 0.01);
+/// assert!((moon.dec - 13.768368).abs() < 0.01);
+/// assert!((moon.distance - 368409.7).abs() < 10.0);
+/// ```
+///
+/// # Notes
+///
+/// This uses the main terms of the ELP-2000/82 theory, as given in chapter 47 of "Astronomical Algorithms"
+/// 2nd edition by Jean Meeus (Willmann-Bell, Richmond) 1998, accurate to about 10" in longitude and 4" in latitude.
+/// The difference between TT and UTC (about a minute) is neglected: the Moon moves by 0.01 degree in a minute.
+pub fn position(time: &Time) -> MoonPosition {
+    let (lon, lat, distance) = ecliptic_position(time);
+    let (ra, dec) = Rotation3::about_x(true_obliquity(time))
+        .inverse()
+        .apply_radec(lon + nutation(time).0, lat);
+    MoonPosition { ra, dec, distance }
+}
+
+/// Calculate the geocentric position of the Moon at a given time, in J2000 coordinates
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `MoonPosition` - The position of the Moon, in the J2000 equator and equinox
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::{position, position_j2000};
+/// use flare::spatial::great_circle_distance;
+/// use flare::Time;
+///
+/// // precession moves positions by about 50" per year
+/// let time = Time::new(2024, 9, 10, 0, 0, 0);
+/// let (of_date, j2000) = (position(&time), position_j2000(&time));
+/// let shift = great_circle_distance(of_date.ra, of_date.dec, j2000.ra, j2000.dec);
+/// assert!(shift > 0.25 && shift < 0.4);
+/// ```
+pub fn position_j2000(time: &Time) -> MoonPosition {
+    let moon = position(time);
+    let (ra, dec) = true_equator_rotation(time).inverse().apply_radec(moon.ra, moon.dec);
+    MoonPosition { ra, dec, distance: moon.distance }
+}
+
+/// Calculate the apparent topocentric position of the Moon, seen by an observer at a given time
+///
+/// # Arguments
+///
+/// * `observer` - Observer object representing the observer
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `MoonPosition` - The position of the Moon seen by the observer, in the true equator and equinox of date
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::{position, topocentric_position};
+/// use flare::spatial::great_circle_distance;
+/// use flare::{Observer, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let time = Time::new(2024, 9, 10, 3, 0, 0);
+///
+/// // the parallax of the Moon is up to a degree
+/// let geocentric = position(&time);
+/// let topocentric = topocentric_position(&observer, &time);
+/// let parallax = great_circle_distance(geocentric.ra, geocentric.dec, topocentric.ra, topocentric.dec);
+/// assert!(parallax > 0.1 && parallax < 1.1);
+/// ```
+pub fn topocentric_position(observer: &Observer, time: &Time) -> MoonPosition {
+    let moon = position(time);
+    let geocentric = UnitVector::from_radec(moon.ra, moon.dec).cartesian() * moon.distance;
+    let topocentric = geocentric - observer.geocentric_position(time);
+    let (ra, dec) = topocentric.direction().expect("the Moon is not at the observer").to_radec();
+    MoonPosition { ra, dec, distance: topocentric.norm() }
+}
+
+/// Calculate the phase angle of the Moon at a given time: the angle Sun-Moon-Earth
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `f64` - The phase angle in degrees, in [0, 180] (0 at full moon, 180 at new moon)
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::phase_angle;
+/// use flare::Time;
+///
+/// // example 48.a of Meeus
+/// let angle = phase_angle(&Time::new(1992, 4, 12, 0, 0, 0));
+/// assert!((angle - 69.0756).abs() < 0.01);
+/// ```
+///
+/// # References
+///
+/// formulas 48.2 and 48.3 of "Astronomical Algorithms" 2nd edition by Jean Meeus (Willmann-Bell, Richmond) 1998.
+pub fn phase_angle(time: &Time) -> f64 {
+    let moon = position(time);
+    let sun = sun::position(time);
+    let elongation = great_circle_distance(moon.ra, moon.dec, sun.ra, sun.dec) * DEGRA;
+    let sun_distance = sun.distance * sun::AU;
+    (sun_distance * elongation.sin()).atan2(moon.distance - sun_distance * elongation.cos()) / DEGRA
+}
+
+/// Calculate the illuminated fraction of the disk of the Moon at a given time
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `f64` - The illuminated fraction, in [0, 1] (0 at new moon, 1 at full moon)
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::illumination;
+/// use flare::Time;
+///
+/// // example 48.a of Meeus
+/// let fraction = illumination(&Time::new(1992, 4, 12, 0, 0, 0));
+/// assert!((fraction - 0.6786).abs() < 1e-3);
+/// ```
+pub fn illumination(time: &Time) -> f64 {
+    (1.0 + (phase_angle(time) * DEGRA).cos()) / 2.0
+}
+
+/// Difference between the apparent ecliptic longitudes of the Moon and of the Sun, in [-180, 180)
+fn longitude_difference(jd: f64, target: f64) -> f64 {
+    let time = Time::from_jd(jd);
+    let (moon_lon, _, _) = ecliptic_position(&time);
+    let (sun_lon, _) = sun::apparent_longitude(&time);
+    (moon_lon + nutation(&time).0 - sun_lon - target + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Find the next time when the difference between the longitudes of the Moon and the Sun reaches a given value
+fn next_phase(after: &Time, target: f64) -> Time {
+    // the difference increases by about 12 degrees per day: it crosses the target value
+    // when it changes sign from negative to positive (from positive to negative, it wraps around)
+    let mut start = after.to_jd();
+    let mut before = longitude_difference(start, target);
+    loop {
+        let end = start + 1.0;
+        let current = longitude_difference(end, target);
+        if before < 0.0 && current >= 0.0 {
+            let (mut low, mut high) = (start, end);
+            while (high - low) * 86400.0 > 1.0 {
+                let middle = (low + high) / 2.0;
+                if longitude_difference(middle, target) < 0.0 {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            return Time::from_jd(high);
+        }
+        start = end;
+        before = current;
+    }
+}
+
+/// Find the time of the next new moon
+///
+/// # Arguments
+///
+/// * `after` - Time object representing the time after which to search
+///
+/// # Returns
+///
+/// * `Time` - The time of the next new moon (conjunction in ecliptic longitude with the Sun)
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::next_new_moon;
+/// use flare::Time;
+///
+/// // example 49.a of Meeus: 1977 February 18, 3h37m42s TD
+/// let new_moon = next_new_moon(&Time::new(1977, 2, 10, 0, 0, 0));
+/// let expected = Time::new(1977, 2, 18, 3, 37, 42).to_jd();
+/// assert!((new_moon.to_jd() - expected).abs() * 1440.0 < 3.0);
+/// ```
+///
+/// # Notes
+///
+/// The times are accurate to about two minutes, limited by the accuracy of the position of the Sun
+/// (the Moon moves by 0.01 degree relative to the Sun in a minute).
+pub fn next_new_moon(after: &Time) -> Time {
+    next_phase(after, 0.0)
+}
+
+/// Find the time of the next full moon
+///
+/// # Arguments
+///
+/// * `after` - Time object representing the time after which to search
+///
+/// # Returns
+///
+/// * `Time` - The time of the next full moon (opposition in ecliptic longitude with the Sun)
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::{next_full_moon, next_new_moon, SYNODIC_MONTH};
+/// use flare::Time;
+///
+/// let time = Time::new(2024, 9, 10, 0, 0, 0);
+/// // the full moon of 2024 September 18, at 2h34m UTC
+/// let full_moon = next_full_moon(&time);
+/// assert!((full_moon.to_jd() - Time::new(2024, 9, 18, 2, 34, 0).to_jd()).abs() * 1440.0 < 3.0);
+///
+/// // half a month later, the next new moon
+/// let days = next_new_moon(&full_moon).to_jd() - full_moon.to_jd();
+/// assert!((days - SYNODIC_MONTH / 2.0).abs() < 1.0);
+/// ```
+pub fn next_full_moon(after: &Time) -> Time {
+    next_phase(after, 180.0)
+}
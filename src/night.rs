@@ -0,0 +1,90 @@
+use crate::time::Time;
+
+/// NightInfo struct
+///
+/// This struct summarizes a night for an observer, e.g. for a nightly run sheet: see `Observer::night`.
+///
+/// # Attributes
+///
+/// * `start` - Start of the night: the sunset, or the reference time of `Observer::night` if the Sun does not set
+/// * `sunset` - Time of the sunset starting the night (None during the polar day or night)
+/// * `sunrise` - Time of the sunrise ending the night
+/// * `civil_dusk` - Time when the Sun sets below -6 degrees (end of the evening civil twilight)
+/// * `civil_dawn` - Time when the Sun rises above -6 degrees (start of the morning civil twilight)
+/// * `nautical_dusk` - Time when the Sun sets below -12 degrees
+/// * `nautical_dawn` - Time when the Sun rises above -12 degrees
+/// * `astronomical_dusk` - Time when the Sun sets below -18 degrees (start of the dark time)
+/// * `astronomical_dawn` - Time when the Sun rises above -18 degrees (end of the dark time)
+/// * `length` - Length of the night, from sunset to sunrise, in hours (0 during the polar day, at most 24)
+/// * `dark_length` - Length of the dark time, from astronomical dusk to dawn, in hours (at most 24)
+/// * `moonrise` - Time of the first moonrise after the sunset
+/// * `moonset` - Time of the first moonset after the sunset
+/// * `moon_illumination` - Illuminated fraction of the Moon at the middle of the night, in [0, 1]
+/// * `lst_dusk` - Local sidereal time at the astronomical dusk, in degrees
+/// * `lst_dawn` - Local sidereal time at the astronomical dawn, in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::{Observer, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, Some("P48"));
+/// let night = observer.night(&Time::new(2024, 9, 10, 20, 0, 0));
+/// println!("{}", night);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NightInfo {
+    pub start: Time,
+    pub sunset: Option<Time>,
+    pub sunrise: Option<Time>,
+    pub civil_dusk: Option<Time>,
+    pub civil_dawn: Option<Time>,
+    pub nautical_dusk: Option<Time>,
+    pub nautical_dawn: Option<Time>,
+    pub astronomical_dusk: Option<Time>,
+    pub astronomical_dawn: Option<Time>,
+    pub length: f64,
+    pub dark_length: f64,
+    pub moonrise: Option<Time>,
+    pub moonset: Option<Time>,
+    pub moon_illumination: f64,
+    pub lst_dusk: Option<f64>,
+    pub lst_dawn: Option<f64>,
+}
+
+/// Format an optional time for the summary of a night
+fn format_time(time: &Option<Time>) -> String {
+    match time {
+        Some(time) => format!("{} UTC", time),
+        None => "-".to_string(),
+    }
+}
+
+/// Format an optional local sidereal time (in degrees) as hours and minutes
+fn format_lst(lst: &Option<f64>) -> String {
+    match lst {
+        Some(lst) => {
+            let minutes = (lst.rem_euclid(360.0) * 4.0).round() as u32 % 1440;
+            format!("{:02}:{:02}", minutes / 60, minutes % 60)
+        }
+        None => "-".to_string(),
+    }
+}
+
+impl std::fmt::Display for NightInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "Sunset: {}", format_time(&self.sunset))?;
+        writeln!(f, "Civil dusk (-6 deg): {}", format_time(&self.civil_dusk))?;
+        writeln!(f, "Nautical dusk (-12 deg): {}", format_time(&self.nautical_dusk))?;
+        writeln!(f, "Astronomical dusk (-18 deg): {}", format_time(&self.astronomical_dusk))?;
+        writeln!(f, "Astronomical dawn (-18 deg): {}", format_time(&self.astronomical_dawn))?;
+        writeln!(f, "Nautical dawn (-12 deg): {}", format_time(&self.nautical_dawn))?;
+        writeln!(f, "Civil dawn (-6 deg): {}", format_time(&self.civil_dawn))?;
+        writeln!(f, "Sunrise: {}", format_time(&self.sunrise))?;
+        writeln!(f, "Night length: {:.2} h (dark time: {:.2} h)", self.length, self.dark_length)?;
+        writeln!(f, "Moonrise: {}", format_time(&self.moonrise))?;
+        writeln!(f, "Moonset: {}", format_time(&self.moonset))?;
+        writeln!(f, "Moon illumination: {:.1}%", self.moon_illumination * 100.0)?;
+        write!(f, "LST (astronomical dusk - dawn): {} - {}", format_lst(&self.lst_dusk), format_lst(&self.lst_dawn))
+    }
+}
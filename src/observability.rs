@@ -0,0 +1,926 @@
+use crate::airmass::AirmassModel;
+use crate::moon::illumination;
+use crate::night::NightInfo;
+use crate::observer::Observer;
+use crate::target::Target;
+use crate::time::Time;
+
+/// Constraint trait
+///
+/// This trait is implemented by all the observability constraints defined in this module.
+/// A constraint is evaluated for a target and an observer over a grid of times (see `time_grid`),
+/// and constraints can be combined with `AndConstraint` and `OrConstraint`.
+///
+/// # Methods
+///
+/// * `is_met` - Check if the constraint is met at each time of a grid
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{AirmassConstraint, Constraint, SunAltitudeConstraint, time_grid};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let times = time_grid(&Time::new(2024, 9, 11, 3, 0, 0), &Time::new(2024, 9, 11, 12, 0, 0), 60.0);
+///
+/// let constraints: Vec<Box<dyn Constraint>> = vec![
+///     Box::new(AirmassConstraint::new(2.0)),
+///     Box::new(SunAltitudeConstraint::new(-90.0, -18.0)),
+/// ];
+/// for constraint in &constraints {
+///     assert_eq!(constraint.is_met(&target, &observer, &times).len(), times.len());
+/// }
+/// ```
+pub trait Constraint {
+    /// Check if the constraint is met at each time of a grid
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target
+    /// * `observer` - The observer
+    /// * `times` - The times at which to evaluate the constraint
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<bool>` - True at each time when the constraint is met, false otherwise
+    fn is_met(&self, target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool>;
+}
+
+/// AltitudeConstraint struct
+///
+/// This constraint is met when the apparent altitude of the target (see `Target::altitude`) is in a range.
+///
+/// # Attributes
+///
+/// * `min` - Minimum altitude in degrees
+/// * `max` - Maximum altitude in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{AltitudeConstraint, Constraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let time = Time::new(2024, 8, 24, 6, 35, 34);
+///
+/// // the target is at an altitude of about 43 degrees
+/// assert_eq!(AltitudeConstraint::new(30.0, 90.0).is_met(&target, &observer, &[time.clone()]), vec![true]);
+/// assert_eq!(AltitudeConstraint::new(50.0, 90.0).is_met(&target, &observer, &[time]), vec![false]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AltitudeConstraint {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl AltitudeConstraint {
+    /// Create a new AltitudeConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum altitude in degrees
+    /// * `max` - Maximum altitude in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `AltitudeConstraint` - A new AltitudeConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn new(min: f64, max: f64) -> AltitudeConstraint {
+        if min > max {
+            panic!("min must be less than or equal to max");
+        }
+        AltitudeConstraint { min, max }
+    }
+}
+
+impl Constraint for AltitudeConstraint {
+    fn is_met(&self, target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        times
+            .iter()
+            .map(|time| {
+                let alt = target.altitude(observer, time);
+                alt >= self.min && alt <= self.max
+            })
+            .collect()
+    }
+}
+
+/// AirmassConstraint struct
+///
+/// This constraint is met when the airmass of the target is at most a given value (and the target is above the horizon).
+///
+/// # Attributes
+///
+/// * `max` - Maximum airmass
+/// * `model` - The formula used to compute the airmass from the altitude (Pickering 2002 by default)
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{AirmassConstraint, Constraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let times = vec![Time::new(2024, 8, 24, 6, 35, 34), Time::new(2024, 8, 24, 18, 35, 34)];
+///
+/// // the target is at an airmass of about 1.47, and then below the horizon
+/// assert_eq!(AirmassConstraint::new(1.5).is_met(&target, &observer, &times), vec![true, false]);
+/// assert_eq!(AirmassConstraint::new(1.4).is_met(&target, &observer, &times), vec![false, false]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AirmassConstraint {
+    pub max: f64,
+    pub model: AirmassModel,
+}
+
+impl AirmassConstraint {
+    /// Create a new AirmassConstraint, with the default airmass model
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum airmass
+    ///
+    /// # Returns
+    ///
+    /// * `AirmassConstraint` - A new AirmassConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is less than 1.
+    pub fn new(max: f64) -> AirmassConstraint {
+        AirmassConstraint::with_model(max, AirmassModel::default())
+    }
+
+    /// Create a new AirmassConstraint, with a given airmass model
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum airmass
+    /// * `model` - The formula used to compute the airmass from the altitude
+    ///
+    /// # Returns
+    ///
+    /// * `AirmassConstraint` - A new AirmassConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is less than 1.
+    pub fn with_model(max: f64, model: AirmassModel) -> AirmassConstraint {
+        if max < 1.0 {
+            panic!("max must be greater than or equal to 1");
+        }
+        AirmassConstraint { max, model }
+    }
+}
+
+impl Constraint for AirmassConstraint {
+    fn is_met(&self, target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        observer
+            .targets_airmasses_with_model(std::slice::from_ref(target), times, &self.model)
+            .remove(0)
+            .iter()
+            .map(|&airmass| airmass <= self.max)
+            .collect()
+    }
+}
+
+/// MoonSeparationConstraint struct
+///
+/// This constraint is met when the angular distance between the target and the Moon
+/// (see `Target::moon_separation`) is in a range.
+///
+/// # Attributes
+///
+/// * `min` - Minimum separation in degrees
+/// * `max` - Maximum separation in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{Constraint, MoonSeparationConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+///
+/// // the full moon of 2024 September 18 is about 24 degrees away
+/// let times = vec![Time::new(2024, 9, 18, 6, 0, 0)];
+/// assert_eq!(MoonSeparationConstraint::new(30.0, 180.0).is_met(&target, &observer, &times), vec![false]);
+/// assert_eq!(MoonSeparationConstraint::new(20.0, 180.0).is_met(&target, &observer, &times), vec![true]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoonSeparationConstraint {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl MoonSeparationConstraint {
+    /// Create a new MoonSeparationConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum separation in degrees
+    /// * `max` - Maximum separation in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `MoonSeparationConstraint` - A new MoonSeparationConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn new(min: f64, max: f64) -> MoonSeparationConstraint {
+        if min > max {
+            panic!("min must be less than or equal to max");
+        }
+        MoonSeparationConstraint { min, max }
+    }
+}
+
+impl Constraint for MoonSeparationConstraint {
+    fn is_met(&self, target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        times
+            .iter()
+            .map(|time| {
+                let separation = target.moon_separation(observer, time);
+                separation >= self.min && separation <= self.max
+            })
+            .collect()
+    }
+}
+
+/// MoonIlluminationConstraint struct
+///
+/// This constraint is met when the illuminated fraction of the Moon (see `moon::illumination`) is in a range,
+/// or when the Moon is below the horizon.
+///
+/// # Attributes
+///
+/// * `min` - Minimum illuminated fraction, in [0, 1]
+/// * `max` - Maximum illuminated fraction, in [0, 1]
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{Constraint, MoonIlluminationConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+///
+/// // the full moon of 2024 September 18 is up at 06:00 UTC, and down at 15:00 UTC
+/// let times = vec![Time::new(2024, 9, 18, 6, 0, 0), Time::new(2024, 9, 18, 15, 0, 0)];
+/// let dark = MoonIlluminationConstraint::new(0.0, 0.25);
+/// assert_eq!(dark.is_met(&target, &observer, &times), vec![false, true]);
+/// ```
+///
+/// # Notes
+///
+/// The Moon is below the horizon when the apparent altitude of its center is negative (see `Observer::moon_altaz`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoonIlluminationConstraint {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl MoonIlluminationConstraint {
+    /// Create a new MoonIlluminationConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum illuminated fraction, in [0, 1]
+    /// * `max` - Maximum illuminated fraction, in [0, 1]
+    ///
+    /// # Returns
+    ///
+    /// * `MoonIlluminationConstraint` - A new MoonIlluminationConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`, or if they are not in [0, 1].
+    pub fn new(min: f64, max: f64) -> MoonIlluminationConstraint {
+        if !(0.0..=1.0).contains(&min) || !(0.0..=1.0).contains(&max) {
+            panic!("min and max must be in [0, 1]");
+        }
+        if min > max {
+            panic!("min must be less than or equal to max");
+        }
+        MoonIlluminationConstraint { min, max }
+    }
+}
+
+impl Constraint for MoonIlluminationConstraint {
+    fn is_met(&self, _target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        times
+            .iter()
+            .map(|time| {
+                if observer.moon_altaz(time).alt < 0.0 {
+                    return true;
+                }
+                let fraction = illumination(time);
+                fraction >= self.min && fraction <= self.max
+            })
+            .collect()
+    }
+}
+
+/// SunAltitudeConstraint struct
+///
+/// This constraint is met when the apparent altitude of the Sun (see `Observer::sun_altaz`) is in a range,
+/// e.g. below -18 degrees for the astronomical night.
+///
+/// # Attributes
+///
+/// * `min` - Minimum altitude of the Sun in degrees
+/// * `max` - Maximum altitude of the Sun in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{Constraint, SunAltitudeConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+///
+/// // the Sun sets below -18 degrees between 03:15 and 03:30 UTC
+/// let times = vec![Time::new(2024, 9, 11, 3, 15, 0), Time::new(2024, 9, 11, 3, 30, 0)];
+/// let night = SunAltitudeConstraint::new(-90.0, -18.0);
+/// assert_eq!(night.is_met(&target, &observer, &times), vec![false, true]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SunAltitudeConstraint {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl SunAltitudeConstraint {
+    /// Create a new SunAltitudeConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum altitude of the Sun in degrees
+    /// * `max` - Maximum altitude of the Sun in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `SunAltitudeConstraint` - A new SunAltitudeConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn new(min: f64, max: f64) -> SunAltitudeConstraint {
+        if min > max {
+            panic!("min must be less than or equal to max");
+        }
+        SunAltitudeConstraint { min, max }
+    }
+}
+
+impl Constraint for SunAltitudeConstraint {
+    fn is_met(&self, _target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        times
+            .iter()
+            .map(|time| {
+                let alt = observer.sun_altaz(time).alt;
+                alt >= self.min && alt <= self.max
+            })
+            .collect()
+    }
+}
+
+/// HourAngleConstraint struct
+///
+/// This constraint is met when the hour angle of the target (see `Target::hour_angle`) is in a range.
+///
+/// # Attributes
+///
+/// * `min` - Minimum hour angle in degrees, in [-180, 180] (negative East of the meridian)
+/// * `max` - Maximum hour angle in degrees, in [-180, 180]
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{Constraint, HourAngleConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let time = Time::new(2024, 8, 24, 6, 35, 34);
+///
+/// // the target is about 51 degrees (3.4 hours) East of the meridian
+/// let within_4h = HourAngleConstraint::new(-60.0, 60.0);
+/// assert_eq!(within_4h.is_met(&target, &observer, &[time.clone()]), vec![true]);
+/// let within_3h = HourAngleConstraint::new(-45.0, 45.0);
+/// assert_eq!(within_3h.is_met(&target, &observer, &[time]), vec![false]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HourAngleConstraint {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl HourAngleConstraint {
+    /// Create a new HourAngleConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum hour angle in degrees, in [-180, 180]
+    /// * `max` - Maximum hour angle in degrees, in [-180, 180]
+    ///
+    /// # Returns
+    ///
+    /// * `HourAngleConstraint` - A new HourAngleConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`, or if they are not in [-180, 180].
+    pub fn new(min: f64, max: f64) -> HourAngleConstraint {
+        if !(-180.0..=180.0).contains(&min) || !(-180.0..=180.0).contains(&max) {
+            panic!("min and max must be in [-180, 180]");
+        }
+        if min > max {
+            panic!("min must be less than or equal to max");
+        }
+        HourAngleConstraint { min, max }
+    }
+}
+
+impl Constraint for HourAngleConstraint {
+    fn is_met(&self, target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        times
+            .iter()
+            .map(|time| {
+                let ha = target.hour_angle(observer, time);
+                ha >= self.min && ha <= self.max
+            })
+            .collect()
+    }
+}
+
+/// TimeWindowConstraint struct
+///
+/// This constraint is met between two times (included), e.g. for a scheduled block or a time-critical follow-up.
+///
+/// # Attributes
+///
+/// * `start` - Start of the window
+/// * `end` - End of the window
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{Constraint, TimeWindowConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+///
+/// let window = TimeWindowConstraint::new(Time::new(2024, 9, 11, 4, 0, 0), Time::new(2024, 9, 11, 6, 0, 0));
+/// let times = vec![Time::new(2024, 9, 11, 3, 0, 0), Time::new(2024, 9, 11, 5, 0, 0)];
+/// assert_eq!(window.is_met(&target, &observer, &times), vec![false, true]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeWindowConstraint {
+    pub start: Time,
+    pub end: Time,
+}
+
+impl TimeWindowConstraint {
+    /// Create a new TimeWindowConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Start of the window
+    /// * `end` - End of the window
+    ///
+    /// # Returns
+    ///
+    /// * `TimeWindowConstraint` - A new TimeWindowConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is after `end`.
+    pub fn new(start: Time, end: Time) -> TimeWindowConstraint {
+        if start.to_jd() > end.to_jd() {
+            panic!("start must be before end");
+        }
+        TimeWindowConstraint { start, end }
+    }
+}
+
+impl Constraint for TimeWindowConstraint {
+    fn is_met(&self, _target: &Target, _observer: &Observer, times: &[Time]) -> Vec<bool> {
+        let (start, end) = (self.start.to_jd(), self.end.to_jd());
+        times
+            .iter()
+            .map(|time| {
+                let jd = time.to_jd();
+                jd >= start && jd <= end
+            })
+            .collect()
+    }
+}
+
+/// GalacticLatitudeConstraint struct
+///
+/// This constraint is met when the absolute Galactic latitude of the target is in a range,
+/// e.g. to avoid the crowded and extincted Galactic plane. It does not depend on the time.
+///
+/// # Attributes
+///
+/// * `min` - Minimum absolute Galactic latitude in degrees
+/// * `max` - Maximum absolute Galactic latitude in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{Constraint, GalacticLatitudeConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let times = vec![Time::new(2024, 9, 11, 6, 0, 0)];
+/// let off_plane = GalacticLatitudeConstraint::new(15.0, 90.0);
+///
+/// // Vega is at b = -42 degrees
+/// let target = Target::new(6.374817, 20.242942, None);
+/// assert_eq!(off_plane.is_met(&target, &observer, &times), vec![true]);
+/// let target = Target::from_galactic(30.0, 2.0, None);
+/// assert_eq!(off_plane.is_met(&target, &observer, &times), vec![false]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GalacticLatitudeConstraint {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl GalacticLatitudeConstraint {
+    /// Create a new GalacticLatitudeConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum absolute Galactic latitude in degrees
+    /// * `max` - Maximum absolute Galactic latitude in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `GalacticLatitudeConstraint` - A new GalacticLatitudeConstraint object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`, or if they are not in [0, 90].
+    pub fn new(min: f64, max: f64) -> GalacticLatitudeConstraint {
+        if !(0.0..=90.0).contains(&min) || !(0.0..=90.0).contains(&max) {
+            panic!("min and max must be in [0, 90]");
+        }
+        if min > max {
+            panic!("min must be less than or equal to max");
+        }
+        GalacticLatitudeConstraint { min, max }
+    }
+}
+
+impl Constraint for GalacticLatitudeConstraint {
+    fn is_met(&self, target: &Target, _observer: &Observer, times: &[Time]) -> Vec<bool> {
+        let b = target.radec2lb().1.abs();
+        vec![b >= self.min && b <= self.max; times.len()]
+    }
+}
+
+/// AndConstraint struct
+///
+/// This constraint is met when all of its constraints are met (it is always met if it has none).
+///
+/// # Attributes
+///
+/// * `constraints` - The constraints to combine
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{AltitudeConstraint, AndConstraint, Constraint, HourAngleConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let times = vec![Time::new(2024, 8, 24, 6, 35, 34)];
+///
+/// let constraint = AndConstraint::new(vec![
+///     Box::new(AltitudeConstraint::new(30.0, 90.0)),
+///     Box::new(HourAngleConstraint::new(-45.0, 45.0)),
+/// ]);
+/// assert_eq!(constraint.is_met(&target, &observer, &times), vec![false]);
+/// ```
+pub struct AndConstraint {
+    pub constraints: Vec<Box<dyn Constraint>>,
+}
+
+impl AndConstraint {
+    /// Create a new AndConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `constraints` - The constraints to combine
+    ///
+    /// # Returns
+    ///
+    /// * `AndConstraint` - A new AndConstraint object
+    pub fn new(constraints: Vec<Box<dyn Constraint>>) -> AndConstraint {
+        AndConstraint { constraints }
+    }
+}
+
+impl Constraint for AndConstraint {
+    fn is_met(&self, target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        let mut met = vec![true; times.len()];
+        for constraint in &self.constraints {
+            for (m, c) in met.iter_mut().zip(constraint.is_met(target, observer, times)) {
+                *m = *m && c;
+            }
+        }
+        met
+    }
+}
+
+/// OrConstraint struct
+///
+/// This constraint is met when at least one of its constraints is met (it is never met if it has none).
+///
+/// # Attributes
+///
+/// * `constraints` - The constraints to combine
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{AltitudeConstraint, Constraint, HourAngleConstraint, OrConstraint};
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let times = vec![Time::new(2024, 8, 24, 6, 35, 34)];
+///
+/// let constraint = OrConstraint::new(vec![
+///     Box::new(AltitudeConstraint::new(30.0, 90.0)),
+///     Box::new(HourAngleConstraint::new(-45.0, 45.0)),
+/// ]);
+/// assert_eq!(constraint.is_met(&target, &observer, &times), vec![true]);
+/// ```
+pub struct OrConstraint {
+    pub constraints: Vec<Box<dyn Constraint>>,
+}
+
+impl OrConstraint {
+    /// Create a new OrConstraint
+    ///
+    /// # Arguments
+    ///
+    /// * `constraints` - The constraints to combine
+    ///
+    /// # Returns
+    ///
+    /// * `OrConstraint` - A new OrConstraint object
+    pub fn new(constraints: Vec<Box<dyn Constraint>>) -> OrConstraint {
+        OrConstraint { constraints }
+    }
+}
+
+impl Constraint for OrConstraint {
+    fn is_met(&self, target: &Target, observer: &Observer, times: &[Time]) -> Vec<bool> {
+        let mut met = vec![false; times.len()];
+        for constraint in &self.constraints {
+            for (m, c) in met.iter_mut().zip(constraint.is_met(target, observer, times)) {
+                *m = *m || c;
+            }
+        }
+        met
+    }
+}
+
+/// Observability struct
+///
+/// This struct represents when a target is observable over a grid of times (see `observability`).
+///
+/// # Attributes
+///
+/// * `times` - The times of the grid
+/// * `observable` - True at each time of the grid when all the constraints are met
+/// * `windows` - The observable windows: the first and last times of each run of consecutive observable times
+/// * `fraction` - The fraction of the times of the grid when the target is observable, in [0, 1]
+///
+/// # Methods
+///
+/// * `is_observable` - Check if the target is observable at any time of the grid
+#[derive(Debug, Clone)]
+pub struct Observability {
+    pub times: Vec<Time>,
+    pub observable: Vec<bool>,
+    pub windows: Vec<(Time, Time)>,
+    pub fraction: f64,
+}
+
+impl Observability {
+    /// Check if the target is observable at any time of the grid
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the constraints are met at least once, false otherwise
+    pub fn is_observable(&self) -> bool {
+        self.observable.iter().any(|&o| o)
+    }
+}
+
+/// Build a regular grid of times between two times (both included)
+///
+/// # Arguments
+///
+/// * `start` - The first time of the grid
+/// * `end` - The last time of the grid (if it falls on a step)
+/// * `step` - The step between two times, in minutes
+///
+/// # Returns
+///
+/// * `Vec<Time>` - The times of the grid
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::time_grid;
+/// use flare::Time;
+///
+/// let times = time_grid(&Time::new(2024, 9, 11, 3, 0, 0), &Time::new(2024, 9, 11, 4, 0, 0), 15.0);
+/// assert_eq!(times.len(), 5);
+/// assert_eq!(times[1].to_string(None), "2024-09-11 03:15:00 UTC");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `step` is not positive, or if `start` is after `end`.
+pub fn time_grid(start: &Time, end: &Time, step: f64) -> Vec<Time> {
+    if step <= 0.0 {
+        panic!("step must be positive");
+    }
+    let (start, end) = (start.to_jd(), end.to_jd());
+    if start > end {
+        panic!("start must be before end");
+    }
+    // times have a resolution of a second, and Time::from_jd truncates: half a second of margin
+    // rounds each time of the grid to the nearest second, and keeps the last one
+    let n = ((end - start) * 1440.0 / step + 0.5 / 60.0 / step).floor() as usize;
+    (0..=n).map(|i| Time::from_jd(start + i as f64 * step / 1440.0 + 0.5 / 86400.0)).collect()
+}
+
+/// Find when a target is observable over a grid of times, given a constraint
+///
+/// # Arguments
+///
+/// * `constraint` - The constraint, e.g. an `AndConstraint` of several constraints
+/// * `target` - The target
+/// * `observer` - The observer
+/// * `times` - The times at which to evaluate the constraint (see `time_grid`)
+///
+/// # Returns
+///
+/// * `Observability` - The observable times, windows and fraction of the grid
+///
+/// # Examples
+///
+/// ```
+/// use flare::horizon::MountLimits;
+/// use flare::observability::{
+///     observability, time_grid, AirmassConstraint, AndConstraint, MoonSeparationConstraint, SunAltitudeConstraint,
+/// };
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let times = time_grid(&Time::new(2024, 9, 11, 2, 0, 0), &Time::new(2024, 9, 11, 14, 0, 0), 10.0);
+///
+/// let constraint = AndConstraint::new(vec![
+///     Box::new(SunAltitudeConstraint::new(-90.0, -18.0)),
+///     Box::new(AirmassConstraint::new(2.0)),
+///     Box::new(MoonSeparationConstraint::new(30.0, 180.0)),
+/// ]);
+/// let result = observability(&constraint, &target, &observer, &times);
+/// assert!(result.is_observable());
+/// assert_eq!(result.windows.len(), 1);
+/// let (start, end) = &result.windows[0];
+/// println!("Observable from {} to {} ({:.0}% of the grid)", start, end, result.fraction * 100.0);
+/// assert_eq!(start.to_string(None), "2024-09-11 04:30:00 UTC");
+/// assert_eq!(end.to_string(None), "2024-09-11 12:00:00 UTC");
+///
+/// // a mount that can't point more than 3 hours from the meridian
+/// let observer = observer.with_mount_limits(MountLimits::new(-45.0, 45.0, -30.0, 90.0));
+/// let limited = observability(&constraint, &target, &observer, &times);
+/// assert!(limited.fraction < result.fraction);
+/// ```
+///
+/// # Notes
+///
+/// The target is only observable when it is above the horizon mask and within the mount limits of the observer
+/// (see `Observer::is_accessible`), whatever the constraint.
+pub fn observability(constraint: &dyn Constraint, target: &Target, observer: &Observer, times: &[Time]) -> Observability {
+    let observable = constraint
+        .is_met(target, observer, times)
+        .into_iter()
+        .zip(times)
+        .map(|(met, time)| met && observer.is_accessible(target, time))
+        .collect::<Vec<bool>>();
+    let mut windows = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, &o) in observable.iter().enumerate() {
+        match (o, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                windows.push((times[s].clone(), times[i - 1].clone()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        windows.push((times[s].clone(), times[times.len() - 1].clone()));
+    }
+    let fraction = if times.is_empty() {
+        0.0
+    } else {
+        observable.iter().filter(|&&o| o).count() as f64 / times.len() as f64
+    };
+    Observability { times: times.to_vec(), observable, windows, fraction }
+}
+
+/// Find when a target is observable during a night (from sunset to sunrise, or the whole day during the polar night), given a constraint
+///
+/// # Arguments
+///
+/// * `constraint` - The constraint, e.g. an `AndConstraint` of several constraints
+/// * `target` - The target
+/// * `observer` - The observer
+/// * `night` - The night (see `Observer::night`)
+/// * `step` - The step of the grid of times, in minutes
+///
+/// # Returns
+///
+/// * `Option<Observability>` - The observable times and windows, and the fraction of the night when the target is
+///   observable, or None during the polar day, when there is no night
+///
+/// # Examples
+///
+/// ```
+/// use flare::observability::{
+///     night_observability, observability, time_grid, AirmassConstraint, AndConstraint, SunAltitudeConstraint,
+/// };
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let target = Target::new(6.374817, 20.242942, None);
+/// let night = observer.night(&Time::new(2024, 9, 10, 20, 0, 0));
+///
+/// // the target is below an airmass of 2 during most of the dark time
+/// let constraint = AndConstraint::new(vec![
+///     Box::new(SunAltitudeConstraint::new(-90.0, -18.0)),
+///     Box::new(AirmassConstraint::new(2.0)),
+/// ]);
+/// let result = night_observability(&constraint, &target, &observer, &night, 5.0).unwrap();
+/// assert!((result.fraction - 0.68).abs() < 0.01);
+///
+/// // during the polar night the Sun does not set nor rise: the night lasts the whole day
+/// let observer = Observer::new(78.22, 15.65, 10.0, None);
+/// let time = Time::new(2024, 12, 21, 12, 0, 0);
+/// let night = observer.night(&time);
+/// let result = night_observability(&constraint, &target, &observer, &night, 5.0).unwrap();
+/// let end = Time::from_jd(time.to_jd() + 1.0);
+/// assert_eq!(result.fraction, observability(&constraint, &target, &observer, &time_grid(&time, &end, 5.0)).fraction);
+/// assert!((result.fraction - 0.18).abs() < 0.01);
+///
+/// // and during the polar day, there is no night
+/// let night = observer.night(&Time::new(2024, 6, 21, 12, 0, 0));
+/// assert!(night_observability(&constraint, &target, &observer, &night, 5.0).is_none());
+/// ```
+pub fn night_observability(
+    constraint: &dyn Constraint,
+    target: &Target,
+    observer: &Observer,
+    night: &NightInfo,
+    step: f64,
+) -> Option<Observability> {
+    match (&night.sunset, &night.sunrise) {
+        (Some(sunset), Some(sunrise)) => {
+            Some(observability(constraint, target, observer, &time_grid(sunset, sunrise, step)))
+        }
+        // during the polar night, the night lasts the whole day
+        _ if night.length > 0.0 => {
+            let end = Time::from_jd(night.start.to_jd() + night.length / 24.0);
+            Some(observability(constraint, target, observer, &time_grid(&night.start, &end, step)))
+        }
+        _ => None,
+    }
+}
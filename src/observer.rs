@@ -1,6 +1,18 @@
+use crate::airmass::AirmassModel;
+use crate::altaz::{hadec2altaz, normalize_hour_angle, AltAz};
+use crate::corrections::{refraction, Atmosphere};
+use crate::ecliptic::{nutation, true_equator_rotation, true_obliquity};
+use crate::geodesy::geodetic_to_geocentric;
+use crate::horizon::{HorizonMask, MountLimits};
 use crate::spatial::DEGRA;
-use crate::target::Target;
+use crate::moon;
+use crate::night::NightInfo;
+use crate::planets::Planet;
+use crate::rise_set::{horizon_crossing, hour_angle_event, HorizonCrossing, SearchMode, SunRiseSet, SIDEREAL_RATE};
+use crate::sun;
+use crate::target::{SkyObject, Target};
 use crate::time::Time;
+use crate::vector::{Cartesian3, Rotation3};
 
 /// Observer struct
 /// 
@@ -12,12 +24,33 @@ use crate::time::Time;
 /// * `lat` - Latitude of the observer in degrees
 /// * `lon` - Longitude of the observer in degrees
 /// * `elevation` - Elevation of the observer in meters
+/// * `horizon_mask` - Optional horizon mask of the site (minimum altitude per azimuth)
+/// * `mount_limits` - Optional pointing limits of the mount (hour angle and declination)
 /// 
 /// # Methods
 /// 
 /// * `new` - Create a new Observer
+/// * `from_site` - Create an Observer at a built-in site (see `sites`)
+/// * `with_horizon_mask` - Set the horizon mask of the Observer
+/// * `with_mount_limits` - Set the mount limits of the Observer
+/// * `is_accessible` - Check if a target is above the horizon mask and within the mount limits
 /// * `local_sidereal_time` - Calculate the local sidereal time at a given time
+/// * `itrs_position` - Calculate the Earth-fixed (ITRS) position of the observer
+/// * `gcrs_position` - Calculate the position of the observer in the GCRS at a given time
+/// * `gcrs_velocity` - Calculate the velocity of the observer in the GCRS at a given time
+/// * `atmosphere` - Get the atmospheric conditions at the observer, with the pressure estimated from the elevation
+/// * `sun_altaz` - Calculate the altitude and azimuth of the Sun at a given time
+/// * `moon_altaz` - Calculate the altitude and azimuth of the Moon at a given time
+/// * `planet_altaz` - Calculate the altitude and azimuth of a planet at a given time
+/// * `moon_rise_set` - Calculate the time of the next moonrise & moonset
+/// * `target_rise_time` - Calculate the time when an object (target, Sun or Moon) rises
+/// * `target_set_time` - Calculate the time when an object (target, Sun or Moon) sets
+/// * `target_meridian_transit` - Calculate the time when an object crosses the meridian (upper culmination)
+/// * `target_antitransit` - Calculate the time when an object crosses the meridian at its lowest (lower culmination)
+/// * `sun_set_time` - Calculate the time of the next sunrise & sunset, or whether the Sun stays up or down
+/// * `night` - Summarize the next night: sunset, twilights, sunrise, moonrise & moonset, Moon illumination and LST range
 /// * `targets_airmasses` - Calculate the airmasses of a list of targets at a list of times
+/// * `targets_airmasses_with_model` - Calculate the airmasses of a list of targets at a list of times, with a given airmass model
 /// * `to_string` - Convert the Observer to a string
 /// 
 /// # Examples
@@ -31,12 +64,13 @@ use crate::time::Time;
 /// println!("Local sidereal time: {}", lst);
 /// assert_eq!(lst, 315.09169822871746);
 /// ```
-
 pub struct Observer<'a> {
     pub name: Option<&'a str>,
     pub lat: f64,
     pub lon: f64,
-    pub elevation: f64, // not used yet, but will be used for refraction correction
+    pub elevation: f64,
+    pub horizon_mask: Option<HorizonMask>,
+    pub mount_limits: Option<MountLimits>,
 }
 
 impl <'a> Observer<'a> {
@@ -76,8 +110,120 @@ impl <'a> Observer<'a> {
     /// assert_eq!(observer.name, None);
     /// println!("{}", observer.to_string());
     /// ```
-    pub fn new(lat: f64, lon: f64, elevation: f64, name: Option<&str>) -> Observer {
-        Observer { name, lat, lon, elevation }
+    pub fn new(lat: f64, lon: f64, elevation: f64, name: Option<&str>) -> Observer<'_> {
+        Observer { name, lat, lon, elevation, horizon_mask: None, mount_limits: None }
+    }
+
+    /// Set the horizon mask of the Observer
+    /// 
+    /// # Arguments
+    /// 
+    /// * `horizon_mask` - The horizon mask of the site (see `horizon::HorizonMask`)
+    /// 
+    /// # Returns
+    /// 
+    /// * `Observer` - The Observer, with the horizon mask
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::horizon::HorizonMask;
+    /// use flare::Observer;
+    /// 
+    /// let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/horizons/example.txt");
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None)
+    ///     .with_horizon_mask(HorizonMask::from_file(path).unwrap());
+    /// assert!(observer.horizon_mask.is_some());
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// The horizon mask is honored by `target_rise_time`, `target_set_time`, `is_accessible` and the observability
+    /// calculations (see `observability::observability`), but not by the sunrise, sunset, twilight and moonrise times.
+    pub fn with_horizon_mask(mut self, horizon_mask: HorizonMask) -> Observer<'a> {
+        self.horizon_mask = Some(horizon_mask);
+        self
+    }
+
+    /// Set the mount limits of the Observer
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mount_limits` - The pointing limits of the mount (see `horizon::MountLimits`)
+    /// 
+    /// # Returns
+    /// 
+    /// * `Observer` - The Observer, with the mount limits
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::horizon::MountLimits;
+    /// use flare::Observer;
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None)
+    ///     .with_mount_limits(MountLimits::new(-90.0, 90.0, -31.0, 90.0));
+    /// assert_eq!(observer.mount_limits.unwrap().dec_min, -31.0);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// Like the horizon mask, the mount limits are honored by `target_rise_time`, `target_set_time`,
+    /// `is_accessible` and the observability calculations.
+    pub fn with_mount_limits(mut self, mount_limits: MountLimits) -> Observer<'a> {
+        self.mount_limits = Some(mount_limits);
+        self
+    }
+
+    /// Check if a target is above the horizon mask and within the mount limits of the Observer
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The target
+    /// * `time` - Time object representing the time at which to check the target
+    /// 
+    /// # Returns
+    /// 
+    /// * `bool` - True if the target is above the horizon mask and within the mount limits
+    ///   (always true if the Observer has neither)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::horizon::{HorizonMask, MountLimits};
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// // the target is at an altitude of 43 degrees in the East, 51 degrees from the meridian
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// assert!(observer.is_accessible(&target, &time));
+    /// let observer = observer.with_horizon_mask(HorizonMask::new(&[(0.0, 10.0), (90.0, 50.0), (180.0, 10.0)]));
+    /// assert!(!observer.is_accessible(&target, &time));
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None)
+    ///     .with_mount_limits(MountLimits::new(-45.0, 45.0, -30.0, 90.0));
+    /// assert!(!observer.is_accessible(&target, &time));
+    /// ```
+    pub fn is_accessible(&self, target: &Target, time: &Time) -> bool {
+        let (ha, dec) = target.hadec(self, time);
+        let (alt, az) = hadec2altaz(ha, dec, self.lat);
+        self.limits_margin(ha, dec, alt, az) >= 0.0
+    }
+
+    /// Distance (in degrees) of a position to the horizon mask and the mount limits, negative outside of them,
+    /// from its hour angle, declination, geometric altitude and azimuth (infinite without mask nor limits)
+    pub(crate) fn limits_margin(&self, ha: f64, dec: f64, alt: f64, az: f64) -> f64 {
+        let mut margin = f64::INFINITY;
+        if let Some(mask) = &self.horizon_mask {
+            // the altitudes of the mask are apparent altitudes
+            margin = margin.min(alt + refraction(alt) - mask.min_altitude(az));
+        }
+        if let Some(limits) = &self.mount_limits {
+            margin = margin.min(limits.margin(ha, dec));
+        }
+        margin
     }
 
     /// Calculate the local sidereal time at a given time
@@ -107,6 +253,481 @@ impl <'a> Observer<'a> {
         lst % 360.0
     }
 
+    /// Get the atmospheric conditions at the observer, with the pressure estimated from its elevation
+    /// 
+    /// # Arguments
+    /// 
+    /// * `temperature` - Ambient temperature at the observer in degrees Celsius
+    /// * `humidity` - Relative humidity at the observer, in [0, 1]
+    /// * `wavelength` - Effective wavelength of the observations in micrometers
+    /// 
+    /// # Returns
+    /// 
+    /// * `Atmosphere` - The atmospheric conditions, to compute the refraction
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::Observer;
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let atmosphere = observer.atmosphere(10.0, 0.5, 0.62);
+    /// assert!((atmosphere.pressure - 808.8).abs() < 0.1);
+    /// ```
+    pub fn atmosphere(&self, temperature: f64, humidity: f64, wavelength: f64) -> Atmosphere {
+        Atmosphere::from_elevation(self.elevation, temperature, humidity, wavelength)
+    }
+
+    /// Position of the observer relative to the center of the Earth at a given time,
+    /// in km, in the equatorial frame of date (the ITRS position rotated by the mean sidereal time)
+    pub(crate) fn geocentric_position(&self, time: &Time) -> Cartesian3 {
+        Rotation3::about_z(-time.to_gst()).apply_cartesian(&self.itrs_position())
+    }
+
+    /// Calculate the Earth-fixed (ITRS) position of the Observer
+    /// 
+    /// # Returns
+    /// 
+    /// * `Cartesian3` - The geocentric position of the Observer in km, from its geodetic coordinates on the WGS84
+    ///   ellipsoid (see `geodesy::geodetic_to_geocentric`)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::geodesy::geocentric_to_geodetic;
+    /// use flare::Observer;
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let position = observer.itrs_position();
+    /// assert!((position.norm() - 6373.577).abs() < 1e-3);
+    /// 
+    /// let (lat, lon, height) = geocentric_to_geodetic(&position);
+    /// assert!((lat - observer.lat).abs() < 1e-9 && (lon - observer.lon).abs() < 1e-9);
+    /// assert!((height - observer.elevation).abs() < 1e-6);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// The elevation of the Observer is taken as its height above the WGS84 ellipsoid.
+    pub fn itrs_position(&self) -> Cartesian3 {
+        geodetic_to_geocentric(self.lat, self.lon, self.elevation)
+    }
+
+    /// Calculate the position of the Observer in the GCRS (geocentric, J2000 equator and equinox) at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - Time object representing the time at which to calculate the position
+    /// 
+    /// # Returns
+    /// 
+    /// * `Cartesian3` - The position of the Observer relative to the center of the Earth in km
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// let position = observer.gcrs_position(&time);
+    /// assert!((position.norm() - observer.itrs_position().norm()).abs() < 1e-9);
+    /// 
+    /// // the Observer is in the direction of its zenith: RA = LST and DEC = geocentric latitude, precessed to J2000
+    /// let (ra, dec) = position.direction().unwrap().to_radec();
+    /// assert!((ra - 314.84).abs() < 0.01);
+    /// assert!((dec - 33.09).abs() < 0.01);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// The Earth is rotated by the apparent sidereal time, and the true equator and equinox of date are rotated back
+    /// to J2000 (precession and nutation). Polar motion, the difference between UT1 and UTC and the frame bias
+    /// between the GCRS and the J2000 frame are neglected: the position is accurate to about 20 m.
+    pub fn gcrs_position(&self, time: &Time) -> Cartesian3 {
+        self.gcrs_rotation(time).apply_cartesian(&self.itrs_position())
+    }
+
+    /// Calculate the velocity of the Observer in the GCRS (geocentric, J2000 equator and equinox) at a given time,
+    /// due to the rotation of the Earth
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - Time object representing the time at which to calculate the velocity
+    /// 
+    /// # Returns
+    /// 
+    /// * `Cartesian3` - The velocity of the Observer relative to the center of the Earth in km/s
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// let velocity = observer.gcrs_velocity(&time);
+    /// 
+    /// // at a latitude of 33 degrees, the rotation of the Earth moves the Observer at 390 m/s, towards the East
+    /// assert!((velocity.norm() - 0.38896).abs() < 1e-5);
+    /// assert!(velocity.dot(&observer.gcrs_position(&time)).abs() < 1e-9);
+    /// 
+    /// // e.g. for radial velocity corrections: the projection on the direction of a target, here rising in the East
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let direction = flare::vector::UnitVector::from_radec(target.ra, target.dec).cartesian();
+    /// assert!((velocity.dot(&direction) - 0.2858).abs() < 1e-4);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// Only the diurnal rotation is included, not the orbital motion of the Earth (about 30 km/s).
+    pub fn gcrs_velocity(&self, time: &Time) -> Cartesian3 {
+        let position = self.itrs_position();
+        // the angular velocity of the Earth, in rad/s
+        let omega = SIDEREAL_RATE * DEGRA / 86400.0;
+        let velocity = Cartesian3::new(-omega * position.y, omega * position.x, 0.0);
+        self.gcrs_rotation(time).apply_cartesian(&velocity)
+    }
+
+    /// Rotation from the Earth-fixed frame (ITRS) to the GCRS at a given time
+    fn gcrs_rotation(&self, time: &Time) -> Rotation3 {
+        let (dpsi, _) = nutation(time);
+        let apparent_sidereal_time = time.to_gst() + dpsi * (true_obliquity(time) * DEGRA).cos();
+        Rotation3::about_z(-apparent_sidereal_time).then(&true_equator_rotation(time).inverse())
+    }
+
+    /// Calculate the altitude and azimuth of the Sun at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - Time object representing the time at which to calculate the position
+    /// 
+    /// # Returns
+    /// 
+    /// * `AltAz` - The apparent altitude (corrected for refraction) and the azimuth (East of North) of the Sun in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// 
+    /// // around noon (local time), the Sun is high in the South
+    /// let altaz = observer.sun_altaz(&Time::new(2024, 9, 10, 19, 45, 0));
+    /// assert!(altaz.alt > 55.0 && (altaz.az - 180.0).abs() < 10.0);
+    /// 
+    /// // and it is below the horizon at midnight
+    /// let altaz = observer.sun_altaz(&Time::new(2024, 9, 10, 7, 45, 0));
+    /// assert!(altaz.alt < -30.0);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// This uses the topocentric position of the Sun from `sun::topocentric_position`.
+    pub fn sun_altaz(&self, time: &Time) -> AltAz {
+        let sun = sun::topocentric_position(self, time);
+        let ha = normalize_hour_angle(self.local_sidereal_time(time) - sun.ra);
+        let (alt, az) = hadec2altaz(ha, sun.dec, self.lat);
+        AltAz::new(alt + refraction(alt), az)
+    }
+
+    /// Calculate the altitude and azimuth of the Moon at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - Time object representing the time at which to calculate the position
+    /// 
+    /// # Returns
+    /// 
+    /// * `AltAz` - The apparent altitude (corrected for refraction) and the azimuth (East of North) of the Moon in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// 
+    /// // a few days after the new moon, the Moon sets after the Sun, in the West
+    /// let altaz = observer.moon_altaz(&Time::new(2024, 9, 7, 3, 0, 0));
+    /// assert!(altaz.alt > 0.0 && altaz.alt < 20.0 && altaz.az > 180.0);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// This uses the topocentric position of the Moon from `moon::topocentric_position`:
+    /// the parallax of the Moon moves it by up to a degree compared to its geocentric position.
+    pub fn moon_altaz(&self, time: &Time) -> AltAz {
+        let moon = moon::topocentric_position(self, time);
+        let ha = normalize_hour_angle(self.local_sidereal_time(time) - moon.ra);
+        let (alt, az) = hadec2altaz(ha, moon.dec, self.lat);
+        AltAz::new(alt + refraction(alt), az)
+    }
+
+    /// Calculate the altitude and azimuth of a planet at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `planet` - The planet
+    /// * `time` - Time object representing the time at which to calculate the position
+    /// 
+    /// # Returns
+    /// 
+    /// * `AltAz` - The apparent altitude (corrected for refraction) and the azimuth (East of North) of the planet in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// 
+    /// // at opposition, Jupiter is high in the South around local midnight
+    /// let altaz = observer.planet_altaz(&Planet::Jupiter, &Time::new(2023, 11, 3, 7, 30, 0));
+    /// assert!(altaz.alt > 65.0 && (altaz.az - 180.0).abs() < 25.0);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// This uses the topocentric position of the planet from `Planet::topocentric_position`.
+    pub fn planet_altaz(&self, planet: &Planet, time: &Time) -> AltAz {
+        let planet = planet.topocentric_position(self, time);
+        let ha = normalize_hour_angle(self.local_sidereal_time(time) - planet.ra);
+        let (alt, az) = hadec2altaz(ha, planet.dec, self.lat);
+        AltAz::new(alt + refraction(alt), az)
+    }
+
+    /// Calculate the time of the next moonrise & moonset (in UTC)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `after` - Optional Time object representing the time after which to calculate the next moonrise & moonset
+    /// 
+    /// # Returns
+    /// 
+    /// * (`HorizonCrossing`, `HorizonCrossing`) - A tuple with the next moonrise & moonset (up to a synodic month later),
+    ///   or `AlwaysUp` / `AlwaysDown` if the Moon does not cross the horizon in the meantime
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::rise_set::HorizonCrossing;
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let time = Time::new(2024, 9, 10, 3, 0, 0);
+    /// let (moonrise, moonset) = observer.moon_rise_set(Some(&time));
+    /// let (moonrise, moonset) = (moonrise.time().unwrap(), moonset.time().unwrap());
+    /// assert!(moonset.to_jd() > time.to_jd() && moonrise.to_jd() > moonset.to_jd());
+    /// 
+    /// // at moonrise, the center of the Moon is still below the geometric horizon,
+    /// // by 34' of refraction and the radius of the Moon
+    /// let alt = observer.moon_altaz(moonrise).alt;
+    /// assert!(alt < -0.75 && alt > -0.85);
+    /// 
+    /// // in Svalbard, close to the 2025 major lunar standstill, the Moon stays up for more than a week
+    /// let svalbard = Observer::new(78.22, 15.65, 10.0, None);
+    /// let time = Time::new(2025, 1, 9, 0, 0, 0);
+    /// match svalbard.moon_rise_set(Some(&time)) {
+    ///     (HorizonCrossing::At(moonrise), HorizonCrossing::At(moonset)) => {
+    ///         let days = moonset.to_jd() - time.to_jd();
+    ///         assert!(days > 8.0 && days < 9.0);
+    ///         assert!(moonrise.to_jd() > moonset.to_jd());
+    ///     }
+    ///     _ => panic!("the Moon rises and sets within a synodic month"),
+    /// }
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// The Moon rises and sets when its upper limb is on the apparent horizon, with 34' of refraction,
+    /// at the topocentric position of the Moon (see `moon::Moon`), regardless of the horizon mask and the mount
+    /// limits of the observer. Use `target_rise_time` and `target_set_time` with `moon::Moon` for other search modes
+    /// or horizons (they honor the horizon mask and the mount limits).
+    pub fn moon_rise_set(&self, after: Option<&Time>) -> (HorizonCrossing, HorizonCrossing) {
+        let after = match after {
+            Some(time) => time.clone(),
+            None => Time::now(),
+        };
+        let moonrise = horizon_crossing(&moon::Moon, self, &after, SearchMode::Next, None, true, false);
+        let moonset = horizon_crossing(&moon::Moon, self, &after, SearchMode::Next, None, false, false);
+        (moonrise, moonset)
+    }
+
+    /// Calculate the time when an object rises above the horizon
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The object: a `Target`, the Sun (`sun::Sun`) or the Moon (`moon::Moon`)
+    /// * `time` - Time object representing the reference time of the search
+    /// * `mode` - Whether to find the next rise, the previous one, or the one closest to the reference time
+    /// * `horizon` - Optional geometric altitude of the horizon in degrees
+    ///   (by default, the apparent horizon of the object, see `SkyObject::horizon_altitude`)
+    /// 
+    /// # Returns
+    /// 
+    /// * `HorizonCrossing` - The time of the rise, or `AlwaysUp` / `AlwaysDown` if the object does not cross
+    ///   the horizon within its search window (`SkyObject::search_window`: a day and a half, or a synodic month for the Moon)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::horizon::HorizonMask;
+    /// use flare::rise_set::{HorizonCrossing, SearchMode};
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// // the target rose a few hours ago, and will rise again a sidereal day later
+    /// let previous = observer.target_rise_time(&target, &time, SearchMode::Previous, None);
+    /// let next = observer.target_rise_time(&target, &time, SearchMode::Next, None);
+    /// let (previous, next) = (previous.time().unwrap().to_jd(), next.time().unwrap().to_jd());
+    /// assert!(previous < time.to_jd() && next > time.to_jd());
+    /// assert!(((next - previous) * 1440.0 - 1436.07).abs() < 0.1);
+    /// 
+    /// // with a horizon at 30 degrees of altitude, it rises later
+    /// let later = observer.target_rise_time(&target, &time, SearchMode::Previous, Some(30.0));
+    /// assert!(later.time().unwrap().to_jd() > previous);
+    /// 
+    /// // behind mountains in the East, it rises later too, when it is above the horizon mask
+    /// let masked = Observer::new(33.3633675, -116.8361345, 1870.0, None)
+    ///     .with_horizon_mask(HorizonMask::new(&[(0.0, 10.0), (90.0, 20.0), (180.0, 10.0), (270.0, 0.0)]));
+    /// let rise = masked.target_rise_time(&target, &time, SearchMode::Previous, None);
+    /// let rise = rise.time().unwrap();
+    /// assert!(rise.to_jd() > previous);
+    /// let altaz = target.altaz(&masked, rise);
+    /// let mask = masked.horizon_mask.as_ref().unwrap();
+    /// assert!((altaz.alt - mask.min_altitude(altaz.az)).abs() < 0.01);
+    /// 
+    /// // Polaris never sets, and the Large Magellanic Cloud never rises
+    /// let polaris = Target::new(37.95, 89.26, None);
+    /// assert!(matches!(observer.target_rise_time(&polaris, &time, SearchMode::Next, None), HorizonCrossing::AlwaysUp));
+    /// let lmc = Target::new(80.89, -69.76, None);
+    /// assert!(matches!(observer.target_rise_time(&lmc, &time, SearchMode::Next, None), HorizonCrossing::AlwaysDown));
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// The crossings are bracketed between the transits and antitransits of the object, where its altitude
+    /// is monotonic, and refined by bisection: grazing passes are not missed, and the times are accurate to a second
+    /// (for the position of the object given by `SkyObject::radec`).
+    /// 
+    /// If the observer has a horizon mask or mount limits, the object rises when it is both above the horizon and
+    /// accessible (see `is_accessible`). They are not monotonic: the crossings are then also bracketed every 5 minutes,
+    /// and an obstruction passed in less than 5 minutes can be missed.
+    pub fn target_rise_time(&self, target: &dyn SkyObject, time: &Time, mode: SearchMode, horizon: Option<f64>) -> HorizonCrossing {
+        horizon_crossing(target, self, time, mode, horizon, true, true)
+    }
+
+    /// Calculate the time when an object sets below the horizon
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The object: a `Target`, the Sun (`sun::Sun`) or the Moon (`moon::Moon`)
+    /// * `time` - Time object representing the reference time of the search
+    /// * `mode` - Whether to find the next set, the previous one, or the one closest to the reference time
+    /// * `horizon` - Optional geometric altitude of the horizon in degrees
+    ///   (by default, the apparent horizon of the object, see `SkyObject::horizon_altitude`)
+    /// 
+    /// # Returns
+    /// 
+    /// * `HorizonCrossing` - The time of the set, or `AlwaysUp` / `AlwaysDown` if the object does not cross
+    ///   the horizon within its search window (`SkyObject::search_window`: a day and a half, or a synodic month for the Moon)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::rise_set::SearchMode;
+    /// use flare::sun::Sun;
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let time = Time::new(2024, 9, 10, 3, 0, 0);
+    /// 
+    /// // the sunset closest to the reference time was an hour before it
+    /// let sunset = observer.target_set_time(&Sun, &time, SearchMode::Nearest, None);
+    /// let sunset = sunset.time().unwrap();
+    /// assert_eq!(sunset.to_string(None), "2024-09-10 02:00:58 UTC");
+    /// // when the upper limb touches the apparent horizon, the center is 50' below the geometric horizon
+    /// assert!((observer.sun_altaz(sunset).alt + 50.0 / 60.0).abs() < 0.01);
+    /// ```
+    pub fn target_set_time(&self, target: &dyn SkyObject, time: &Time, mode: SearchMode, horizon: Option<f64>) -> HorizonCrossing {
+        horizon_crossing(target, self, time, mode, horizon, false, true)
+    }
+
+    /// Calculate the time when an object crosses the meridian, at its highest altitude (upper culmination)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The object: a `Target`, the Sun (`sun::Sun`) or the Moon (`moon::Moon`)
+    /// * `time` - Time object representing the reference time of the search
+    /// * `mode` - Whether to find the next transit, the previous one, or the one closest to the reference time
+    /// 
+    /// # Returns
+    /// 
+    /// * `Time` - The time of the transit, when the hour angle of the object is 0
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::rise_set::SearchMode;
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// // the target is on the meridian
+    /// let transit = observer.target_meridian_transit(&target, &time, SearchMode::Next);
+    /// assert!(transit.to_jd() > time.to_jd());
+    /// assert!(target.hour_angle(&observer, &transit).abs() < 0.01);
+    /// 
+    /// // and at its rise, its center is on the horizon of the search, 34' below the geometric one
+    /// let time = Time::new(2024, 9, 10, 0, 0, 0);
+    /// let rise = observer.target_rise_time(&target, &time, SearchMode::Next, None);
+    /// assert!((target.altitude(&observer, rise.time().unwrap()) + 34.0 / 60.0).abs() < 0.01);
+    /// ```
+    pub fn target_meridian_transit(&self, target: &dyn SkyObject, time: &Time, mode: SearchMode) -> Time {
+        hour_angle_event(target, self, time, mode, 0.0)
+    }
+
+    /// Calculate the time when an object crosses the meridian, at its lowest altitude (lower culmination)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The object: a `Target`, the Sun (`sun::Sun`) or the Moon (`moon::Moon`)
+    /// * `time` - Time object representing the reference time of the search
+    /// * `mode` - Whether to find the next antitransit, the previous one, or the one closest to the reference time
+    /// 
+    /// # Returns
+    /// 
+    /// * `Time` - The time of the antitransit, when the hour angle of the object is 180 degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::moon::Moon;
+    /// use flare::rise_set::SearchMode;
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let time = Time::new(2024, 9, 10, 3, 0, 0);
+    /// 
+    /// // the Moon is at its lowest between two transits, a bit more than a day apart
+    /// let antitransit = observer.target_antitransit(&Moon, &time, SearchMode::Nearest);
+    /// let before = observer.target_meridian_transit(&Moon, &antitransit, SearchMode::Previous);
+    /// let after = observer.target_meridian_transit(&Moon, &antitransit, SearchMode::Next);
+    /// assert!(before.to_jd() < antitransit.to_jd() && antitransit.to_jd() < after.to_jd());
+    /// assert!(((after.to_jd() - before.to_jd()) * 24.0 - 24.8).abs() < 0.5);
+    /// ```
+    pub fn target_antitransit(&self, target: &dyn SkyObject, time: &Time, mode: SearchMode) -> Time {
+        hour_angle_event(target, self, time, mode, -180.0)
+    }
+
     /// Calculate the airmasses of a list of targets at a list of times
     /// 
     /// # Arguments
@@ -117,7 +738,7 @@ impl <'a> Observer<'a> {
     /// # Returns
     /// 
     /// * `Vec<Vec<f64>>` - A 2D vector of airmasses, with the first dimension being the targets
-    /// and the second dimension being the times
+    ///   and the second dimension being the times
     /// 
     /// # Examples
     /// 
@@ -139,39 +760,70 @@ impl <'a> Observer<'a> {
     /// 
     /// let airmasses = observer.targets_airmasses(&targets, &times);
     /// 
-    /// let count = airmasses.iter().flatten().filter(|&x| *x > 0.0).count();
-    /// assert_eq!(count, 11713);
+    /// let count = airmasses.iter().flatten().filter(|&x| x.is_finite()).count();
+    /// assert_eq!(count, 12088);
     /// 
-    /// let count = airmasses.iter().flatten().filter(|&x| *x > 2.0).count();
-    /// assert_eq!(count, 4171);
+    /// let count = airmasses.iter().flatten().filter(|&x| *x > 2.0 && x.is_finite()).count();
+    /// assert_eq!(count, 4314);
+    /// 
+    /// // the same as the airmass of each target
+    /// assert_eq!(airmasses[1][4242], targets[1].airmass(&observer, &times[4242]));
     /// ```
     /// 
     /// # Notes
     /// 
-    /// This airmass calculation is quite simple and does not take into account refraction or other atmospheric effects.
-    /// For a more accurate calculation, consider using another dedicated library.
+    /// This uses the formula of Pickering (2002), like `Target::airmass`: see `airmass::AirmassModel` for the other models.
+    /// Below the horizon, the airmass is `f64::INFINITY`.
+    #[allow(clippy::ptr_arg)]
     pub fn targets_airmasses(&self, targets: &Vec<Target>, times: &Vec<Time>) -> Vec<Vec<f64>> {
-        let lat = self.lat;
-        let lsts = times.iter().map(|time| self.local_sidereal_time(time)).collect::<Vec<f64>>();
-
-        let ra_array = targets.iter().map(|target| target.ra).collect::<Vec<f64>>();
-        let dec_array = targets.iter().map(|target| target.dec).collect::<Vec<f64>>();
-
-        let mut airmasses = vec![vec![0.0; times.len()]; targets.len()];
-
-        for (i, _target) in targets.iter().enumerate() {
-            for (j, _time) in times.iter().enumerate() {
-                let ha = ((lsts[j] - ra_array[i]) % 360.0) * DEGRA;
-                let lat = lat * DEGRA;
-                let dec = dec_array[i] * DEGRA;
-            
-                let alt = (dec.sin() * lat.sin() + dec.cos() * lat.cos() * ha.cos()).asin() / DEGRA;
-                let alt = alt - 0.0347 * (90.0 - alt).tan().powi(2);
-                let sinarg = alt + 244.0 / (165.0 + 47.0 * alt.powf(1.1));
-                airmasses[i][j] = 1.0 / (sinarg * DEGRA).sin();
-            }
-        }
-        airmasses
+        self.targets_airmasses_with_model(targets, times, &AirmassModel::default())
+    }
+
+    /// Calculate the airmasses of a list of targets at a list of times, with a given airmass model
+    /// 
+    /// # Arguments
+    /// 
+    /// * `targets` - A vector of Target objects
+    /// * `times` - A vector of Time objects
+    /// * `model` - The formula used to compute the airmass from the altitude
+    /// 
+    /// # Returns
+    /// 
+    /// * `Vec<Vec<f64>>` - A 2D vector of airmasses, with the first dimension being the targets
+    ///   and the second dimension being the times (`f64::INFINITY` below the horizon)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::airmass::AirmassModel;
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let targets = vec![Target::new(6.374817, 20.242942, None)];
+    /// let times = vec![Time::new(2024, 8, 24, 6, 34, 0), Time::new(2024, 8, 24, 18, 34, 0)];
+    /// 
+    /// let airmasses = observer.targets_airmasses_with_model(&targets, &times, &AirmassModel::KastenYoung);
+    /// assert_eq!(airmasses[0][0], targets[0].airmass_with_model(&observer, &times[0], &AirmassModel::KastenYoung));
+    /// assert_eq!(airmasses[0][1], f64::INFINITY);
+    /// ```
+    pub fn targets_airmasses_with_model(&self, targets: &[Target], times: &[Time], model: &AirmassModel) -> Vec<Vec<f64>> {
+        // the precession to the date and the sidereal time only depend on the time
+        let frames = times
+            .iter()
+            .map(|time| (self.local_sidereal_time(time), true_equator_rotation(time)))
+            .collect::<Vec<_>>();
+        targets
+            .iter()
+            .map(|target| {
+                frames.iter()
+                    .map(|(lst, rotation)| {
+                        let (ra, dec) = rotation.apply_radec(target.ra, target.dec);
+                        let (alt, _) = hadec2altaz(normalize_hour_angle(lst - ra), dec, self.lat);
+                        model.airmass(alt)
+                    })
+                    .collect()
+            })
+            .collect()
     }
 
     /// Convert the Observer to a string
@@ -197,6 +849,7 @@ impl <'a> Observer<'a> {
     /// assert_eq!(observer.to_string(), "Lat: 33.3633675, Lon: -116.8361345, Elevation: 1870 (no name)");
     /// println!("{}", observer.to_string());
     /// ```
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         if let Some(name) = &self.name {
             return format!("Name: {}, Lat: {}, Lon: {}, Elevation: {}", name, self.lat, self.lon, self.elevation)
@@ -213,20 +866,26 @@ impl <'a> Observer<'a> {
     /// 
     /// # Returns
     /// 
-    /// * (`Time`, `Time`) - A tuple of Time objects representing the next sunrise & sunset
+    /// * `SunRiseSet` - The next sunrise and the sunset that follows it (`SunRiseSet::Normal(sunrise, sunset)`),
+    ///   or `SunRiseSet::AlwaysUp` / `SunRiseSet::AlwaysDown` during the polar day / night
     /// 
     /// # Examples
     /// 
     /// ```
+    /// use flare::rise_set::SunRiseSet;
     /// use flare::{Observer, Time};
     /// 
     /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
     /// let time = Time::new(2024, 9, 10, 3, 0, 0);
-    /// let (sunrise, sunset) = observer.sun_set_time(Some(&time), None);
-    /// println!("Next sunrise: {}", sunrise.to_string(None));
-    /// println!("Next sunset: {}", sunset.to_string(None));
-    /// assert_eq!(sunrise.to_string(None), "2024-09-10 13:22:01 UTC");
-    /// assert_eq!(sunset.to_string(None), "2024-09-11 02:09:11 UTC");
+    /// match observer.sun_set_time(Some(&time), None) {
+    ///     SunRiseSet::Normal(sunrise, sunset) => {
+    ///         println!("Next sunrise: {}", sunrise.to_string(None));
+    ///         println!("Next sunset: {}", sunset.to_string(None));
+    ///         assert_eq!(sunrise.to_string(None), "2024-09-10 13:20:44 UTC");
+    ///         assert_eq!(sunset.to_string(None), "2024-09-11 02:06:49 UTC");
+    ///     }
+    ///     _ => panic!("the Sun rises and sets every day in California"),
+    /// }
     /// ```
     /// 
     /// ```
@@ -234,66 +893,59 @@ impl <'a> Observer<'a> {
     /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
     /// let time = Time::new(2024, 9, 10, 3, 0, 0);
     /// let sun_alt_astronomical = -18.0;
-    /// let (sunrise, sunset) = observer.sun_set_time(Some(&time), Some(sun_alt_astronomical));
+    /// let events = observer.sun_set_time(Some(&time), Some(sun_alt_astronomical));
+    /// let (sunrise, sunset) = (events.rise().unwrap(), events.set().unwrap());
     /// println!("Next sunrise: {}", sunrise.to_string(None));
     /// println!("Next sunset: {}", sunset.to_string(None));
-    /// assert_eq!(sunrise.to_string(None), "2024-09-10 11:57:23 UTC");
-    /// assert_eq!(sunset.to_string(None), "2024-09-11 03:33:49 UTC");
+    /// assert_eq!(sunrise.to_string(None), "2024-09-10 11:56:05 UTC");
+    /// assert_eq!(sunset.to_string(None), "2024-09-11 03:31:14 UTC");
+    /// ```
+    /// 
+    /// Close to the poles, the Sun can stay above or below the horizon for days:
+    /// 
+    /// ```
+    /// use flare::rise_set::SunRiseSet;
+    /// use flare::{Observer, Time};
+    /// 
+    /// // Svalbard, in summer and in winter
+    /// let observer = Observer::new(78.2232, 15.6267, 0.0, Some("Longyearbyen"));
+    /// let events = observer.sun_set_time(Some(&Time::new(2024, 6, 21, 0, 0, 0)), None);
+    /// assert!(matches!(events, SunRiseSet::AlwaysUp));
+    /// let events = observer.sun_set_time(Some(&Time::new(2024, 12, 21, 0, 0, 0)), None);
+    /// assert!(matches!(events, SunRiseSet::AlwaysDown));
+    /// 
+    /// // the South Pole
+    /// let observer = Observer::new(-90.0, 0.0, 2835.0, Some("South Pole"));
+    /// let events = observer.sun_set_time(Some(&Time::new(2024, 12, 21, 0, 0, 0)), None);
+    /// assert!(matches!(events, SunRiseSet::AlwaysUp));
     /// ```
     /// 
     /// # Notes
     /// 
-    /// This calculation is based on the algorithm described at <https://en.wikipedia.org/wiki/Sunrise_equation>
-    pub fn sun_set_time(&self, after: Option<&Time>, solar_alt: Option<f64>) -> (Time, Time) {
-        // 1. calculate the current julian day
+    /// The sunrise is the first one after `after`, and the sunset is the first one after that sunrise,
+    /// so the sunrise is always before the sunset: the Sun is up between them.
+    /// If the Sun rises but does not set again within a day and a half (at the start of the polar day),
+    /// `SunRiseSet::AlwaysUp` is returned.
+    /// 
+    /// The altitude is that of the center of the Sun (-0.833 degrees by default, when the upper limb is on
+    /// the apparent horizon), lowered by the dip of the horizon seen from the elevation of the observer.
+    /// The times are found like with `target_rise_time` and `target_set_time` for the Sun (`sun::Sun`),
+    /// but regardless of the horizon mask and the mount limits of the observer.
+    pub fn sun_set_time(&self, after: Option<&Time>, solar_alt: Option<f64>) -> SunRiseSet {
         let after = match after {
-            Some(time) => time,
-            None => &Time::now(),
+            Some(time) => time.clone(),
+            None => Time::now(),
         };
-        let solar_alt = solar_alt.unwrap_or(-0.833);
-        let jd = after.to_jd();
-
-        let n = (jd - (2451545.0 + 0.0009) - 69.184 / 86400.0).ceil();
-
-        // 2. get the mean solar time
-        let jstar = n + 0.0009 - self.lon / 360.0;
-
-        // 3. calculate the solar mean anomaly
-        let m = (357.5291 + 0.98560028 * jstar) % 360.0;
-        let m_rad = m.to_radians();
-
-        // 4. calculate the equation of center
-        let c = 1.9148 * m_rad.sin() + 0.0200 * (2.0 * m_rad).sin() + 0.0003 * (3.0 * m_rad).sin();
-
-        // 5. calculate the ecliptic longitude
-        let lambda = (m + c + 180.0 + 102.9372) % 360.0;
-        let lambda_rad = lambda.to_radians();
-
-        // 6. calculate the solar transit
-        let jtransit = 2451545.0 + jstar + 0.0053 * m_rad.sin() - 0.0069 * (2.0 * lambda_rad).sin();
-
-        // 7. calculate the declination of the sun
-        let delta_sin = lambda_rad.sin() * 23.4397_f64.to_radians().sin();
-        let delta_cos = delta_sin.asin().cos();
-
-        // 8. calculate the hour angle
-        let w0_cos = (
-            (solar_alt - 2.076 * self.elevation.sqrt() / 60.0).to_radians().sin()
-            - self.lat.to_radians().sin() * delta_sin
-        ) / (self.lat.to_radians().cos() * delta_cos);
-        
-        let w0_rad = w0_cos.acos();
-        let w0 = w0_rad.to_degrees();
-
-        // 9. calculate the sunrise and sunset times
-        let jrise = jtransit - w0 / 360.0;
-        let jset = jtransit + w0 / 360.0;
-
-        // 10. convert the sunrise time to a Time object
-        let sunrise = Time::from_jd(jrise);
-        let sunset = Time::from_jd(jset);
-
-        (sunrise, sunset)
+        let horizon = self.sun_horizon(solar_alt);
+        let sunrise = match horizon_crossing(&sun::Sun, self, &after, SearchMode::Next, Some(horizon), true, false) {
+            HorizonCrossing::At(sunrise) => sunrise,
+            HorizonCrossing::AlwaysUp => return SunRiseSet::AlwaysUp,
+            HorizonCrossing::AlwaysDown => return SunRiseSet::AlwaysDown,
+        };
+        match horizon_crossing(&sun::Sun, self, &sunrise, SearchMode::Next, Some(horizon), false, false) {
+            HorizonCrossing::At(sunset) => SunRiseSet::Normal(sunrise, sunset),
+            _ => SunRiseSet::AlwaysUp,
+        }
     }
 
     /// Calculate the time of the next astronomical sunrise & sunset (in UTC)
@@ -304,7 +956,7 @@ impl <'a> Observer<'a> {
     /// 
     /// # Returns
     /// 
-    /// * (`Time`, `Time`) - A tuple of Time objects representing the next sunrise & sunset
+    /// * `SunRiseSet` - The next sunrise and the sunset that follows it, or the polar day / night (see `sun_set_time`)
     /// 
     /// # Examples
     /// 
@@ -313,18 +965,19 @@ impl <'a> Observer<'a> {
     /// 
     /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
     /// let time = Time::new(2024, 9, 10, 3, 0, 0);
-    /// let (sunrise, sunset) = observer.twilight_astronomical(Some(&time));
+    /// let events = observer.twilight_astronomical(Some(&time));
+    /// let (sunrise, sunset) = (events.rise().unwrap(), events.set().unwrap());
     /// println!("Next sunrise: {}", sunrise.to_string(None));
     /// println!("Next sunset: {}", sunset.to_string(None));
-    /// assert_eq!(sunrise.to_string(None), "2024-09-10 11:57:23 UTC");
-    /// assert_eq!(sunset.to_string(None), "2024-09-11 03:33:49 UTC");
+    /// assert_eq!(sunrise.to_string(None), "2024-09-10 11:56:05 UTC");
+    /// assert_eq!(sunset.to_string(None), "2024-09-11 03:31:14 UTC");
     /// ```
     /// 
     /// # Notes
     /// 
     /// Sunrise & sunset astronomical times are defined as the time when the sun is 18 degrees below the horizon.
     /// This is the time when the sky is dark enough for most astronomical observations.
-    pub fn twilight_astronomical(&self, after: Option<&Time>) -> (Time, Time) {
+    pub fn twilight_astronomical(&self, after: Option<&Time>) -> SunRiseSet {
         self.sun_set_time(after, Some(-18.0))
     }
 
@@ -336,7 +989,7 @@ impl <'a> Observer<'a> {
     /// 
     /// # Returns
     /// 
-    /// * (`Time`, `Time`) - A tuple of Time objects representing the next sunrise & sunset
+    /// * `SunRiseSet` - The next sunrise and the sunset that follows it, or the polar day / night (see `sun_set_time`)
     /// 
     /// # Examples
     /// 
@@ -345,18 +998,19 @@ impl <'a> Observer<'a> {
     /// 
     /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
     /// let time = Time::new(2024, 9, 10, 3, 0, 0);
-    /// let (sunrise, sunset) = observer.twilight_nautical(Some(&time));
+    /// let events = observer.twilight_nautical(Some(&time));
+    /// let (sunrise, sunset) = (events.rise().unwrap(), events.set().unwrap());
     /// println!("Next sunrise: {}", sunrise.to_string(None));
     /// println!("Next sunset: {}", sunset.to_string(None));
-    /// assert_eq!(sunrise.to_string(None), "2024-09-10 12:27:29 UTC");
-    /// assert_eq!(sunset.to_string(None), "2024-09-11 03:03:42 UTC");
+    /// assert_eq!(sunrise.to_string(None), "2024-09-10 12:26:12 UTC");
+    /// assert_eq!(sunset.to_string(None), "2024-09-11 03:01:14 UTC");
     /// ```
     /// 
     /// # Notes
     /// 
     /// Sunrise & sunset nautical times are defined as the time when the sun is 12 degrees below the horizon.
     /// This is the time when the horizon is still visible at sea.
-    pub fn twilight_nautical(&self, after: Option<&Time>) -> (Time, Time) {
+    pub fn twilight_nautical(&self, after: Option<&Time>) -> SunRiseSet {
         self.sun_set_time(after, Some(-12.0))
     }
 
@@ -368,7 +1022,7 @@ impl <'a> Observer<'a> {
     /// 
     /// # Returns
     /// 
-    /// * (`Time`, `Time`) - A tuple of Time objects representing the next sunrise & sunset
+    /// * `SunRiseSet` - The next sunrise and the sunset that follows it, or the polar day / night (see `sun_set_time`)
     /// 
     /// # Examples
     /// 
@@ -377,20 +1031,130 @@ impl <'a> Observer<'a> {
     /// 
     /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
     /// let time = Time::new(2024, 9, 10, 3, 0, 0);
-    /// let (sunrise, sunset) = observer.twilight_civil(Some(&time));
+    /// let events = observer.twilight_civil(Some(&time));
+    /// let (sunrise, sunset) = (events.rise().unwrap(), events.set().unwrap());
     /// println!("Next sunrise: {}", sunrise.to_string(None));
     /// println!("Next sunset: {}", sunset.to_string(None));
-    /// assert_eq!(sunrise.to_string(None), "2024-09-10 12:56:58 UTC");
-    /// assert_eq!(sunset.to_string(None), "2024-09-11 02:34:14 UTC");
+    /// assert_eq!(sunrise.to_string(None), "2024-09-10 12:55:41 UTC");
+    /// assert_eq!(sunset.to_string(None), "2024-09-11 02:31:49 UTC");
     /// ```
     /// 
     /// # Notes
     /// 
     /// Sunrise & sunset civil times are defined as the time when the sun is 6 degrees below the horizon.
     /// This is the time when the sky is light enough for most outdoor activities.
-    pub fn twilight_civil(&self, after: Option<&Time>) -> (Time, Time) {
+    pub fn twilight_civil(&self, after: Option<&Time>) -> SunRiseSet {
         self.sun_set_time(after, Some(-6.0))
     }
+
+    /// Summarize the next night: sunset, twilights, sunrise, moonrise & moonset, Moon illumination and LST range
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - Time object representing the time after which the night starts (e.g. the local noon)
+    /// 
+    /// # Returns
+    /// 
+    /// * `NightInfo` - The summary of the night, which can be printed for log output
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, Some("P48"));
+    /// let night = observer.night(&Time::new(2024, 9, 10, 20, 0, 0));
+    /// println!("{}", night);
+    /// 
+    /// // the night starts with the sunset of the same day as sun_set_time, and the twilights follow it
+    /// let sunset = observer.sun_set_time(Some(&Time::new(2024, 9, 10, 3, 0, 0)), None).set().unwrap().clone();
+    /// assert_eq!(night.sunset.as_ref().unwrap().to_string(None), sunset.to_string(None));
+    /// assert_eq!(night.start.to_jd(), night.sunset.as_ref().unwrap().to_jd());
+    /// assert_eq!(night.astronomical_dusk.as_ref().unwrap().to_string(None), "2024-09-11 03:31:14 UTC");
+    /// assert_eq!(night.astronomical_dawn.as_ref().unwrap().to_string(None), "2024-09-11 11:56:55 UTC");
+    /// assert!((night.length - 11.24).abs() < 0.01);
+    /// assert!((night.dark_length - 8.43).abs() < 0.01);
+    /// assert!((night.moon_illumination - 0.508).abs() < 0.01);
+    /// ```
+    /// 
+    /// During the polar day, there is no sunset and the night has no length:
+    /// 
+    /// ```
+    /// use flare::{Observer, Time};
+    /// 
+    /// let observer = Observer::new(78.2232, 15.6267, 0.0, Some("Longyearbyen"));
+    /// let night = observer.night(&Time::new(2024, 6, 21, 12, 0, 0));
+    /// assert!(night.sunset.is_none() && night.astronomical_dusk.is_none());
+    /// assert_eq!(night.length, 0.0);
+    /// 
+    /// // and during the polar night, it lasts the whole day
+    /// let night = observer.night(&Time::new(2024, 12, 21, 12, 0, 0));
+    /// assert!(night.sunset.is_none());
+    /// assert_eq!(night.start.to_string(None), "2024-12-21 12:00:00 UTC");
+    /// assert_eq!(night.length, 24.0);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// The night starts at the first sunset after `time`, and the twilights are searched from that sunset
+    /// (or from `time` if the Sun does not set), with the same horizons as `sun_set_time` and the `twilight_*` helpers:
+    /// each dawn is the first sunrise above the same altitude after the dusk. The moonrise & moonset are the first
+    /// ones after the sunset (they can be after the end of the night), and the illumination of the Moon is taken
+    /// at the middle of the night.
+    pub fn night(&self, time: &Time) -> NightInfo {
+        let (sunset, sunrise, length) = self.dusk_dawn(time, None);
+        let start = sunset.clone().unwrap_or_else(|| time.clone());
+        let (civil_dusk, civil_dawn, _) = self.dusk_dawn(&start, Some(-6.0));
+        let (nautical_dusk, nautical_dawn, _) = self.dusk_dawn(&start, Some(-12.0));
+        let (astronomical_dusk, astronomical_dawn, dark_length) = self.dusk_dawn(&start, Some(-18.0));
+        let (moonrise, moonset) = self.moon_rise_set(Some(&start));
+        let middle = match (&sunset, &sunrise) {
+            (Some(sunset), Some(sunrise)) => Time::from_jd((sunset.to_jd() + sunrise.to_jd()) / 2.0),
+            _ => start.clone(),
+        };
+        NightInfo {
+            start,
+            lst_dusk: astronomical_dusk.as_ref().map(|t| self.local_sidereal_time(t)),
+            lst_dawn: astronomical_dawn.as_ref().map(|t| self.local_sidereal_time(t)),
+            sunset,
+            sunrise,
+            civil_dusk,
+            civil_dawn,
+            nautical_dusk,
+            nautical_dawn,
+            astronomical_dusk,
+            astronomical_dawn,
+            length,
+            dark_length,
+            moonrise: moonrise.time().cloned(),
+            moonset: moonset.time().cloned(),
+            moon_illumination: moon::illumination(&middle),
+        }
+    }
+
+    /// Geometric altitude of the center of the Sun at sunrise & sunset (by default, its upper limb
+    /// on the apparent horizon), lowered by the dip of the horizon seen from the elevation of the observer
+    fn sun_horizon(&self, solar_alt: Option<f64>) -> f64 {
+        solar_alt.unwrap_or(-0.833) - 2.076 * self.elevation.sqrt() / 60.0
+    }
+
+    /// Find the first time the Sun sets below an altitude after a time, the first time it rises above it
+    /// after that, and the time between them in hours (0 if the Sun stays up, at most 24 if it stays down)
+    fn dusk_dawn(&self, time: &Time, solar_alt: Option<f64>) -> (Option<Time>, Option<Time>, f64) {
+        let horizon = self.sun_horizon(solar_alt);
+        let dusk = match horizon_crossing(&sun::Sun, self, time, SearchMode::Next, Some(horizon), false, false) {
+            HorizonCrossing::At(dusk) => dusk,
+            HorizonCrossing::AlwaysUp => return (None, None, 0.0),
+            HorizonCrossing::AlwaysDown => return (None, None, 24.0),
+        };
+        match horizon_crossing(&sun::Sun, self, &dusk, SearchMode::Next, Some(horizon), true, false) {
+            HorizonCrossing::At(dawn) => {
+                let length = ((dawn.to_jd() - dusk.to_jd()) * 24.0).min(24.0);
+                (Some(dusk), Some(dawn), length)
+            }
+            _ => (Some(dusk), None, 24.0),
+        }
+    }
 }
 
 impl <'a> std::fmt::Display for Observer<'a> {
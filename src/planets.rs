@@ -0,0 +1,530 @@
+use crate::ecliptic::{centuries, ecliptic_rotation, true_equator_rotation, EclipticFrame};
+use crate::observer::Observer;
+use crate::spatial::DEGRA;
+use crate::sun::AU;
+use crate::target::{SkyObject, Target};
+use crate::time::Time;
+use crate::vector::{Cartesian3, Rotation3, UnitVector};
+use crate::vsop87::{self, Theory};
+
+/// Light-time for one astronomical unit, in days
+const LIGHT_TIME: f64 = 0.0057755183;
+
+/// Pole of the ring plane of Saturn (inclination and ascending node on the J2000 ecliptic), in degrees
+const SATURN_RINGS: (f64, f64) = (28.0752, 169.5085);
+
+/// Planet enum
+///
+/// This enum represents the major planets (other than the Earth), e.g. to veto alerts coincident with them,
+/// or to compute their rise & set times with `rise_set` (as a `SkyObject`, at their apparent topocentric position).
+///
+/// # Variants
+///
+/// * `Mercury`, `Venus`, `Mars`, `Jupiter`, `Saturn`, `Uranus`, `Neptune`
+///
+/// # Methods
+///
+/// * `name` - Get the name of the planet
+/// * `heliocentric_position` - Calculate the heliocentric ecliptic position of the planet
+/// * `position` - Calculate the apparent geocentric position of the planet
+/// * `position_j2000` - Calculate the astrometric geocentric position of the planet, in J2000 coordinates
+/// * `topocentric_position` - Calculate the apparent topocentric position of the planet
+/// * `phase_angle` - Calculate the phase angle of the planet (Sun-planet-Earth)
+/// * `magnitude` - Calculate the apparent visual magnitude of the planet
+/// * `angular_diameter` - Calculate the apparent (equatorial) angular diameter of the planet
+/// * `to_target` - Get a Target at the J2000 position of the planet, e.g. to compute separations
+///
+/// # Examples
+///
+/// ```
+/// use flare::planets::Planet;
+/// use flare::{Target, Time};
+///
+/// // Jupiter at opposition, on 2023 November 3
+/// let time = Time::new(2023, 11, 3, 5, 0, 0);
+/// let jupiter = Planet::Jupiter.position_j2000(&time);
+/// assert!((jupiter.ra - 38.2175).abs() < 0.001 && (jupiter.dec - 13.5253).abs() < 0.001);
+///
+/// // is an alert on Jupiter (49" across)?
+/// let alert = Target::new(38.22, 13.53, None);
+/// let separation = Planet::Jupiter.to_target(&time).separation(&alert);
+/// assert!(separation < 24.0 / 3600.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+/// HeliocentricPosition struct
+///
+/// This struct represents the heliocentric position of a planet, in the J2000 ecliptic and equinox.
+///
+/// # Attributes
+///
+/// * `lon` - Ecliptic longitude in degrees, in [0, 360)
+/// * `lat` - Ecliptic latitude in degrees
+/// * `distance` - Distance to the Sun in astronomical units
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeliocentricPosition {
+    pub lon: f64,
+    pub lat: f64,
+    pub distance: f64,
+}
+
+/// PlanetPosition struct
+///
+/// This struct represents the position of a planet, seen from the Earth.
+///
+/// # Attributes
+///
+/// * `ra` - Right Ascension in degrees, in [0, 360)
+/// * `dec` - Declination in degrees
+/// * `distance` - Distance to the planet in astronomical units
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetPosition {
+    pub ra: f64,
+    pub dec: f64,
+    pub distance: f64,
+}
+
+/// Geometry of a planet seen from the Earth, corrected for the light-time
+struct Geometry {
+    // heliocentric position of the planet when the light left it, J2000 ecliptic, in AU
+    planet: Cartesian3,
+    // heliocentric position of the Earth, J2000 ecliptic, in AU
+    earth: Cartesian3,
+}
+
+impl Geometry {
+    /// Vector from the Earth to the planet, J2000 ecliptic, in AU
+    fn geocentric(&self) -> Cartesian3 {
+        self.planet - self.earth
+    }
+
+    /// Phase angle (Sun-planet-Earth) in degrees
+    fn phase_angle(&self) -> f64 {
+        let to_sun = -self.planet;
+        let to_earth = -self.geocentric();
+        (to_sun.dot(&to_earth) / (to_sun.norm() * to_earth.norm())).clamp(-1.0, 1.0).acos() / DEGRA
+    }
+}
+
+impl Planet {
+    /// The seven major planets, ordered by distance to the Sun
+    pub const ALL: [Planet; 7] = [
+        Planet::Mercury,
+        Planet::Venus,
+        Planet::Mars,
+        Planet::Jupiter,
+        Planet::Saturn,
+        Planet::Uranus,
+        Planet::Neptune,
+    ];
+
+    /// Get the name of the planet
+    ///
+    /// # Returns
+    ///
+    /// * `&'static str` - The name of the planet, e.g. "Jupiter"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Planet::Mercury => "Mercury",
+            Planet::Venus => "Venus",
+            Planet::Mars => "Mars",
+            Planet::Jupiter => "Jupiter",
+            Planet::Saturn => "Saturn",
+            Planet::Uranus => "Uranus",
+            Planet::Neptune => "Neptune",
+        }
+    }
+
+    /// Truncated VSOP87 theory of the planet
+    fn theory(&self) -> &'static Theory {
+        match self {
+            Planet::Mercury => &vsop87::MERCURY,
+            Planet::Venus => &vsop87::VENUS,
+            Planet::Mars => &vsop87::MARS,
+            Planet::Jupiter => &vsop87::JUPITER,
+            Planet::Saturn => &vsop87::SATURN,
+            Planet::Uranus => &vsop87::URANUS,
+            Planet::Neptune => &vsop87::NEPTUNE,
+        }
+    }
+
+    /// Semi-diameter (equatorial) at a distance of 1 AU, in arcseconds (table 55.A of Meeus)
+    fn semidiameter(&self) -> f64 {
+        match self {
+            Planet::Mercury => 3.36,
+            Planet::Venus => 8.41,
+            Planet::Mars => 4.68,
+            Planet::Jupiter => 98.44,
+            Planet::Saturn => 82.73,
+            Planet::Uranus => 35.02,
+            Planet::Neptune => 33.50,
+        }
+    }
+
+    /// Geometry of the planet at a given time, iterating on the light-time
+    fn geometry(&self, time: &Time) -> Geometry {
+        let (tau, rotation) = (millennia(time), to_j2000_ecliptic(time));
+        let earth = heliocentric_vector(&vsop87::EARTH, tau, &rotation);
+        let mut planet = heliocentric_vector(self.theory(), tau, &rotation);
+        for _ in 0..3 {
+            let delay = LIGHT_TIME * (planet - earth).norm();
+            planet = heliocentric_vector(self.theory(), tau - delay / 365250.0, &rotation);
+        }
+        Geometry { planet, earth }
+    }
+
+    /// Calculate the heliocentric position of the planet at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `HeliocentricPosition` - The position of the planet, in the J2000 ecliptic and equinox
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::Time;
+    ///
+    /// // example 32.a of Meeus: L = 26.11428, B = -2.62070 (ecliptic of date), R = 0.724603
+    /// let venus = Planet::Venus.heliocentric_position(&Time::new(1992, 12, 20, 0, 0, 0));
+    /// // 7 years of precession to the J2000 ecliptic add 354" to the longitude
+    /// assert!((venus.lon - 26.11428 - 354.0 / 3600.0).abs() < 0.0005);
+    /// assert!((venus.lat + 2.62070).abs() < 0.0005);
+    /// assert!((venus.distance - 0.724603).abs() < 1e-6);
+    ///
+    /// // Mars at perihelion on 2022 June 21, at 1.381 AU from the Sun
+    /// let mars = Planet::Mars.heliocentric_position(&Time::new(2022, 6, 21, 0, 0, 0));
+    /// assert!((mars.distance - 1.3813).abs() < 1e-4);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// This uses the VSOP87 theory of Bretagnon & Francou (1988, A&A 202, 309), truncated as in appendix III of
+    /// "Astronomical Algorithms" 2nd edition by Jean Meeus (Willmann-Bell, Richmond) 1998, with the positions
+    /// rotated from the ecliptic and equinox of date to the J2000 ecliptic. The truncation limits the accuracy
+    /// to a few arcseconds over a few millennia around J2000, including the mutual perturbations of Jupiter
+    /// and Saturn. The difference between TT and UTC (about a minute) is neglected.
+    pub fn heliocentric_position(&self, time: &Time) -> HeliocentricPosition {
+        let v = heliocentric_vector(self.theory(), millennia(time), &to_j2000_ecliptic(time));
+        let distance = v.norm();
+        HeliocentricPosition {
+            lon: v.y.atan2(v.x).rem_euclid(2.0 * std::f64::consts::PI) / DEGRA,
+            lat: (v.z / distance).asin() / DEGRA,
+            distance,
+        }
+    }
+
+    /// Calculate the apparent geocentric position of the planet at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `PlanetPosition` - The position of the planet, in the true equator and equinox of date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::Time;
+    ///
+    /// // example 33.a of Meeus: RA = 21h04m41.45s, Dec = -18d53'16.8", at 0.9109 AU
+    /// let venus = Planet::Venus.position(&Time::new(1992, 12, 20, 0, 0, 0));
+    /// assert!((venus.ra - 316.17273).abs() < 0.0005);
+    /// assert!((venus.dec + 18.88801).abs() < 0.0005);
+    /// assert!((venus.distance - 0.910947).abs() < 1e-6);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The position is corrected for the light-time, the annual aberration, the precession and the nutation.
+    /// See `heliocentric_position` for the accuracy of the theory.
+    pub fn position(&self, time: &Time) -> PlanetPosition {
+        let geometry = self.geometry(time);
+        let geocentric = to_equatorial(&geometry.geocentric());
+        let distance = geocentric.norm();
+        // Earth velocity in AU per day, from the positions half a day before and after
+        let (tau, rotation) = (millennia(time), to_j2000_ecliptic(time));
+        let velocity = to_equatorial(
+            &(heliocentric_vector(&vsop87::EARTH, tau + 0.5 / 365250.0, &rotation)
+                - heliocentric_vector(&vsop87::EARTH, tau - 0.5 / 365250.0, &rotation)),
+        );
+        let direction = geocentric * (1.0 / distance) + velocity * LIGHT_TIME;
+        let apparent = true_equator_rotation(time).apply_cartesian(&direction);
+        to_position(&(apparent * (distance / apparent.norm())))
+    }
+
+    /// Calculate the astrometric geocentric position of the planet at a given time, in J2000 coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `PlanetPosition` - The position of the planet, in the J2000 equator and equinox
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::Time;
+    ///
+    /// // Neptune at opposition, on 2024 September 21
+    /// let neptune = Planet::Neptune.position_j2000(&Time::new(2024, 9, 21, 0, 0, 0));
+    /// assert!((neptune.ra - 358.8444).abs() < 0.001 && (neptune.dec + 1.9464).abs() < 0.001);
+    /// assert!((neptune.distance - 28.8931).abs() < 1e-4);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The position is only corrected for the light-time, like catalog or alert coordinates:
+    /// use it to find alerts coincident with the planet.
+    pub fn position_j2000(&self, time: &Time) -> PlanetPosition {
+        to_position(&to_equatorial(&self.geometry(time).geocentric()))
+    }
+
+    /// Calculate the apparent topocentric position of the planet, seen by an observer at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `PlanetPosition` - The position of the planet seen by the observer, in the true equator and equinox of date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::spatial::great_circle_distance;
+    /// use flare::{Observer, Time};
+    ///
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let time = Time::new(2022, 12, 8, 6, 0, 0);
+    ///
+    /// // close to its opposition, the parallax of Mars is about 16"
+    /// let geocentric = Planet::Mars.position(&time);
+    /// let topocentric = Planet::Mars.topocentric_position(&observer, &time);
+    /// let parallax = great_circle_distance(geocentric.ra, geocentric.dec, topocentric.ra, topocentric.dec);
+    /// assert!(parallax > 5.0 / 3600.0 && parallax < 17.0 / 3600.0);
+    /// ```
+    pub fn topocentric_position(&self, observer: &Observer, time: &Time) -> PlanetPosition {
+        let planet = self.position(time);
+        let geocentric = UnitVector::from_radec(planet.ra, planet.dec).cartesian() * (planet.distance * AU);
+        to_position(&((geocentric - observer.geocentric_position(time)) * (1.0 / AU)))
+    }
+
+    /// Calculate the phase angle of the planet at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The angle between the Sun and the Earth seen from the planet, in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::Time;
+    ///
+    /// // Venus at its inferior conjunction on 2023 August 13, 7.7 degrees South of the Sun
+    /// assert!((Planet::Venus.phase_angle(&Time::new(2023, 8, 13, 11, 0, 0)) - 169.27).abs() < 0.01);
+    /// // the phase angle of Jupiter is always small
+    /// assert!(Planet::Jupiter.phase_angle(&Time::new(2024, 1, 1, 0, 0, 0)) < 12.0);
+    /// ```
+    pub fn phase_angle(&self, time: &Time) -> f64 {
+        self.geometry(time).phase_angle()
+    }
+
+    /// Calculate the apparent visual magnitude of the planet at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The apparent visual magnitude of the planet
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::Time;
+    ///
+    /// // Jupiter at opposition
+    /// let magnitude = Planet::Jupiter.magnitude(&Time::new(2023, 11, 3, 5, 0, 0));
+    /// assert!((magnitude + 2.9).abs() < 0.1);
+    ///
+    /// // Saturn at opposition, with its rings almost edge-on
+    /// let magnitude = Planet::Saturn.magnitude(&Time::new(2024, 9, 8, 0, 0, 0));
+    /// assert!((magnitude - 0.6).abs() < 0.15);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// This uses the formulas of chapter 41 of "Astronomical Algorithms" 2nd edition by Jean Meeus
+    /// (Willmann-Bell, Richmond) 1998, from the Astronomical Almanac 1984, accurate to about 0.1 mag.
+    /// For Saturn, it includes the contribution of the rings, which depends on their inclination.
+    pub fn magnitude(&self, time: &Time) -> f64 {
+        let geometry = self.geometry(time);
+        let r = geometry.planet.norm();
+        let delta = geometry.geocentric().norm();
+        let i = geometry.phase_angle();
+        let distance = 5.0 * (r * delta).log10();
+        match self {
+            Planet::Mercury => -0.42 + distance + 0.0380 * i - 0.000273 * i * i + 0.000002 * i * i * i,
+            Planet::Venus => -4.40 + distance + 0.0009 * i + 0.000239 * i * i - 0.00000065 * i * i * i,
+            Planet::Mars => -1.52 + distance + 0.016 * i,
+            Planet::Jupiter => -9.40 + distance + 0.005 * i,
+            Planet::Saturn => {
+                let (b, du) = saturn_rings(&geometry);
+                -8.88 + distance + 0.044 * du.abs() - 2.60 * (b * DEGRA).sin().abs() + 1.25 * (b * DEGRA).sin().powi(2)
+            }
+            Planet::Uranus => -7.19 + distance,
+            Planet::Neptune => -6.87 + distance,
+        }
+    }
+
+    /// Calculate the apparent angular diameter of the planet at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The equatorial angular diameter of the planet (without the rings of Saturn), in arcseconds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::Time;
+    ///
+    /// // Jupiter at opposition
+    /// let diameter = Planet::Jupiter.angular_diameter(&Time::new(2023, 11, 3, 5, 0, 0));
+    /// assert!((diameter - 49.43).abs() < 0.01);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// This uses the semi-diameters at 1 AU of table 55.A of Meeus (to the cloud tops for Venus).
+    pub fn angular_diameter(&self, time: &Time) -> f64 {
+        2.0 * self.semidiameter() / self.geometry(time).geocentric().norm()
+    }
+
+    /// Get a Target at the position of the planet at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `Target` - A Target named after the planet, at its astrometric J2000 position (`position_j2000`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::planets::Planet;
+    /// use flare::{Target, Time};
+    ///
+    /// let time = Time::new(2024, 9, 8, 0, 0, 0);
+    /// let saturn = Planet::Saturn.to_target(&time);
+    /// assert_eq!(saturn.name, Some("Saturn"));
+    ///
+    /// // Saturn is at opposition, 180 degrees from the Sun
+    /// assert!(saturn.solar_elongation(&time) > 175.0);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The planet moves: the Target is only valid close to the given time.
+    pub fn to_target(&self, time: &Time) -> Target<'static> {
+        let position = self.position_j2000(time);
+        Target::new(position.ra, position.dec, Some(self.name()))
+    }
+}
+
+impl std::fmt::Display for Planet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl SkyObject for Planet {
+    fn radec(&self, observer: &Observer, time: &Time) -> (f64, f64) {
+        let planet = self.topocentric_position(observer, time);
+        (planet.ra, planet.dec)
+    }
+}
+
+/// Time in Julian millennia from J2000, the time argument of VSOP87
+fn millennia(time: &Time) -> f64 {
+    centuries(time) / 10.0
+}
+
+/// Rotation from the mean ecliptic and equinox of a date (the frame of VSOP87D) to the J2000 ecliptic
+fn to_j2000_ecliptic(time: &Time) -> Rotation3 {
+    ecliptic_rotation(&EclipticFrame::MeanOfDate(time.clone()))
+        .inverse()
+        .then(&ecliptic_rotation(&EclipticFrame::J2000))
+}
+
+/// Heliocentric position (J2000 ecliptic, in AU) from a VSOP87 theory, at a time in Julian millennia from J2000
+///
+/// The rotation to the J2000 ecliptic is the one of the date of the observation: the precession during the
+/// light-time (or half a day) is below 0.1".
+fn heliocentric_vector(theory: &Theory, tau: f64, rotation: &Rotation3) -> Cartesian3 {
+    let (l, b, r) = theory.spherical(tau);
+    let v = Cartesian3::new(r * b.cos() * l.cos(), r * b.cos() * l.sin(), r * b.sin());
+    rotation.apply_cartesian(&v)
+}
+
+/// Convert a vector from the J2000 ecliptic to the J2000 equator
+fn to_equatorial(v: &Cartesian3) -> Cartesian3 {
+    ecliptic_rotation(&EclipticFrame::J2000).inverse().apply_cartesian(v)
+}
+
+/// Convert a geocentric or topocentric vector (in AU) to a PlanetPosition
+fn to_position(v: &Cartesian3) -> PlanetPosition {
+    let (ra, dec) = v.direction().expect("the planet is not at the observer").to_radec();
+    PlanetPosition { ra, dec, distance: v.norm() }
+}
+
+/// Saturnicentric latitude of the Earth on the ring plane, and difference between the saturnicentric
+/// longitudes of the Sun and the Earth on the ring plane, in degrees (chapter 45 of Meeus)
+fn saturn_rings(geometry: &Geometry) -> (f64, f64) {
+    let (i, node) = (SATURN_RINGS.0 * DEGRA, SATURN_RINGS.1 * DEGRA);
+    let pole = Cartesian3::new(i.sin() * node.sin(), -i.sin() * node.cos(), i.cos());
+    let to_sun = -geometry.planet;
+    let to_earth = -geometry.geocentric();
+    let b = (pole.dot(&to_earth) / to_earth.norm()).asin() / DEGRA;
+    // projections on the ring plane
+    let sun = to_sun - pole * pole.dot(&to_sun);
+    let earth = to_earth - pole * pole.dot(&to_earth);
+    let du = (sun.dot(&earth) / (sun.norm() * earth.norm())).clamp(-1.0, 1.0).acos() / DEGRA;
+    (b, du)
+}
@@ -0,0 +1,424 @@
+use crate::spatial::DEGRA;
+
+/// Conversion factor from radians to degrees, the "R0" of the FITS WCS projections
+const R0: f64 = 180.0 / std::f64::consts::PI;
+
+/// Projection enum
+///
+/// This enum represents the sky projections of the FITS WCS standard (Calabretta & Greisen 2002,
+/// "Representations of celestial coordinates in FITS", aka WCS paper II) implemented in flare.
+/// Projections map native spherical coordinates (phi, theta) to projection plane coordinates (x, y),
+/// all in degrees.
+///
+/// # Variants
+///
+/// * `Tan` - Gnomonic (zenithal)
+/// * `Sin` - Orthographic/synthesis (zenithal), without the optional xi/eta parameters
+/// * `Arc` - Zenithal equidistant
+/// * `Zea` - Zenithal equal-area
+/// * `Car` - Plate carrée (cylindrical)
+/// * `Ait` - Hammer-Aitoff (all-sky)
+/// * `Mol` - Mollweide (pseudo-cylindrical)
+///
+/// # Examples
+///
+/// ```
+/// use flare::projection::Projection;
+///
+/// let projection = Projection::from_code("ZEA").unwrap();
+/// let (x, y) = projection.project(30.0, 60.0).unwrap();
+/// let (phi, theta) = projection.deproject(x, y).unwrap();
+/// assert!((phi - 30.0).abs() < 1e-9);
+/// assert!((theta - 60.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Projection {
+    Tan,
+    Sin,
+    Arc,
+    Zea,
+    Car,
+    Ait,
+    Mol,
+}
+
+impl Projection {
+    /// Get a projection from its FITS 3-letter code
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - FITS projection code (e.g. "TAN"), case insensitive
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Projection>` - The projection, or None if the code is not supported
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    ///
+    /// assert_eq!(Projection::from_code("tan"), Some(Projection::Tan));
+    /// assert_eq!(Projection::from_code("XYZ"), None);
+    /// ```
+    pub fn from_code(code: &str) -> Option<Projection> {
+        match code.to_uppercase().as_str() {
+            "TAN" => Some(Projection::Tan),
+            "SIN" => Some(Projection::Sin),
+            "ARC" => Some(Projection::Arc),
+            "ZEA" => Some(Projection::Zea),
+            "CAR" => Some(Projection::Car),
+            "AIT" => Some(Projection::Ait),
+            "MOL" => Some(Projection::Mol),
+            _ => None,
+        }
+    }
+
+    /// Get the FITS 3-letter code of the projection
+    ///
+    /// # Returns
+    ///
+    /// * `&str` - FITS projection code
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    ///
+    /// assert_eq!(Projection::Mol.code(), "MOL");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            Projection::Tan => "TAN",
+            Projection::Sin => "SIN",
+            Projection::Arc => "ARC",
+            Projection::Zea => "ZEA",
+            Projection::Car => "CAR",
+            Projection::Ait => "AIT",
+            Projection::Mol => "MOL",
+        }
+    }
+
+    /// Get the native latitude of the fiducial point of the projection (theta_0 in WCS paper II)
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - 90 degrees for zenithal projections, 0 degrees for the others
+    pub fn theta0(&self) -> f64 {
+        match self {
+            Projection::Tan | Projection::Sin | Projection::Arc | Projection::Zea => 90.0,
+            Projection::Car | Projection::Ait | Projection::Mol => 0.0,
+        }
+    }
+
+    /// Project native spherical coordinates onto the projection plane
+    ///
+    /// # Arguments
+    ///
+    /// * `phi` - Native longitude in degrees
+    /// * `theta` - Native latitude in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(f64, f64)>` - Projection plane coordinates (x, y) in degrees, or None if the
+    ///   position can't be projected (e.g. behind the tangent plane for `Tan`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    ///
+    /// // 45 degrees away from the reference point, the gnomonic projection is 1 radian away from the center
+    /// let (x, y) = Projection::Tan.project(180.0, 45.0).unwrap();
+    /// assert!(x.abs() < 1e-9);
+    /// assert!((y - 1.0_f64.to_degrees()).abs() < 1e-9);
+    ///
+    /// assert!(Projection::Tan.project(0.0, -10.0).is_none());
+    /// ```
+    pub fn project(&self, phi: f64, theta: f64) -> Option<(f64, f64)> {
+        let phi_rad = phi * DEGRA;
+        let theta_rad = theta * DEGRA;
+        match self {
+            Projection::Tan | Projection::Sin | Projection::Arc | Projection::Zea => {
+                let r = match self {
+                    Projection::Tan => {
+                        if theta <= 0.0 {
+                            return None;
+                        }
+                        R0 * theta_rad.cos() / theta_rad.sin()
+                    }
+                    Projection::Sin => {
+                        if theta < 0.0 {
+                            return None;
+                        }
+                        R0 * theta_rad.cos()
+                    }
+                    Projection::Arc => 90.0 - theta,
+                    _ => 2.0 * R0 * ((90.0 - theta) * DEGRA / 2.0).sin(),
+                };
+                Some((r * phi_rad.sin(), -r * phi_rad.cos()))
+            }
+            Projection::Car => Some((normalize_longitude(phi), theta)),
+            Projection::Ait => {
+                let phi_rad = normalize_longitude(phi) * DEGRA;
+                let gamma = R0 * (2.0 / (1.0 + theta_rad.cos() * (phi_rad / 2.0).cos())).sqrt();
+                Some((2.0 * gamma * theta_rad.cos() * (phi_rad / 2.0).sin(), gamma * theta_rad.sin()))
+            }
+            Projection::Mol => {
+                let phi = normalize_longitude(phi);
+                let gamma = mollweide_gamma(theta_rad);
+                let sqrt2 = std::f64::consts::SQRT_2;
+                Some((2.0 * sqrt2 / std::f64::consts::PI * phi * gamma.cos(), sqrt2 * R0 * gamma.sin()))
+            }
+        }
+    }
+
+    /// Deproject projection plane coordinates to native spherical coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Projection plane `x` coordinate in degrees
+    /// * `y` - Projection plane `y` coordinate in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(f64, f64)>` - Native longitude and latitude (phi, theta) in degrees, or None
+    ///   if (x, y) is outside of the projection's boundary
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    ///
+    /// let (phi, theta) = Projection::Ait.deproject(0.0, 0.0).unwrap();
+    /// assert!(phi.abs() < 1e-9 && theta.abs() < 1e-9);
+    ///
+    /// // outside of the all-sky ellipse
+    /// assert!(Projection::Ait.deproject(300.0, 100.0).is_none());
+    /// ```
+    pub fn deproject(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        match self {
+            Projection::Tan | Projection::Sin | Projection::Arc | Projection::Zea => {
+                let r = (x * x + y * y).sqrt();
+                let phi = if r == 0.0 { 0.0 } else { x.atan2(-y) / DEGRA };
+                let theta = match self {
+                    Projection::Tan => (R0 / r).atan() / DEGRA,
+                    Projection::Sin => {
+                        if r > R0 {
+                            return None;
+                        }
+                        (r / R0).acos() / DEGRA
+                    }
+                    Projection::Arc => {
+                        if r > 180.0 {
+                            return None;
+                        }
+                        90.0 - r
+                    }
+                    _ => {
+                        if r > 2.0 * R0 {
+                            return None;
+                        }
+                        90.0 - 2.0 * (r / (2.0 * R0)).asin() / DEGRA
+                    }
+                };
+                Some((phi, theta))
+            }
+            Projection::Car => {
+                if y.abs() > 90.0 || x.abs() > 180.0 {
+                    return None;
+                }
+                Some((x, y))
+            }
+            Projection::Ait => {
+                let z2 = 1.0 - (x / (4.0 * R0)).powi(2) - (y / (2.0 * R0)).powi(2);
+                if z2 < 0.5 {
+                    return None;
+                }
+                let z = z2.sqrt();
+                let phi = 2.0 * (z * x / (2.0 * R0)).atan2(2.0 * z2 - 1.0) / DEGRA;
+                let theta = (y * z / R0).clamp(-1.0, 1.0).asin() / DEGRA;
+                Some((phi, theta))
+            }
+            Projection::Mol => {
+                let sqrt2 = std::f64::consts::SQRT_2;
+                let sin_gamma = y / (sqrt2 * R0);
+                if sin_gamma.abs() > 1.0 {
+                    return None;
+                }
+                let gamma = sin_gamma.asin();
+                let phi = if gamma.cos() == 0.0 {
+                    0.0
+                } else {
+                    std::f64::consts::PI * x / (2.0 * sqrt2 * gamma.cos())
+                };
+                if phi.abs() > 180.0 {
+                    return None;
+                }
+                let theta = ((2.0 * gamma + (2.0 * gamma).sin()) / std::f64::consts::PI).clamp(-1.0, 1.0).asin();
+                Some((phi, theta / DEGRA))
+            }
+        }
+    }
+}
+
+/// Wrap a longitude in degrees into [-180, 180)
+fn normalize_longitude(phi: f64) -> f64 {
+    (phi + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Solve 2 gamma + sin(2 gamma) = pi sin(theta) for the Mollweide projection (Newton's method)
+fn mollweide_gamma(theta: f64) -> f64 {
+    if (theta.abs() - std::f64::consts::FRAC_PI_2).abs() < 1e-12 {
+        return theta;
+    }
+    let target = std::f64::consts::PI * theta.sin();
+    let mut gamma = theta;
+    for _ in 0..100 {
+        let step = (2.0 * gamma + (2.0 * gamma).sin() - target) / (2.0 + 2.0 * (2.0 * gamma).cos());
+        gamma -= step;
+        if step.abs() < 1e-15 {
+            break;
+        }
+    }
+    gamma
+}
+
+/// SphericalRotation struct
+///
+/// This struct represents the rotation between celestial coordinates (RA, DEC) and the native
+/// spherical coordinates of a projection, as defined in WCS paper II.
+///
+/// # Attributes
+///
+/// * `alpha_p` - Right ascension of the native pole in degrees
+/// * `delta_p` - Declination of the native pole in degrees
+/// * `phi_p` - Native longitude of the celestial pole in degrees (LONPOLE)
+///
+/// # Examples
+///
+/// ```
+/// use flare::projection::{Projection, SphericalRotation};
+///
+/// let rotation = SphericalRotation::new(Projection::Tan, 150.0, 2.0, None, None);
+/// // the reference point is at the native pole of zenithal projections
+/// let (_, theta) = rotation.to_native(150.0, 2.0);
+/// assert!((theta - 90.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SphericalRotation {
+    pub alpha_p: f64,
+    pub delta_p: f64,
+    pub phi_p: f64,
+}
+
+impl SphericalRotation {
+    /// Create the rotation for a projection with a given reference point
+    ///
+    /// # Arguments
+    ///
+    /// * `projection` - The projection
+    /// * `ra0` - Right ascension of the reference point (CRVAL1) in degrees
+    /// * `dec0` - Declination of the reference point (CRVAL2) in degrees
+    /// * `lonpole` - Optional native longitude of the celestial pole (LONPOLE) in degrees
+    /// * `latpole` - Optional native latitude of the celestial pole (LATPOLE) in degrees,
+    ///   used to pick between the two solutions for non-zenithal projections
+    ///
+    /// # Returns
+    ///
+    /// * `SphericalRotation` - A new SphericalRotation object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::{Projection, SphericalRotation};
+    ///
+    /// let rotation = SphericalRotation::new(Projection::Car, 10.0, -20.0, None, None);
+    /// // the reference point is at the origin of the native coordinates of cylindrical projections
+    /// let (phi, theta) = rotation.to_native(10.0, -20.0);
+    /// assert!(phi.abs() < 1e-9 && theta.abs() < 1e-9);
+    /// ```
+    pub fn new(projection: Projection, ra0: f64, dec0: f64, lonpole: Option<f64>, latpole: Option<f64>) -> SphericalRotation {
+        let theta0 = projection.theta0();
+        let phi_p = lonpole.unwrap_or(if dec0 >= theta0 { 0.0 } else { 180.0 });
+        if theta0 == 90.0 {
+            return SphericalRotation { alpha_p: ra0, delta_p: dec0, phi_p };
+        }
+
+        // WCS paper II, eqs. (8) to (10), with phi_0 = 0
+        let latpole = latpole.unwrap_or(90.0);
+        let (dec0_rad, theta0_rad, dphi) = (dec0 * DEGRA, theta0 * DEGRA, phi_p * DEGRA);
+        let base = theta0_rad.sin().atan2(theta0_rad.cos() * dphi.cos());
+        let denominator = (1.0 - (theta0_rad.cos() * dphi.sin()).powi(2)).sqrt();
+        let spread = (dec0_rad.sin() / denominator).clamp(-1.0, 1.0).acos();
+        let candidates = [base + spread, base - spread]
+            .iter()
+            .map(|d| {
+                let d = d / DEGRA;
+                // bring the solution back into [-180, 180)
+                (d + 180.0).rem_euclid(360.0) - 180.0
+            })
+            .filter(|d| d.abs() <= 90.0 + 1e-9)
+            .collect::<Vec<f64>>();
+        let delta_p = candidates
+            .iter()
+            .copied()
+            .min_by(|a, b| (a - latpole).abs().total_cmp(&(b - latpole).abs()))
+            .unwrap_or(90.0)
+            .clamp(-90.0, 90.0);
+
+        let delta_p_rad = delta_p * DEGRA;
+        let alpha_p = if (delta_p.abs() - 90.0).abs() < 1e-12 {
+            if delta_p > 0.0 { ra0 + phi_p - 180.0 } else { ra0 - phi_p }
+        } else {
+            let x = (theta0_rad.sin() - delta_p_rad.sin() * dec0_rad.sin()) / (delta_p_rad.cos() * dec0_rad.cos());
+            let y = dphi.sin() * theta0_rad.cos() / dec0_rad.cos();
+            ra0 - y.atan2(x) / DEGRA
+        };
+        SphericalRotation { alpha_p, delta_p, phi_p }
+    }
+
+    /// Convert celestial coordinates to native spherical coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension in degrees
+    /// * `dec` - Declination in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - Native longitude and latitude (phi, theta) in degrees
+    pub fn to_native(&self, ra: f64, dec: f64) -> (f64, f64) {
+        let (ra, dec) = (ra * DEGRA, dec * DEGRA);
+        let (alpha_p, delta_p) = (self.alpha_p * DEGRA, self.delta_p * DEGRA);
+        let x = dec.sin() * delta_p.cos() - dec.cos() * delta_p.sin() * (ra - alpha_p).cos();
+        let y = -dec.cos() * (ra - alpha_p).sin();
+        let phi = self.phi_p + y.atan2(x) / DEGRA;
+        let theta = (dec.sin() * delta_p.sin() + dec.cos() * delta_p.cos() * (ra - alpha_p).cos())
+            .clamp(-1.0, 1.0)
+            .asin();
+        (normalize_longitude(phi), theta / DEGRA)
+    }
+
+    /// Convert native spherical coordinates to celestial coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `phi` - Native longitude in degrees
+    /// * `theta` - Native latitude in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - Right ascension (in [0, 360)) and declination in degrees
+    pub fn to_celestial(&self, phi: f64, theta: f64) -> (f64, f64) {
+        let (phi, theta) = (phi * DEGRA, theta * DEGRA);
+        let (delta_p, phi_p) = (self.delta_p * DEGRA, self.phi_p * DEGRA);
+        let x = theta.sin() * delta_p.cos() - theta.cos() * delta_p.sin() * (phi - phi_p).cos();
+        let y = -theta.cos() * (phi - phi_p).sin();
+        let ra = self.alpha_p + y.atan2(x) / DEGRA;
+        let dec = (theta.sin() * delta_p.sin() + theta.cos() * delta_p.cos() * (phi - phi_p).cos())
+            .clamp(-1.0, 1.0)
+            .asin();
+        (ra.rem_euclid(360.0), dec / DEGRA)
+    }
+}
@@ -0,0 +1,239 @@
+use crate::region::SkyBox;
+use crate::spatial::DEGRA;
+
+/// Calculate the minimal RA/DEC boxes enclosing a cone, e.g. to prefilter a database query
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension of the center of the cone in degrees
+/// * `dec` - Declination of the center of the cone in degrees
+/// * `radius` - Radius of the cone in degrees
+///
+/// # Returns
+///
+/// * `Vec<SkyBox>` - One box, or two boxes if the cone straddles RA = 0/360. The boxes never wrap
+///   (`ra_min <= ra_max`, with RA in [0, 360]), so they can be used directly as range queries.
+///
+/// # Examples
+///
+/// ```
+/// use flare::query::cone_bounding_boxes;
+///
+/// let boxes = cone_bounding_boxes(150.0, 2.0, 1.0);
+/// assert_eq!(boxes.len(), 1);
+/// assert!((boxes[0].ra_min - 148.999391).abs() < 1e-6);
+/// assert_eq!((boxes[0].dec_min, boxes[0].dec_max), (1.0, 3.0));
+///
+/// // across RA = 0/360, the box is split in two
+/// let boxes = cone_bounding_boxes(359.5, 10.0, 1.0);
+/// assert_eq!(boxes.len(), 2);
+/// assert_eq!(boxes[0].ra_max, 360.0);
+/// assert_eq!(boxes[1].ra_min, 0.0);
+///
+/// // when the cone contains a pole, the box covers all RAs
+/// let boxes = cone_bounding_boxes(42.0, 89.5, 1.0);
+/// assert_eq!((boxes[0].ra_min, boxes[0].ra_max, boxes[0].dec_max), (0.0, 360.0, 90.0));
+/// ```
+///
+/// # Notes
+///
+/// The RA half-width of the boxes is asin(sin(radius) / cos(dec)), which is wider than `radius / cos(dec)`
+/// close to the poles: positions with a DEC between the center and the edge of the cone
+/// stick out further in RA than the edges of the DEC range.
+pub fn cone_bounding_boxes(ra: f64, dec: f64, radius: f64) -> Vec<SkyBox> {
+    let dec_min = (dec - radius).max(-90.0);
+    let dec_max = (dec + radius).min(90.0);
+    if dec_min <= -90.0 || dec_max >= 90.0 {
+        return vec![SkyBox::new(0.0, 360.0, dec_min, dec_max)];
+    }
+    let sin_half_width = (radius * DEGRA).sin() / (dec * DEGRA).cos();
+    if radius >= 90.0 || sin_half_width >= 1.0 {
+        return vec![SkyBox::new(0.0, 360.0, dec_min, dec_max)];
+    }
+    let half_width = sin_half_width.asin() / DEGRA;
+    let ra = ra.rem_euclid(360.0);
+    let ra_min = ra - half_width;
+    let ra_max = ra + half_width;
+    if ra_min < 0.0 {
+        vec![
+            SkyBox::new(ra_min + 360.0, 360.0, dec_min, dec_max),
+            SkyBox::new(0.0, ra_max, dec_min, dec_max),
+        ]
+    } else if ra_max > 360.0 {
+        vec![
+            SkyBox::new(ra_min, 360.0, dec_min, dec_max),
+            SkyBox::new(0.0, ra_max - 360.0, dec_min, dec_max),
+        ]
+    } else {
+        vec![SkyBox::new(ra_min, ra_max, dec_min, dec_max)]
+    }
+}
+
+/// Build a MongoDB filter selecting the documents within the RA/DEC boxes enclosing a cone
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension of the center of the cone in degrees
+/// * `dec` - Declination of the center of the cone in degrees
+/// * `radius` - Radius of the cone in degrees
+/// * `ra_field` - Name of the RA field of the documents
+/// * `dec_field` - Name of the DEC field of the documents
+///
+/// # Returns
+///
+/// * `String` - The filter, as a JSON document
+///
+/// # Examples
+///
+/// ```
+/// use flare::query::cone_box_filter;
+///
+/// let filter = cone_box_filter(0.0, 0.0, 1.0, "ra", "dec");
+/// assert_eq!(
+///     filter,
+///     r#"{"$or": [{"ra": {"$gte": 359, "$lte": 360}, "dec": {"$gte": -1, "$lte": 1}}, {"ra": {"$gte": 0, "$lte": 1}, "dec": {"$gte": -1, "$lte": 1}}]}"#
+/// );
+/// ```
+///
+/// # Notes
+///
+/// This is a prefilter: documents in the corners of the boxes are outside of the cone,
+/// so the results still need an exact check (e.g. with `spatial::great_circle_distance`).
+pub fn cone_box_filter(ra: f64, dec: f64, radius: f64, ra_field: &str, dec_field: &str) -> String {
+    let boxes = cone_bounding_boxes(ra, dec, radius)
+        .iter()
+        .map(|b| {
+            format!(
+                "{{{}: {{\"$gte\": {}, \"$lte\": {}}}, {}: {{\"$gte\": {}, \"$lte\": {}}}}}",
+                json_string(ra_field), b.ra_min, b.ra_max, json_string(dec_field), b.dec_min, b.dec_max
+            )
+        })
+        .collect::<Vec<String>>();
+    format!("{{\"$or\": [{}]}}", boxes.join(", "))
+}
+
+/// Convert a RA/DEC position to the longitude/latitude of a GeoJSON point
+///
+/// GeoJSON longitudes are in [-180, 180], so the RA is shifted by 180 degrees:
+/// this is the convention used by the alert databases with a 2dsphere index on a `radec_geojson` field.
+///
+/// # Arguments
+///
+/// * `ra` - Right Ascension in degrees
+/// * `dec` - Declination in degrees
+///
+/// # Returns
+///
+/// * `(f64, f64)` - Longitude and latitude of the GeoJSON point in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::query::radec2geojson;
+///
+/// assert_eq!(radec2geojson(0.0, 10.0), (-180.0, 10.0));
+/// assert_eq!(radec2geojson(270.0, -10.0), (90.0, -10.0));
+/// ```
+pub fn radec2geojson(ra: f64, dec: f64) -> (f64, f64) {
+    (ra.rem_euclid(360.0) - 180.0, dec)
+}
+
+/// GeoCone struct
+///
+/// This struct represents a cone as a `$geoWithin` / `$centerSphere` query, for MongoDB 2dsphere indexes
+/// on GeoJSON points built with `radec2geojson`.
+///
+/// # Attributes
+///
+/// * `lon` - Longitude of the center of the cone in degrees (RA - 180)
+/// * `lat` - Latitude of the center of the cone in degrees (DEC)
+/// * `radius` - Radius of the cone in radians
+///
+/// # Examples
+///
+/// ```
+/// use flare::query::GeoCone;
+///
+/// let cone = GeoCone::new(270.0, -10.0, 2.0 / 3600.0);
+/// assert_eq!((cone.lon, cone.lat), (90.0, -10.0));
+/// assert_eq!(
+///     cone.to_filter("coordinates.radec_geojson"),
+///     format!(r#"{{"coordinates.radec_geojson": {{"$geoWithin": {{"$centerSphere": [[90, -10], {}]}}}}}}"#, cone.radius)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoCone {
+    pub lon: f64,
+    pub lat: f64,
+    pub radius: f64,
+}
+
+impl GeoCone {
+    /// Create a new GeoCone
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right Ascension of the center of the cone in degrees
+    /// * `dec` - Declination of the center of the cone in degrees
+    /// * `radius` - Radius of the cone in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `GeoCone` - A new GeoCone
+    pub fn new(ra: f64, dec: f64, radius: f64) -> GeoCone {
+        let (lon, lat) = radec2geojson(ra, dec);
+        GeoCone { lon, lat, radius: radius * DEGRA }
+    }
+
+    /// Get the `$geoWithin` operator of the query, as a JSON document
+    pub fn to_geo_within(&self) -> String {
+        format!(
+            "{{\"$geoWithin\": {{\"$centerSphere\": [[{}, {}], {}]}}}}",
+            self.lon, self.lat, self.radius
+        )
+    }
+
+    /// Get the query on a field holding GeoJSON points, as a JSON document
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - Name of the field, escaped as a JSON string
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The filter, as a JSON document
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::query::GeoCone;
+    ///
+    /// let cone = GeoCone::new(180.0, 0.0, 0.0);
+    /// assert_eq!(
+    ///     cone.to_filter(r#"weird "field"\name"#),
+    ///     r#"{"weird \"field\"\\name": {"$geoWithin": {"$centerSphere": [[0, 0], 0]}}}"#
+    /// );
+    /// ```
+    pub fn to_filter(&self, field: &str) -> String {
+        format!("{{{}: {}}}", json_string(field), self.to_geo_within())
+    }
+}
+
+/// Quote and escape a string (e.g. a field name) for a JSON document
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -0,0 +1,923 @@
+use std::f64::consts::PI;
+
+use crate::spatial::{great_circle_distance, offset_by, position_angle, DEGRA};
+use crate::target::Target;
+use crate::vector::{Cartesian3, UnitVector};
+
+/// Number of square degrees in a steradian
+pub const SQDEG_PER_SR: f64 = (180.0 / PI) * (180.0 / PI);
+
+/// Number of points sampled along region boundaries for the generic intersection test
+const BOUNDARY_SAMPLES: usize = 360;
+
+/// Region trait
+///
+/// This trait is implemented by all the regions of the sky defined in this module.
+/// Positions are given in degrees, areas in square degrees. All the computations are done
+/// on the sphere, so regions can straddle RA = 0/360 and contain the celestial poles.
+///
+/// # Methods
+///
+/// * `contains` - Check if a position is inside the region
+/// * `contains_target` - Check if a target is inside the region
+/// * `area` - Calculate the area of the region
+/// * `bounding_cap` - Calculate a circle enclosing the whole region
+/// * `boundary` - Sample points along the boundary of the region
+/// * `intersects` - Check if the region overlaps another region
+///
+/// # Examples
+///
+/// ```
+/// use flare::region::{Region, SkyCircle, SkyPolygon};
+///
+/// let regions: Vec<Box<dyn Region>> = vec![
+///     Box::new(SkyCircle::new(0.0, 0.0, 1.0)),
+///     Box::new(SkyPolygon::new(&[(10.0, -1.0), (12.0, -1.0), (12.0, 1.0), (10.0, 1.0)])),
+/// ];
+/// let hits = regions.iter().filter(|r| r.contains(359.5, 0.2)).count();
+/// assert_eq!(hits, 1);
+/// ```
+pub trait Region {
+    /// Check if a position is inside the region
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the position in degrees
+    /// * `dec` - Declination of the position in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the position is inside the region, false otherwise
+    fn contains(&self, ra: f64, dec: f64) -> bool;
+
+    /// Calculate the area of the region
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Area of the region in square degrees
+    fn area(&self) -> f64;
+
+    /// Calculate a circle (spherical cap) enclosing the whole region
+    ///
+    /// The cap is not guaranteed to be the smallest possible one, but it always contains the region,
+    /// which makes it suitable for quick rejection tests.
+    ///
+    /// # Returns
+    ///
+    /// * `SkyCircle` - A circle enclosing the region
+    fn bounding_cap(&self) -> SkyCircle;
+
+    /// Sample points along the boundary of the region
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Approximate number of points to sample
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(f64, f64)>` - RA and DEC of the points in degrees
+    fn boundary(&self, n: usize) -> Vec<(f64, f64)>;
+
+    /// Check if a target is inside the region
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the target is inside the region, false otherwise
+    fn contains_target(&self, target: &Target) -> bool {
+        self.contains(target.ra, target.dec)
+    }
+
+    /// Check if the region overlaps another region
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other region
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the regions overlap, false otherwise
+    ///
+    /// # Notes
+    ///
+    /// This generic test first compares the bounding caps of the two regions, and then looks for
+    /// boundary points of each region lying inside the other one. It can miss overlaps thinner than
+    /// the boundary sampling; use `SkyCircle::intersects_circle`, `SkyPolygon::intersects_circle`
+    /// or `SkyPolygon::intersects_polygon` for exact tests.
+    fn intersects(&self, other: &dyn Region) -> bool {
+        if !self.bounding_cap().intersects_circle(&other.bounding_cap()) {
+            return false;
+        }
+        self.boundary(BOUNDARY_SAMPLES).iter().any(|&(ra, dec)| other.contains(ra, dec))
+            || other.boundary(BOUNDARY_SAMPLES).iter().any(|&(ra, dec)| self.contains(ra, dec))
+    }
+}
+
+/// Area of a region described in polar coordinates around its center, in square degrees
+fn polar_area<F>(edge_distance: F) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let n = 720;
+    let step = 2.0 * PI / n as f64;
+    let sum = (0..n)
+        .map(|i| 1.0 - (edge_distance((i as f64 + 0.5) * step) * DEGRA).cos())
+        .sum::<f64>();
+    sum * step * SQDEG_PER_SR
+}
+
+/// SkyCircle struct
+///
+/// This struct represents a circle (spherical cap) on the sky.
+///
+/// # Attributes
+///
+/// * `ra` - Right ascension of the center in degrees
+/// * `dec` - Declination of the center in degrees
+/// * `radius` - Radius of the circle in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::region::{Region, SkyCircle};
+///
+/// let circle = SkyCircle::new(359.8, 0.0, 0.5);
+/// assert!(circle.contains(0.1, 0.1));
+/// assert!(!circle.contains(0.5, 0.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkyCircle {
+    pub ra: f64,
+    pub dec: f64,
+    pub radius: f64,
+}
+
+impl SkyCircle {
+    /// Create a new SkyCircle
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the center in degrees
+    /// * `dec` - Declination of the center in degrees
+    /// * `radius` - Radius of the circle in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `SkyCircle` - A new SkyCircle object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::{Region, SkyCircle};
+    ///
+    /// // a circle of 1 radian radius covers 2 * pi * (1 - cos(1)) steradians
+    /// let circle = SkyCircle::new(10.0, 20.0, 1.0_f64.to_degrees());
+    /// let expected = 2.0 * std::f64::consts::PI * (1.0 - 1.0_f64.cos()) * flare::region::SQDEG_PER_SR;
+    /// assert!((circle.area() - expected).abs() < 1e-9);
+    /// ```
+    pub fn new(ra: f64, dec: f64, radius: f64) -> SkyCircle {
+        SkyCircle { ra, dec, radius }
+    }
+
+    /// Check if the circle overlaps another circle
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other circle
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the circles overlap, false otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::SkyCircle;
+    ///
+    /// let a = SkyCircle::new(0.0, 89.0, 1.0);
+    /// let b = SkyCircle::new(180.0, 89.0, 1.1);
+    /// assert!(a.intersects_circle(&b)); // across the pole
+    ///
+    /// let c = SkyCircle::new(180.0, 89.0, 0.9);
+    /// assert!(!a.intersects_circle(&c));
+    /// ```
+    pub fn intersects_circle(&self, other: &SkyCircle) -> bool {
+        great_circle_distance(self.ra, self.dec, other.ra, other.dec) <= self.radius + other.radius
+    }
+}
+
+impl Region for SkyCircle {
+    fn contains(&self, ra: f64, dec: f64) -> bool {
+        great_circle_distance(self.ra, self.dec, ra, dec) <= self.radius
+    }
+
+    fn area(&self) -> f64 {
+        2.0 * PI * (1.0 - (self.radius * DEGRA).cos()) * SQDEG_PER_SR
+    }
+
+    fn bounding_cap(&self) -> SkyCircle {
+        self.clone()
+    }
+
+    fn boundary(&self, n: usize) -> Vec<(f64, f64)> {
+        (0..n)
+            .map(|i| offset_by(self.ra, self.dec, 360.0 * i as f64 / n as f64, self.radius))
+            .collect()
+    }
+}
+
+/// SkyAnnulus struct
+///
+/// This struct represents an annulus (ring between two concentric circles) on the sky.
+///
+/// # Attributes
+///
+/// * `ra` - Right ascension of the center in degrees
+/// * `dec` - Declination of the center in degrees
+/// * `inner_radius` - Inner radius in degrees
+/// * `outer_radius` - Outer radius in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::region::{Region, SkyAnnulus};
+///
+/// let annulus = SkyAnnulus::new(45.0, -30.0, 0.1, 0.2);
+/// assert!(!annulus.contains(45.0, -30.05));
+/// assert!(annulus.contains(45.0, -30.15));
+/// assert!(!annulus.contains(45.0, -30.25));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkyAnnulus {
+    pub ra: f64,
+    pub dec: f64,
+    pub inner_radius: f64,
+    pub outer_radius: f64,
+}
+
+impl SkyAnnulus {
+    /// Create a new SkyAnnulus
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the center in degrees
+    /// * `dec` - Declination of the center in degrees
+    /// * `inner_radius` - Inner radius in degrees
+    /// * `outer_radius` - Outer radius in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `SkyAnnulus` - A new SkyAnnulus object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::{Region, SkyAnnulus, SkyCircle};
+    ///
+    /// let annulus = SkyAnnulus::new(45.0, -30.0, 1.0, 2.0);
+    /// let outer = SkyCircle::new(45.0, -30.0, 2.0);
+    /// let inner = SkyCircle::new(45.0, -30.0, 1.0);
+    /// assert!((annulus.area() - (outer.area() - inner.area())).abs() < 1e-9);
+    /// ```
+    pub fn new(ra: f64, dec: f64, inner_radius: f64, outer_radius: f64) -> SkyAnnulus {
+        if inner_radius > outer_radius {
+            panic!("Invalid annulus radii: inner {} > outer {}", inner_radius, outer_radius);
+        }
+        SkyAnnulus { ra, dec, inner_radius, outer_radius }
+    }
+}
+
+impl Region for SkyAnnulus {
+    fn contains(&self, ra: f64, dec: f64) -> bool {
+        let distance = great_circle_distance(self.ra, self.dec, ra, dec);
+        distance >= self.inner_radius && distance <= self.outer_radius
+    }
+
+    fn area(&self) -> f64 {
+        2.0 * PI * ((self.inner_radius * DEGRA).cos() - (self.outer_radius * DEGRA).cos()) * SQDEG_PER_SR
+    }
+
+    fn bounding_cap(&self) -> SkyCircle {
+        SkyCircle::new(self.ra, self.dec, self.outer_radius)
+    }
+
+    fn boundary(&self, n: usize) -> Vec<(f64, f64)> {
+        let half = (n / 2).max(1);
+        let mut points = SkyCircle::new(self.ra, self.dec, self.outer_radius).boundary(half);
+        points.extend(SkyCircle::new(self.ra, self.dec, self.inner_radius).boundary(half));
+        points
+    }
+}
+
+/// SkyBox struct
+///
+/// This struct represents a box in RA/DEC, bounded by two meridians and two parallels.
+///
+/// # Attributes
+///
+/// * `ra_min` - Lower RA bound in degrees
+/// * `ra_max` - Upper RA bound in degrees, the box wraps around RA = 0/360 if `ra_max < ra_min`
+/// * `dec_min` - Lower DEC bound in degrees
+/// * `dec_max` - Upper DEC bound in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::region::{Region, SkyBox};
+///
+/// let sky_box = SkyBox::new(350.0, 10.0, -5.0, 5.0);
+/// assert!(sky_box.contains(355.0, 0.0));
+/// assert!(sky_box.contains(5.0, 0.0));
+/// assert!(!sky_box.contains(180.0, 0.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkyBox {
+    pub ra_min: f64,
+    pub ra_max: f64,
+    pub dec_min: f64,
+    pub dec_max: f64,
+}
+
+impl SkyBox {
+    /// Create a new SkyBox
+    ///
+    /// # Arguments
+    ///
+    /// * `ra_min` - Lower RA bound in degrees
+    /// * `ra_max` - Upper RA bound in degrees, the box wraps around RA = 0/360 if `ra_max < ra_min`
+    /// * `dec_min` - Lower DEC bound in degrees
+    /// * `dec_max` - Upper DEC bound in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `SkyBox` - A new SkyBox object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::{Region, SkyBox};
+    ///
+    /// // the whole northern hemisphere
+    /// let sky_box = SkyBox::new(0.0, 360.0, 0.0, 90.0);
+    /// assert!((sky_box.area() - 20626.480624709636).abs() < 1e-6);
+    /// assert!(sky_box.contains(123.0, 90.0));
+    /// ```
+    pub fn new(ra_min: f64, ra_max: f64, dec_min: f64, dec_max: f64) -> SkyBox {
+        if dec_min > dec_max || dec_min < -90.0 || dec_max > 90.0 {
+            panic!("Invalid DEC range: [{}, {}]", dec_min, dec_max);
+        }
+        SkyBox { ra_min, ra_max, dec_min, dec_max }
+    }
+
+    /// Calculate the RA extent of the box, taking the RA = 0/360 wrap into account
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Width of the box in RA, in degrees in [0, 360]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::SkyBox;
+    ///
+    /// assert_eq!(SkyBox::new(350.0, 10.0, 0.0, 1.0).ra_width(), 20.0);
+    /// assert_eq!(SkyBox::new(0.0, 360.0, 0.0, 1.0).ra_width(), 360.0);
+    /// ```
+    pub fn ra_width(&self) -> f64 {
+        let width = self.ra_max - self.ra_min;
+        if width >= 360.0 {
+            return 360.0;
+        }
+        width.rem_euclid(360.0)
+    }
+}
+
+impl Region for SkyBox {
+    fn contains(&self, ra: f64, dec: f64) -> bool {
+        if dec < self.dec_min || dec > self.dec_max {
+            return false;
+        }
+        if dec.abs() >= 90.0 {
+            return true;
+        }
+        (ra - self.ra_min).rem_euclid(360.0) <= self.ra_width()
+    }
+
+    fn area(&self) -> f64 {
+        self.ra_width() * DEGRA
+            * ((self.dec_max * DEGRA).sin() - (self.dec_min * DEGRA).sin())
+            * SQDEG_PER_SR
+    }
+
+    fn bounding_cap(&self) -> SkyCircle {
+        let width = self.ra_width();
+        let north = self.dec_min + self.dec_max >= 0.0;
+        if self.dec_max >= 90.0 || (width > 180.0 && north) {
+            return SkyCircle::new(0.0, 90.0, 90.0 - self.dec_min);
+        }
+        if self.dec_min <= -90.0 || width > 180.0 {
+            return SkyCircle::new(0.0, -90.0, 90.0 + self.dec_max);
+        }
+        // for boxes narrower than 180 degrees in RA, the farthest points from the center are corners
+        let ra = (self.ra_min + width / 2.0).rem_euclid(360.0);
+        let dec = (self.dec_min + self.dec_max) / 2.0;
+        let radius = [self.dec_min, self.dec_max]
+            .iter()
+            .map(|&corner_dec| great_circle_distance(ra, dec, self.ra_min, corner_dec))
+            .fold(0.0, f64::max);
+        SkyCircle::new(ra, dec, radius)
+    }
+
+    fn boundary(&self, n: usize) -> Vec<(f64, f64)> {
+        let per_edge = (n / 4).max(1);
+        let width = self.ra_width();
+        let mut points = Vec::with_capacity(4 * per_edge);
+        for i in 0..per_edge {
+            let f = i as f64 / per_edge as f64;
+            let ra = (self.ra_min + f * width).rem_euclid(360.0);
+            points.push((ra, self.dec_min));
+            points.push(((self.ra_max - f * width).rem_euclid(360.0), self.dec_max));
+            points.push((self.ra_max.rem_euclid(360.0), self.dec_min + f * (self.dec_max - self.dec_min)));
+            points.push((self.ra_min.rem_euclid(360.0), self.dec_max - f * (self.dec_max - self.dec_min)));
+        }
+        points
+    }
+}
+
+/// SkyEllipse struct
+///
+/// This struct represents an ellipse on the sky, defined in polar coordinates around its center
+/// (angular distance and position angle), which keeps it well-defined near the poles.
+///
+/// # Attributes
+///
+/// * `ra` - Right ascension of the center in degrees
+/// * `dec` - Declination of the center in degrees
+/// * `a` - Semi-major axis in degrees
+/// * `b` - Semi-minor axis in degrees
+/// * `pa` - Position angle of the major axis (East of North) in degrees
+///
+/// # Examples
+///
+/// ```
+/// use flare::region::{Region, SkyEllipse};
+///
+/// // an ellipse elongated along the North-South direction
+/// let ellipse = SkyEllipse::new(150.0, 2.0, 0.01, 0.005, 0.0);
+/// assert!(ellipse.contains(150.0, 2.009));
+/// assert!(!ellipse.contains(150.009, 2.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkyEllipse {
+    pub ra: f64,
+    pub dec: f64,
+    pub a: f64,
+    pub b: f64,
+    pub pa: f64,
+}
+
+impl SkyEllipse {
+    /// Create a new SkyEllipse
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the center in degrees
+    /// * `dec` - Declination of the center in degrees
+    /// * `a` - Semi-major axis in degrees
+    /// * `b` - Semi-minor axis in degrees
+    /// * `pa` - Position angle of the major axis (East of North) in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `SkyEllipse` - A new SkyEllipse object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::{Region, SkyEllipse};
+    ///
+    /// let ellipse = SkyEllipse::new(150.0, 2.0, 0.02, 0.01, 30.0);
+    /// let flat_area = std::f64::consts::PI * 0.02 * 0.01;
+    /// assert!((ellipse.area() - flat_area).abs() / flat_area < 1e-6);
+    /// ```
+    pub fn new(ra: f64, dec: f64, a: f64, b: f64, pa: f64) -> SkyEllipse {
+        if b > a || b <= 0.0 {
+            panic!("Invalid ellipse axes: a = {}, b = {}", a, b);
+        }
+        SkyEllipse { ra, dec, a, b, pa }
+    }
+
+    /// Create a new SkyEllipse from a semi-major axis and an axis ratio, as given by most galaxy catalogs
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the center in degrees
+    /// * `dec` - Declination of the center in degrees
+    /// * `a` - Semi-major axis in degrees
+    /// * `axis_ratio` - Ratio of the minor axis to the major axis (b/a), in (0, 1]
+    /// * `pa` - Position angle of the major axis (East of North) in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `SkyEllipse` - A new SkyEllipse object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::SkyEllipse;
+    ///
+    /// let ellipse = SkyEllipse::from_axis_ratio(150.0, 2.0, 0.01, 0.5, 30.0);
+    /// assert_eq!(ellipse.b, 0.005);
+    /// ```
+    pub fn from_axis_ratio(ra: f64, dec: f64, a: f64, axis_ratio: f64, pa: f64) -> SkyEllipse {
+        SkyEllipse::new(ra, dec, a, a * axis_ratio, pa)
+    }
+
+    /// Calculate the directional light radius (DLR): the distance from the center to the edge of
+    /// the ellipse, in the direction of a position
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the position in degrees
+    /// * `dec` - Declination of the position in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The DLR in degrees, between `b` and `a`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::SkyEllipse;
+    ///
+    /// // an ellipse elongated along the North-South direction
+    /// let ellipse = SkyEllipse::new(150.0, 2.0, 0.01, 0.005, 0.0);
+    /// assert!((ellipse.dlr(150.0, 2.1) - 0.01).abs() < 1e-12);
+    /// assert!((ellipse.dlr(150.1, 2.0) - 0.005).abs() < 1e-9);
+    /// ```
+    pub fn dlr(&self, ra: f64, dec: f64) -> f64 {
+        if great_circle_distance(self.ra, self.dec, ra, dec) == 0.0 {
+            return self.a;
+        }
+        let phi = (position_angle(self.ra, self.dec, ra, dec) - self.pa) * DEGRA;
+        self.edge_distance(phi)
+    }
+
+    /// Calculate the normalized elliptical distance of a position (d_DLR): its separation from the
+    /// center in units of the DLR, which is 1.0 on the edge of the ellipse
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension of the position in degrees
+    /// * `dec` - Declination of the position in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The separation divided by the DLR
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::SkyEllipse;
+    ///
+    /// let ellipse = SkyEllipse::new(150.0, 2.0, 0.01, 0.005, 0.0);
+    /// // 0.01 degree East of the center is twice the semi-minor axis
+    /// let (ra, dec) = flare::spatial::offset_by(150.0, 2.0, 90.0, 0.01);
+    /// assert!((ellipse.normalized_distance(ra, dec) - 2.0).abs() < 1e-9);
+    /// ```
+    ///
+    /// # References
+    ///
+    /// Sullivan et al. 2006, ApJ 648, 868; Gupta et al. 2016, AJ 152, 154
+    pub fn normalized_distance(&self, ra: f64, dec: f64) -> f64 {
+        great_circle_distance(self.ra, self.dec, ra, dec) / self.dlr(ra, dec)
+    }
+
+    /// Distance from the center to the edge of the ellipse, along a direction `phi` (in radians)
+    /// measured from the major axis
+    fn edge_distance(&self, phi: f64) -> f64 {
+        1.0 / ((phi.cos() / self.a).powi(2) + (phi.sin() / self.b).powi(2)).sqrt()
+    }
+}
+
+impl Region for SkyEllipse {
+    fn contains(&self, ra: f64, dec: f64) -> bool {
+        self.normalized_distance(ra, dec) <= 1.0
+    }
+
+    fn area(&self) -> f64 {
+        polar_area(|phi| self.edge_distance(phi))
+    }
+
+    fn bounding_cap(&self) -> SkyCircle {
+        SkyCircle::new(self.ra, self.dec, self.a)
+    }
+
+    fn boundary(&self, n: usize) -> Vec<(f64, f64)> {
+        (0..n)
+            .map(|i| {
+                let phi = 360.0 * i as f64 / n as f64;
+                offset_by(self.ra, self.dec, self.pa + phi, self.edge_distance(phi * DEGRA))
+            })
+            .collect()
+    }
+}
+
+/// SkyPolygon struct
+///
+/// This struct represents a polygon on the sky, with vertices connected by great circle arcs.
+/// The polygon can be convex or concave, and the vertices can be given in either order:
+/// its interior is taken to be the smaller of the two regions delimited by its edges.
+///
+/// # Notes
+///
+/// The polygon must be simple (no self-intersections) and must not contain two antipodal points,
+/// which is always the case for polygons that fit within a hemisphere such as instrument footprints.
+///
+/// # Examples
+///
+/// ```
+/// use flare::region::{Region, SkyPolygon};
+///
+/// // an L-shaped (concave) polygon straddling RA = 0
+/// let polygon = SkyPolygon::new(&[
+///     (358.0, -2.0), (2.0, -2.0), (2.0, 0.0), (0.0, 0.0), (0.0, 2.0), (358.0, 2.0),
+/// ]);
+/// assert!(polygon.contains(359.0, 1.0));
+/// assert!(polygon.contains(1.0, -1.0));
+/// assert!(!polygon.contains(1.0, 1.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkyPolygon {
+    vertices: Vec<(f64, f64)>,
+    directions: Vec<UnitVector>,
+    interior_left: bool,
+    area: f64,
+}
+
+impl SkyPolygon {
+    /// Create a new SkyPolygon
+    ///
+    /// # Arguments
+    ///
+    /// * `vertices` - RA and DEC of the vertices in degrees, in order along the boundary.
+    ///   The polygon is closed automatically, repeating the first vertex at the end is optional,
+    ///   and repeated consecutive vertices are dropped.
+    ///
+    /// # Returns
+    ///
+    /// * `SkyPolygon` - A new SkyPolygon object
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than 3 distinct vertices, or if two consecutive vertices are antipodal
+    /// (the great circle arc between them is not defined).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::{Region, SkyPolygon};
+    ///
+    /// // a polygon around the North celestial pole
+    /// let polygon = SkyPolygon::new(&[(0.0, 80.0), (90.0, 80.0), (180.0, 80.0), (270.0, 80.0)]);
+    /// assert!(polygon.contains(0.0, 90.0));
+    /// assert!(polygon.contains(0.0, 80.5));
+    /// // edges are great circle arcs, which bulge towards the pole between vertices
+    /// assert!(polygon.contains(45.0, 83.5));
+    /// assert!(!polygon.contains(45.0, 82.5));
+    /// assert_eq!(polygon.vertices().len(), 4);
+    ///
+    /// // repeated vertices are dropped
+    /// let polygon = SkyPolygon::new(&[(10.0, 10.0), (20.0, 10.0), (20.0, 10.0), (20.0, 20.0), (10.0, 20.0)]);
+    /// assert_eq!(polygon.vertices().len(), 4);
+    /// assert!(polygon.contains(15.0, 15.0));
+    /// assert!((polygon.area() - 96.665).abs() < 1e-3);
+    /// ```
+    pub fn new(vertices: &[(f64, f64)]) -> SkyPolygon {
+        // repeated consecutive vertices (including the closing one) do not make an edge: drop them
+        let mut points: Vec<((f64, f64), UnitVector)> = Vec::with_capacity(vertices.len());
+        for &(ra, dec) in vertices {
+            let u = UnitVector::from_radec(ra, dec);
+            match points.last() {
+                Some((_, last)) if same_direction(last, &u) => {}
+                _ => points.push(((ra, dec), u)),
+            }
+        }
+        while points.len() > 1 && same_direction(&points[0].1, &points[points.len() - 1].1) {
+            points.pop();
+        }
+        if points.len() < 3 {
+            panic!("A polygon needs at least 3 distinct vertices, got {}", points.len());
+        }
+        let (vertices, directions): (Vec<(f64, f64)>, Vec<UnitVector>) = points.into_iter().unzip();
+
+        let n = directions.len();
+        for i in 0..n {
+            if same_direction(&directions[i], &-directions[(i + 1) % n]) {
+                panic!(
+                    "Antipodal consecutive vertices {:?} and {:?}: their edge is not defined",
+                    vertices[i],
+                    vertices[(i + 1) % n]
+                );
+            }
+        }
+
+        // Gauss-Bonnet: the area to the left of the edges is 2 pi minus the sum of the turning angles
+        let normals = (0..n)
+            .map(|i| edge_normal(&directions[i], &directions[(i + 1) % n]))
+            .collect::<Vec<UnitVector>>();
+        let turning = (0..n)
+            .map(|i| {
+                let previous = &normals[(i + n - 1) % n];
+                previous.cross(&normals[i]).dot(&directions[i].cartesian()).atan2(previous.dot(&normals[i]))
+            })
+            .sum::<f64>();
+        let left_area = (2.0 * PI - turning).rem_euclid(4.0 * PI);
+        let interior_left = left_area <= 2.0 * PI;
+        let area = if interior_left { left_area } else { 4.0 * PI - left_area };
+
+        SkyPolygon { vertices, directions, interior_left, area: area * SQDEG_PER_SR }
+    }
+
+    /// Get the vertices of the polygon
+    ///
+    /// # Returns
+    ///
+    /// * `&[(f64, f64)]` - RA and DEC of the vertices in degrees
+    pub fn vertices(&self) -> &[(f64, f64)] {
+        &self.vertices
+    }
+
+    /// Iterate over the edges of the polygon, as pairs of unit vectors
+    fn edges(&self) -> impl Iterator<Item = (&UnitVector, &UnitVector)> {
+        let n = self.directions.len();
+        (0..n).map(move |i| (&self.directions[i], &self.directions[(i + 1) % n]))
+    }
+
+    /// Check if the polygon overlaps another polygon
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other polygon
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the polygons overlap, false otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::SkyPolygon;
+    ///
+    /// let a = SkyPolygon::new(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+    /// // a thin vertical strip crossing `a`, without any vertex inside it
+    /// let b = SkyPolygon::new(&[(0.9, -1.0), (1.1, -1.0), (1.1, 3.0), (0.9, 3.0)]);
+    /// assert!(a.intersects_polygon(&b));
+    ///
+    /// let c = SkyPolygon::new(&[(3.0, 0.0), (4.0, 0.0), (4.0, 1.0)]);
+    /// assert!(!a.intersects_polygon(&c));
+    /// ```
+    pub fn intersects_polygon(&self, other: &SkyPolygon) -> bool {
+        for (a, b) in self.edges() {
+            for (c, d) in other.edges() {
+                if arcs_intersect(a, b, c, d) {
+                    return true;
+                }
+            }
+        }
+        let (ra, dec) = self.vertices[0];
+        let (other_ra, other_dec) = other.vertices[0];
+        other.contains(ra, dec) || self.contains(other_ra, other_dec)
+    }
+
+    /// Check if the polygon overlaps a circle
+    ///
+    /// # Arguments
+    ///
+    /// * `circle` - The circle
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the polygon and the circle overlap, false otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::region::{SkyCircle, SkyPolygon};
+    ///
+    /// let polygon = SkyPolygon::new(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+    /// assert!(polygon.intersects_circle(&SkyCircle::new(1.0, -0.5, 0.6)));
+    /// assert!(!polygon.intersects_circle(&SkyCircle::new(1.0, -0.5, 0.4)));
+    /// ```
+    pub fn intersects_circle(&self, circle: &SkyCircle) -> bool {
+        if self.contains(circle.ra, circle.dec) {
+            return true;
+        }
+        let center = UnitVector::from_radec(circle.ra, circle.dec);
+        let radius = circle.radius * DEGRA;
+        self.edges().any(|(a, b)| arc_distance(&center, a, b) <= radius)
+    }
+}
+
+impl Region for SkyPolygon {
+    fn contains(&self, ra: f64, dec: f64) -> bool {
+        // winding number of the polygon around the position, seen in its tangent plane
+        let p = UnitVector::from_radec(ra, dec).cartesian();
+        let axis = if p.z.abs() < 0.9 { Cartesian3::new(0.0, 0.0, 1.0) } else { Cartesian3::new(1.0, 0.0, 0.0) };
+        let u = axis.cross(&p);
+        let u = u * (1.0 / u.norm());
+        let w = p.cross(&u);
+        let angles = self
+            .directions
+            .iter()
+            .map(|v| v.cartesian().dot(&w).atan2(v.cartesian().dot(&u)))
+            .collect::<Vec<f64>>();
+        let n = angles.len();
+        let winding = (0..n)
+            .map(|i| {
+                let delta = angles[(i + 1) % n] - angles[i];
+                (delta + PI).rem_euclid(2.0 * PI) - PI
+            })
+            .sum::<f64>();
+        if self.interior_left {
+            winding > PI
+        } else {
+            winding < -PI
+        }
+    }
+
+    fn area(&self) -> f64 {
+        self.area
+    }
+
+    fn bounding_cap(&self) -> SkyCircle {
+        let sum = self.directions.iter().fold(Cartesian3::new(0.0, 0.0, 0.0), |acc, v| acc + v.cartesian());
+        let center = match sum.direction() {
+            Some(center) if sum.norm() >= 1e-10 => center,
+            _ => return SkyCircle::new(0.0, 0.0, 180.0),
+        };
+        let radius = self.directions.iter().map(|v| center.angle(v)).fold(0.0, f64::max);
+        let (ra, dec) = center.to_radec();
+        SkyCircle::new(ra, dec, radius)
+    }
+
+    fn boundary(&self, n: usize) -> Vec<(f64, f64)> {
+        let per_edge = (n / self.directions.len()).max(1);
+        let mut points = Vec::with_capacity(per_edge * self.directions.len());
+        for (a, b) in self.edges() {
+            let angle = a.angle(b) * DEGRA;
+            for i in 0..per_edge {
+                let t = angle * i as f64 / per_edge as f64;
+                // spherical linear interpolation between the two vertices
+                let (wa, wb) = ((angle - t).sin() / angle.sin(), t.sin() / angle.sin());
+                let v = a.cartesian() * wa + b.cartesian() * wb;
+                points.push(v.direction().expect("the vertices of an edge are not antipodal").to_radec());
+            }
+        }
+        points
+    }
+}
+
+/// Check if two unit vectors point in the same direction (to about 1e-12 radian)
+fn same_direction(a: &UnitVector, b: &UnitVector) -> bool {
+    a.cross(b).norm() < 1e-12 && a.dot(b) > 0.0
+}
+
+/// Unit normal to the great circle through the two (distinct, non antipodal) vertices of an edge
+fn edge_normal(a: &UnitVector, b: &UnitVector) -> UnitVector {
+    a.cross(b).direction().expect("the vertices of an edge are distinct and not antipodal")
+}
+
+/// Check if a point, known to be on the great circle through `a` and `b`, lies on the shorter arc between them
+fn on_arc(p: &UnitVector, a: &UnitVector, b: &UnitVector) -> bool {
+    let n = a.cross(b);
+    a.cross(p).dot(&n) >= 0.0 && p.cross(b).dot(&n) >= 0.0
+}
+
+/// Check if the great circle arcs a-b and c-d intersect
+fn arcs_intersect(a: &UnitVector, b: &UnitVector, c: &UnitVector, d: &UnitVector) -> bool {
+    let line = a.cross(b).cross(&c.cross(d));
+    let p = match line.direction() {
+        Some(p) if line.norm() >= 1e-15 => p,
+        // both arcs lie on the same great circle
+        _ => return on_arc(c, a, b) || on_arc(d, a, b) || on_arc(a, c, d),
+    };
+    (on_arc(&p, a, b) && on_arc(&p, c, d)) || (on_arc(&-p, a, b) && on_arc(&-p, c, d))
+}
+
+/// Angular distance (in radians) from a point to the great circle arc a-b
+fn arc_distance(p: &UnitVector, a: &UnitVector, b: &UnitVector) -> f64 {
+    let n = edge_normal(a, b);
+    let along = p.cartesian() - n.cartesian() * p.dot(&n);
+    if along.norm() > 1e-15 {
+        if let Some(projected) = along.direction() {
+            if on_arc(&projected, a, b) {
+                return p.dot(&n).abs().clamp(0.0, 1.0).asin();
+            }
+        }
+    }
+    p.angle(a).min(p.angle(b)) * DEGRA
+}
@@ -0,0 +1,266 @@
+use crate::altaz::{hadec2altaz, normalize_hour_angle};
+use crate::observer::Observer;
+use crate::target::SkyObject;
+use crate::time::Time;
+
+/// Rate of change of the hour angle of a fixed position (sidereal rate), in degrees per day
+pub(crate) const SIDEREAL_RATE: f64 = 360.98564736629;
+
+/// Time span searched at once for rise & set times, in days: the search goes on span by span,
+/// away from the reference time, up to the search window of the object (`SkyObject::search_window`)
+const SEARCH_STEP: f64 = 1.5;
+
+/// Time step used to find the crossings of a horizon mask or of mount limits, in days (5 minutes)
+const LIMITS_STEP: f64 = 5.0 / 1440.0;
+
+/// SearchMode enum
+///
+/// This enum represents which event to find, relative to a reference time.
+///
+/// # Variants
+///
+/// * `Next` - The first event after the reference time
+/// * `Previous` - The last event before the reference time
+/// * `Nearest` - The event closest to the reference time, before or after it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchMode {
+    Next,
+    Previous,
+    Nearest,
+}
+
+/// HorizonCrossing enum
+///
+/// This enum represents the result of a rise or set time search.
+///
+/// # Variants
+///
+/// * `At` - The object crosses the horizon at the given time
+/// * `AlwaysUp` - The object does not cross the horizon, and stays above it (e.g. a circumpolar target, or the Sun during the polar day)
+/// * `AlwaysDown` - The object does not cross the horizon, and stays below it (e.g. a target that never rises, or the Sun during the polar night)
+///
+/// # Methods
+///
+/// * `time` - Get the time of the crossing, if any
+#[derive(Debug, Clone)]
+pub enum HorizonCrossing {
+    At(Time),
+    AlwaysUp,
+    AlwaysDown,
+}
+
+impl HorizonCrossing {
+    /// Get the time of the crossing, if any
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Time>` - The time of the crossing, or None if the object stays above or below the horizon
+    pub fn time(&self) -> Option<&Time> {
+        match self {
+            HorizonCrossing::At(time) => Some(time),
+            _ => None,
+        }
+    }
+}
+
+/// SunRiseSet enum
+///
+/// This enum represents the result of a sunrise & sunset search.
+///
+/// # Variants
+///
+/// * `Normal` - The next sunrise, and the sunset that follows it: the sunrise is always before the sunset
+/// * `AlwaysUp` - The Sun does not set (polar day)
+/// * `AlwaysDown` - The Sun does not rise (polar night)
+///
+/// # Methods
+///
+/// * `rise` - Get the time of the sunrise, if any
+/// * `set` - Get the time of the sunset, if any
+#[derive(Debug, Clone)]
+pub enum SunRiseSet {
+    Normal(Time, Time),
+    AlwaysUp,
+    AlwaysDown,
+}
+
+impl SunRiseSet {
+    /// Get the time of the sunrise, if any
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Time>` - The time of the sunrise, or None during the polar day or night
+    pub fn rise(&self) -> Option<&Time> {
+        match self {
+            SunRiseSet::Normal(rise, _) => Some(rise),
+            _ => None,
+        }
+    }
+
+    /// Get the time of the sunset, if any
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Time>` - The time of the sunset, or None during the polar day or night
+    pub fn set(&self) -> Option<&Time> {
+        match self {
+            SunRiseSet::Normal(_, set) => Some(set),
+            _ => None,
+        }
+    }
+}
+
+/// Hour angle of an object in degrees, in [-180, 180), at a Julian Date
+fn object_hour_angle(object: &dyn SkyObject, observer: &Observer, jd: f64) -> f64 {
+    let time = Time::from_jd(jd);
+    normalize_hour_angle(observer.local_sidereal_time(&time) - object.radec(observer, &time).0)
+}
+
+/// Find the closest time (as a JD) after (`forward`) or before a JD, when the hour angle of an object is `hour_angle`
+///
+/// The hour angle of the Sun, the Moon and fixed targets increases steadily, at close to the sidereal rate:
+/// starting from the estimate at the sidereal rate, Newton iterations converge quickly.
+pub(crate) fn hour_angle_time(object: &dyn SkyObject, observer: &Observer, jd: f64, hour_angle: f64, forward: bool) -> f64 {
+    let offset = |jd: f64| normalize_hour_angle(object_hour_angle(object, observer, jd) - hour_angle);
+    let current = offset(jd);
+    let mut t = if forward {
+        jd + (-current).rem_euclid(360.0) / SIDEREAL_RATE
+    } else {
+        jd - current.rem_euclid(360.0) / SIDEREAL_RATE
+    };
+    // times have a resolution of a second
+    for _ in 0..20 {
+        let correction = offset(t) / SIDEREAL_RATE;
+        t -= correction;
+        if correction.abs() * 86400.0 < 1.0 {
+            break;
+        }
+    }
+    t
+}
+
+/// Height of an object above its horizon (or `horizon`) in degrees, at a Julian Date: negative when it is below.
+/// With `limits`, the object is also below the horizon when it is outside of the horizon mask or the mount limits
+/// of the observer (see `Observer::limits_margin`).
+fn height(object: &dyn SkyObject, observer: &Observer, jd: f64, horizon: Option<f64>, limits: bool) -> f64 {
+    let time = Time::from_jd(jd);
+    let (ra, dec) = object.radec(observer, &time);
+    let ha = normalize_hour_angle(observer.local_sidereal_time(&time) - ra);
+    let (alt, az) = hadec2altaz(ha, dec, observer.lat);
+    let height = alt - horizon.unwrap_or_else(|| object.horizon_altitude(observer, &time));
+    if limits {
+        height.min(observer.limits_margin(ha, dec, alt, az))
+    } else {
+        height
+    }
+}
+
+/// Find all the times (as JDs) when an object crosses an altitude upwards (`rising`) or downwards between two JDs
+///
+/// The altitude of an object is monotonic between its transits and antitransits: these extrema are found first,
+/// and then each crossing is bracketed between two of them and found by bisection, so none can be missed.
+/// The horizon mask and the mount limits of the observer (with `limits`) are not monotonic:
+/// the segments are then also split every `LIMITS_STEP`.
+fn crossings(
+    object: &dyn SkyObject,
+    observer: &Observer,
+    start: f64,
+    end: f64,
+    horizon: Option<f64>,
+    rising: bool,
+    limits: bool,
+) -> Vec<f64> {
+    let height = |jd: f64| height(object, observer, jd, horizon, limits);
+
+    // the segments between consecutive extrema, where the altitude is monotonic
+    let mut bounds = vec![start, end];
+    for hour_angle in [0.0, -180.0] {
+        let mut jd = hour_angle_time(object, observer, start, hour_angle, true);
+        while jd < end {
+            bounds.push(jd);
+            jd = hour_angle_time(object, observer, jd + 0.5, hour_angle, true);
+        }
+    }
+    if limits && (observer.horizon_mask.is_some() || observer.mount_limits.is_some()) {
+        let n = ((end - start) / LIMITS_STEP).ceil() as usize;
+        bounds.extend((1..n).map(|i| start + i as f64 * LIMITS_STEP));
+    }
+    bounds.sort_by(f64::total_cmp);
+
+    let sign = if rising { 1.0 } else { -1.0 };
+    let mut times = Vec::new();
+    for segment in bounds.windows(2) {
+        let (mut low, mut high) = (segment[0], segment[1]);
+        if sign * height(low) >= 0.0 || sign * height(high) < 0.0 {
+            continue;
+        }
+        while (high - low) * 86400.0 > 0.5 {
+            let middle = (low + high) / 2.0;
+            if sign * height(middle) < 0.0 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        times.push(high);
+    }
+    times
+}
+
+/// Find the time when an object rises (`rising`) or sets, relative to a reference time
+/// (honoring the horizon mask and the mount limits of the observer with `limits`)
+pub(crate) fn horizon_crossing(
+    object: &dyn SkyObject,
+    observer: &Observer,
+    time: &Time,
+    mode: SearchMode,
+    horizon: Option<f64>,
+    rising: bool,
+    limits: bool,
+) -> HorizonCrossing {
+    let jd = time.to_jd();
+    let crossings = |start: f64, end: f64| crossings(object, observer, start, end, horizon, rising, limits);
+    let (mut next, mut previous) = (None, None);
+    // the crossings found in a span are closer than those of the next spans, so stop at the first one
+    let mut offset = 0.0;
+    while offset < object.search_window() && next.is_none() && previous.is_none() {
+        let step = SEARCH_STEP.min(object.search_window() - offset);
+        if mode != SearchMode::Previous {
+            next = crossings(jd + offset, jd + offset + step).into_iter().find(|&t| t >= jd);
+        }
+        if mode != SearchMode::Next {
+            previous = crossings(jd - offset - step, jd - offset).into_iter().rev().find(|&t| t < jd);
+        }
+        offset += step;
+    }
+    let found = match (previous, next) {
+        (Some(p), Some(n)) => Some(if jd - p < n - jd { p } else { n }),
+        (p, n) => p.or(n),
+    };
+    match found {
+        Some(t) => HorizonCrossing::At(Time::from_jd(t)),
+        None => {
+            if height(object, observer, jd, horizon, limits) >= 0.0 {
+                HorizonCrossing::AlwaysUp
+            } else {
+                HorizonCrossing::AlwaysDown
+            }
+        }
+    }
+}
+
+/// Find the time when the hour angle of an object is `hour_angle`, relative to a reference time
+pub(crate) fn hour_angle_event(object: &dyn SkyObject, observer: &Observer, time: &Time, mode: SearchMode, hour_angle: f64) -> Time {
+    let jd = time.to_jd();
+    let next = || hour_angle_time(object, observer, jd, hour_angle, true);
+    let previous = || hour_angle_time(object, observer, jd, hour_angle, false);
+    let found = match mode {
+        SearchMode::Next => next(),
+        SearchMode::Previous => previous(),
+        SearchMode::Nearest => {
+            let (n, p) = (next(), previous());
+            if jd - p < n - jd { p } else { n }
+        }
+    };
+    Time::from_jd(found)
+}
@@ -0,0 +1,266 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::healpix::{nside2npix, pix2ang, HealpixPixel};
+use crate::region::{Region, SkyPolygon};
+use crate::spatial::{offset_by, DEGRA};
+use crate::target::Target;
+
+/// Golden angle, in degrees
+const GOLDEN_ANGLE: f64 = 137.50776405003785;
+
+/// Sampler struct
+///
+/// This struct draws random positions uniformly distributed on the sphere, or within a region of it.
+/// It owns its random number generator, seeded explicitly, so that simulations are reproducible
+/// and independent samplers never share state.
+///
+/// # Methods
+///
+/// * `new` - Create a new Sampler from a seed
+/// * `uniform_sphere` - Draw targets uniformly on the whole sky
+/// * `uniform_cone` - Draw targets uniformly within a cone
+/// * `uniform_region` - Draw targets uniformly within any region
+/// * `uniform_polygon` - Draw targets uniformly within a polygon
+/// * `uniform_healpix_pixel` - Draw targets uniformly within a HEALPix pixel
+///
+/// # Examples
+///
+/// ```
+/// use flare::sampling::Sampler;
+///
+/// let mut sampler = Sampler::new(42);
+/// let targets = sampler.uniform_sphere(1000);
+/// assert_eq!(targets.len(), 1000);
+///
+/// // the same seed gives the same targets
+/// let again = Sampler::new(42).uniform_sphere(1000);
+/// assert!(targets.iter().zip(&again).all(|(a, b)| a.ra == b.ra && a.dec == b.dec));
+/// ```
+pub struct Sampler {
+    rng: StdRng,
+}
+
+impl Sampler {
+    /// Create a new Sampler
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed of the random number generator
+    ///
+    /// # Returns
+    ///
+    /// * `Sampler` - A new Sampler
+    pub fn new(seed: u64) -> Sampler {
+        Sampler { rng: StdRng::seed_from_u64(seed) }
+    }
+
+    /// Draw targets uniformly distributed on the whole sky
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of targets to draw
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Target<'static>>` - The targets
+    pub fn uniform_sphere(&mut self, n: usize) -> Vec<Target<'static>> {
+        (0..n)
+            .map(|_| {
+                let ra = 360.0 * self.rng.gen::<f64>();
+                let z: f64 = self.rng.gen_range(-1.0..=1.0);
+                Target::new(ra, z.asin() / DEGRA, None)
+            })
+            .collect()
+    }
+
+    /// Draw targets uniformly distributed within a cone (spherical cap)
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right Ascension of the center of the cone in degrees
+    /// * `dec` - Declination of the center of the cone in degrees
+    /// * `radius` - Radius of the cone in degrees
+    /// * `n` - Number of targets to draw
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Target<'static>>` - The targets
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sampling::Sampler;
+    /// use flare::spatial::great_circle_distance;
+    ///
+    /// let mut sampler = Sampler::new(1);
+    /// let targets = sampler.uniform_cone(359.9, 89.5, 1.0, 500);
+    /// assert!(targets.iter().all(|t| great_circle_distance(359.9, 89.5, t.ra, t.dec) <= 1.0 + 1e-9));
+    /// ```
+    pub fn uniform_cone(&mut self, ra: f64, dec: f64, radius: f64, n: usize) -> Vec<Target<'static>> {
+        let cos_radius = (radius.clamp(0.0, 180.0) * DEGRA).cos();
+        (0..n)
+            .map(|_| {
+                // uniform in solid angle: the cosine of the distance to the center is uniform
+                let cos_distance = 1.0 - self.rng.gen::<f64>() * (1.0 - cos_radius);
+                let pa = 360.0 * self.rng.gen::<f64>();
+                let (ra, dec) = offset_by(ra, dec, pa, cos_distance.clamp(-1.0, 1.0).acos() / DEGRA);
+                Target::new(ra, dec, None)
+            })
+            .collect()
+    }
+
+    /// Draw targets uniformly distributed within a region
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The region
+    /// * `n` - Number of targets to draw
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Target<'static>>` - The targets
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sampling::Sampler;
+    /// use flare::region::{Region, SkyAnnulus};
+    ///
+    /// let annulus = SkyAnnulus::new(10.0, -20.0, 1.0, 2.0);
+    /// let targets = Sampler::new(7).uniform_region(&annulus, 200);
+    /// assert!(targets.iter().all(|t| annulus.contains_target(t)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region has no area.
+    ///
+    /// # Notes
+    ///
+    /// Positions are drawn within the bounding cap of the region and rejected if outside of it,
+    /// so this is efficient as long as the region fills a good fraction of its bounding cap.
+    pub fn uniform_region(&mut self, region: &dyn Region, n: usize) -> Vec<Target<'static>> {
+        if region.area() <= 0.0 {
+            panic!("Cannot sample a region with no area");
+        }
+        let cap = region.bounding_cap();
+        let mut targets = Vec::with_capacity(n);
+        while targets.len() < n {
+            let batch = self.uniform_cone(cap.ra, cap.dec, cap.radius, n - targets.len());
+            targets.extend(batch.into_iter().filter(|t| region.contains(t.ra, t.dec)));
+        }
+        targets
+    }
+
+    /// Draw targets uniformly distributed within a polygon
+    ///
+    /// # Arguments
+    ///
+    /// * `polygon` - The polygon
+    /// * `n` - Number of targets to draw
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Target<'static>>` - The targets
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sampling::Sampler;
+    /// use flare::region::{Region, SkyPolygon};
+    ///
+    /// let polygon = SkyPolygon::new(&[(359.0, -1.0), (1.0, -1.0), (0.0, 1.0)]);
+    /// let targets = Sampler::new(3).uniform_polygon(&polygon, 100);
+    /// assert!(targets.iter().all(|t| polygon.contains(t.ra, t.dec)));
+    /// ```
+    pub fn uniform_polygon(&mut self, polygon: &SkyPolygon, n: usize) -> Vec<Target<'static>> {
+        self.uniform_region(polygon, n)
+    }
+
+    /// Draw targets uniformly distributed within a HEALPix pixel (NESTED scheme)
+    ///
+    /// # Arguments
+    ///
+    /// * `nside` - Resolution parameter of the map (a power of 2)
+    /// * `ipix` - Index of the pixel in the NESTED scheme
+    /// * `n` - Number of targets to draw
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Target<'static>>` - The targets
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::healpix::ang2pix;
+    /// use flare::sampling::Sampler;
+    ///
+    /// let targets = Sampler::new(5).uniform_healpix_pixel(64, 1234, 100);
+    /// assert!(targets.iter().all(|t| ang2pix(64, t.ra, t.dec) == 1234));
+    /// ```
+    pub fn uniform_healpix_pixel(&mut self, nside: u64, ipix: u64, n: usize) -> Vec<Target<'static>> {
+        self.uniform_region(&HealpixPixel::new(nside, ipix), n)
+    }
+}
+
+/// Generate a grid of targets covering the whole sky with a Fibonacci lattice
+///
+/// The points are nearly uniformly spaced, and each one represents the same area (4 pi / n steradians).
+///
+/// # Arguments
+///
+/// * `n` - Number of targets
+///
+/// # Returns
+///
+/// * `Vec<Target<'static>>` - The targets, from the North to the South pole
+///
+/// # Examples
+///
+/// ```
+/// use flare::sampling::fibonacci_grid;
+///
+/// let targets = fibonacci_grid(1000);
+/// assert_eq!(targets.len(), 1000);
+/// // as many points in both hemispheres
+/// assert_eq!(targets.iter().filter(|t| t.dec > 0.0).count(), 500);
+/// ```
+pub fn fibonacci_grid(n: usize) -> Vec<Target<'static>> {
+    (0..n)
+        .map(|i| {
+            let z = 1.0 - (2.0 * i as f64 + 1.0) / n as f64;
+            let ra = (i as f64 * GOLDEN_ANGLE).rem_euclid(360.0);
+            Target::new(ra, z.asin() / DEGRA, None)
+        })
+        .collect()
+}
+
+/// Generate a grid of targets at the centers of the pixels of a HEALPix map
+///
+/// # Arguments
+///
+/// * `nside` - Resolution parameter of the map (a power of 2)
+///
+/// # Returns
+///
+/// * `Vec<Target<'static>>` - The targets, in the order of the pixels in the NESTED scheme
+///
+/// # Examples
+///
+/// ```
+/// use flare::healpix::ang2pix;
+/// use flare::sampling::healpix_grid;
+///
+/// let targets = healpix_grid(8);
+/// assert_eq!(targets.len(), 768);
+/// assert_eq!(ang2pix(8, targets[100].ra, targets[100].dec), 100);
+/// ```
+pub fn healpix_grid(nside: u64) -> Vec<Target<'static>> {
+    (0..nside2npix(nside))
+        .map(|ipix| {
+            let (ra, dec) = pix2ang(nside, ipix);
+            Target::new(ra, dec, None)
+        })
+        .collect()
+}
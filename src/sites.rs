@@ -0,0 +1,550 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::observer::Observer;
+use crate::time::Time;
+
+/// SiteError enum
+///
+/// This enum represents the errors that can occur when reading sites from a TOML or JSON file.
+///
+/// # Variants
+///
+/// * `Io` - The file can't be read
+/// * `UnsupportedFormat` - The extension of the file is neither `.toml` nor `.json`
+/// * `Parse` - The file can't be parsed, or a site has a missing field or a field of the wrong type
+///   (with the message of the TOML or JSON parser)
+/// * `InvalidValue` - A field of a site is out of range (site key, field)
+#[derive(Debug, Clone, PartialEq)]
+pub enum SiteError {
+    Io(String),
+    UnsupportedFormat(String),
+    Parse(String),
+    InvalidValue(String, String),
+}
+
+impl std::fmt::Display for SiteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SiteError::Io(error) => write!(f, "Can't read the sites: {}", error),
+            SiteError::UnsupportedFormat(path) => write!(f, "Unsupported sites file (not .toml nor .json): {}", path),
+            SiteError::Parse(error) => write!(f, "Can't parse the sites: {}", error),
+            SiteError::InvalidValue(site, field) => write!(f, "Invalid value of {} for site {}", field, site),
+        }
+    }
+}
+
+impl std::error::Error for SiteError {}
+
+/// Built-in site: key, name, aliases, latitude, longitude, height, time zone, UTC offset
+type BuiltinSite = (&'static str, &'static str, &'static [&'static str], f64, f64, f64, &'static str, f64);
+
+/// The built-in sites, with their geodetic (WGS84) coordinates
+const BUILTIN_SITES: [BuiltinSite; 17] = [
+    ("palomar", "Palomar Observatory", &["p60", "p200", "hale"], 33.3563, -116.8648, 1706.0, "America/Los_Angeles", -8.0),
+    ("p48", "Palomar 48-inch (Samuel Oschin) Telescope", &["ztf", "oschin"], 33.3633675, -116.8361345, 1870.0, "America/Los_Angeles", -8.0),
+    ("rubin", "Vera C. Rubin Observatory", &["cerro pachon", "lsst", "gemini south", "soar"], -30.244639, -70.749417, 2663.0, "America/Santiago", -4.0),
+    ("kitt peak", "Kitt Peak National Observatory", &["kpno"], 31.9583, -111.5967, 2120.0, "America/Phoenix", -7.0),
+    ("mauna kea", "Mauna Kea Observatories", &["keck", "subaru", "cfht", "gemini north"], 19.8261, -155.4700, 4160.0, "Pacific/Honolulu", -10.0),
+    ("la silla", "La Silla Observatory", &["eso la silla"], -29.2567, -70.7300, 2347.0, "America/Santiago", -4.0),
+    ("paranal", "Paranal Observatory", &["vlt", "eso paranal"], -24.6272, -70.4042, 2635.0, "America/Santiago", -4.0),
+    ("siding spring", "Siding Spring Observatory", &["sso", "aat"], -31.2733, 149.0617, 1165.0, "Australia/Sydney", 10.0),
+    ("la palma", "Roque de los Muchachos Observatory", &["roque de los muchachos", "orm", "lapalma"], 28.7606, -17.8814, 2327.0, "Atlantic/Canary", 0.0),
+    ("cerro tololo", "Cerro Tololo Inter-American Observatory", &["ctio", "decam"], -30.1690, -70.8063, 2207.0, "America/Santiago", -4.0),
+    ("las campanas", "Las Campanas Observatory", &["lco", "magellan"], -29.0146, -70.6926, 2380.0, "America/Santiago", -4.0),
+    ("apache point", "Apache Point Observatory", &["apo", "sdss"], 32.7803, -105.8203, 2788.0, "America/Denver", -7.0),
+    ("mcdonald", "McDonald Observatory", &["hobby eberly", "het"], 30.6797, -104.0247, 2075.0, "America/Chicago", -6.0),
+    ("lick", "Lick Observatory", &["mount hamilton"], 37.3414, -121.6429, 1283.0, "America/Los_Angeles", -8.0),
+    ("haleakala", "Haleakala Observatory", &["atlas haleakala", "pan-starrs", "ps1"], 20.7082, -156.2571, 3055.0, "Pacific/Honolulu", -10.0),
+    ("sutherland", "South African Astronomical Observatory", &["saao", "salt"], -32.3794, 20.8117, 1798.0, "Africa/Johannesburg", 2.0),
+    ("calar alto", "Calar Alto Observatory", &["caha"], 37.2236, -2.5461, 2168.0, "Europe/Madrid", 1.0),
+];
+
+/// Normalize a site name for lookups: lowercase ASCII letters and digits only (accents are dropped)
+fn normalize(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' | 'Á' => 'a',
+            'é' | 'è' | 'ê' | 'ë' | 'É' => 'e',
+            'í' | 'ì' | 'î' | 'ï' | 'Í' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' | 'Ó' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' | 'Ú' => 'u',
+            'ñ' | 'Ñ' => 'n',
+            c => c.to_ascii_lowercase(),
+        })
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Find a built-in site by its key or one of its aliases
+fn find_builtin(name: &str) -> Option<&'static BuiltinSite> {
+    let name = normalize(name);
+    BUILTIN_SITES
+        .iter()
+        .find(|site| normalize(site.0) == name || site.2.iter().any(|alias| normalize(alias) == name))
+}
+
+/// Site struct
+///
+/// This struct represents an observatory site.
+///
+/// # Attributes
+///
+/// * `key` - Short name of the site, used to look it up (e.g. "palomar")
+/// * `name` - Full name of the site
+/// * `aliases` - Other names of the site (e.g. telescopes or surveys), also used to look it up
+/// * `lat` - Geodetic latitude of the site in degrees
+/// * `lon` - Longitude of the site in degrees (positive East)
+/// * `height` - Geodetic height of the site above the WGS84 ellipsoid in meters
+/// * `timezone` - IANA time zone of the site (e.g. "America/Los_Angeles")
+/// * `utc_offset` - Offset of the standard (winter) local time to UTC in hours
+///
+/// # Methods
+///
+/// * `builtin` - Get a built-in site by its name
+/// * `observer` - Create an Observer at the site
+/// * `local_time` - Convert a time to the standard local time of the site
+///
+/// # Examples
+///
+/// ```
+/// use flare::sites::Site;
+///
+/// let site = Site::builtin("Cerro Pachón").unwrap();
+/// assert_eq!(site.key, "rubin");
+/// assert_eq!(site.timezone, "America/Santiago");
+/// ```
+///
+/// # Notes
+///
+/// With the `serde` feature, a Site can be deserialized from a table (TOML) or an object (JSON) of its fields,
+/// without the key (see `SiteRegistry`).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Site {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub key: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub aliases: Vec<String>,
+    pub lat: f64,
+    pub lon: f64,
+    pub height: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_timezone"))]
+    pub timezone: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub utc_offset: f64,
+}
+
+/// Time zone of the sites loaded from files without one
+#[cfg(feature = "serde")]
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+impl Site {
+    /// Get a built-in site by its name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The key or an alias of the site, case insensitive, ignoring spaces, punctuation and accents
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Site>` - The site, or None if it is not a built-in site
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sites::Site;
+    ///
+    /// let site = Site::builtin("Kitt Peak").unwrap();
+    /// assert_eq!(site, Site::builtin("KPNO").unwrap());
+    /// assert_eq!(site.height, 2120.0);
+    /// assert!(Site::builtin("Tatooine").is_none());
+    /// ```
+    pub fn builtin(name: &str) -> Option<Site> {
+        find_builtin(name).map(|&(key, name, aliases, lat, lon, height, timezone, utc_offset)| Site {
+            key: key.to_string(),
+            name: name.to_string(),
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            lat,
+            lon,
+            height,
+            timezone: timezone.to_string(),
+            utc_offset,
+        })
+    }
+
+    /// Create an Observer at the site, named after it
+    ///
+    /// # Returns
+    ///
+    /// * `Observer` - An Observer at the coordinates and height of the site
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sites::Site;
+    ///
+    /// let site = Site::builtin("paranal").unwrap();
+    /// let observer = site.observer();
+    /// assert_eq!(observer.name, Some("Paranal Observatory"));
+    /// assert_eq!(observer.elevation, 2635.0);
+    /// ```
+    pub fn observer(&self) -> Observer<'_> {
+        Observer::new(self.lat, self.lon, self.height, Some(&self.name))
+    }
+
+    /// Convert a time to the standard local time of the site
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time object (in UTC)
+    ///
+    /// # Returns
+    ///
+    /// * `DateTime<FixedOffset>` - The local time, with the standard (winter) UTC offset of the site
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sites::Site;
+    /// use flare::Time;
+    ///
+    /// let site = Site::builtin("palomar").unwrap();
+    /// let local = site.local_time(&Time::new(2024, 9, 11, 3, 0, 0));
+    /// assert_eq!(local.to_string(), "2024-09-10 19:00:00 -08:00");
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// Daylight saving time is not applied: see `timezone` to handle it with a time zone database.
+    pub fn local_time(&self, time: &Time) -> DateTime<FixedOffset> {
+        let offset = FixedOffset::east_opt((self.utc_offset * 3600.0).round() as i32).expect("invalid UTC offset");
+        time.to_utc().with_timezone(&offset)
+    }
+
+    /// Check the ranges of the fields of a site loaded from a file
+    #[cfg(feature = "serde")]
+    fn validate(&self) -> Result<(), SiteError> {
+        let invalid = |name: &str| Err(SiteError::InvalidValue(self.key.clone(), name.to_string()));
+        if !(-90.0..=90.0).contains(&self.lat) {
+            return invalid("lat");
+        }
+        if !(-180.0..=360.0).contains(&self.lon) {
+            return invalid("lon");
+        }
+        if !self.height.is_finite() {
+            return invalid("height");
+        }
+        if !(-12.0..=14.0).contains(&self.utc_offset) {
+            return invalid("utc_offset");
+        }
+        Ok(())
+    }
+}
+
+/// SiteRegistry struct
+///
+/// This struct represents a collection of sites, looked up by key or alias: the built-in sites,
+/// and/or sites loaded from TOML or JSON files.
+///
+/// # Attributes
+///
+/// * `sites` - The sites of the registry
+///
+/// # Methods
+///
+/// * `builtin` - Get a registry with all the built-in sites
+/// * `from_toml` - Read sites from a TOML document (with the `serde` feature)
+/// * `from_json` - Read sites from a JSON document (with the `serde` feature)
+/// * `from_file` - Read sites from a TOML or JSON file (with the `serde` feature)
+/// * `extend` - Add the sites of another registry, replacing the sites with the same key
+/// * `get` - Get a site by its key or one of its aliases
+///
+/// # Examples
+///
+/// ```
+/// use flare::sites::{Site, SiteRegistry};
+///
+/// let mut registry = SiteRegistry::builtin();
+/// let wise = Site {
+///     key: "wise".to_string(),
+///     name: "Wise Observatory".to_string(),
+///     aliases: vec!["mitzpe ramon".to_string()],
+///     lat: 30.596,
+///     lon: 34.763,
+///     height: 875.0,
+///     timezone: "Asia/Jerusalem".to_string(),
+///     utc_offset: 2.0,
+/// };
+/// registry.extend(SiteRegistry { sites: vec![wise] });
+/// assert_eq!(registry.get("Mitzpe Ramon").unwrap().name, "Wise Observatory");
+/// assert_eq!(registry.get("paranal").unwrap().key, "paranal");
+/// ```
+///
+/// # Notes
+///
+/// In a TOML or JSON file, each site is a table (TOML) or an object (JSON) named after its key, with the fields
+/// `lat`, `lon` and `height` (in degrees and meters, required), and `name` (defaults to the key), `aliases`,
+/// `timezone` (defaults to "UTC") and `utc_offset` (defaults to 0) (optional):
+///
+/// ```toml
+/// # a comment
+/// [palomar]
+/// name = "Palomar Observatory"
+/// aliases = ["p60", "p200"]
+/// lat = 33.3563
+/// lon = -116.8648
+/// height = 1706.0
+/// timezone = "America/Los_Angeles"
+/// utc_offset = -8
+/// ```
+///
+/// ```json
+/// {"palomar": {"name": "Palomar Observatory", "lat": 33.3563, "lon": -116.8648, "height": 1706.0}}
+/// ```
+///
+/// Reading files needs the `serde` feature, which parses them with the `toml` and `serde_json` crates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteRegistry {
+    pub sites: Vec<Site>,
+}
+
+impl SiteRegistry {
+    /// Get a registry with all the built-in sites
+    ///
+    /// # Returns
+    ///
+    /// * `SiteRegistry` - The built-in sites: Palomar, the Palomar 48-inch (ZTF), Rubin (Cerro Pachón), Kitt Peak, Mauna Kea, La Silla, Paranal,
+    ///   Siding Spring, La Palma, Cerro Tololo, Las Campanas, Apache Point, McDonald, Lick, Haleakala, Sutherland
+    ///   and Calar Alto
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sites::SiteRegistry;
+    ///
+    /// let registry = SiteRegistry::builtin();
+    /// assert_eq!(registry.sites.len(), 17);
+    /// assert_eq!(registry.get("Mauna Kea").unwrap().height, 4160.0);
+    /// ```
+    pub fn builtin() -> SiteRegistry {
+        SiteRegistry { sites: BUILTIN_SITES.iter().filter_map(|site| Site::builtin(site.0)).collect() }
+    }
+
+    /// Read sites from a TOML document
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The TOML document (see the format in the notes of `SiteRegistry`)
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SiteRegistry, SiteError>` - The sites, or an error if the document can't be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sites::{SiteError, SiteRegistry};
+    ///
+    /// let text = "
+    /// [wise] # the Wise Observatory
+    /// name = \"Wise Observatory\"
+    /// aliases = [\"mitzpe ramon\"]
+    /// lat = 30.596
+    /// lon = 34.763
+    /// height = 875
+    /// timezone = \"Asia/Jerusalem\"
+    /// utc_offset = 2
+    /// ";
+    /// let registry = SiteRegistry::from_toml(text).unwrap();
+    /// let site = registry.get("Mitzpe Ramon").unwrap();
+    /// assert_eq!((site.lat, site.lon, site.height, site.utc_offset), (30.596, 34.763, 875.0, 2.0));
+    ///
+    /// let error = SiteRegistry::from_toml("[wise]\nlat = 30.596\nlon = 34.763\n").unwrap_err();
+    /// assert!(error.to_string().contains("missing field `height`"));
+    ///
+    /// let error = SiteRegistry::from_toml("[wise]\nlat = 130.596\nlon = 34.763\nheight = 875\n").unwrap_err();
+    /// assert_eq!(error, SiteError::InvalidValue("wise".to_string(), "lat".to_string()));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_toml(text: &str) -> Result<SiteRegistry, SiteError> {
+        let sites: Sites = toml::from_str(text).map_err(|error| SiteError::Parse(error.to_string()))?;
+        SiteRegistry::from_sites(sites)
+    }
+
+    /// Read sites from a JSON document
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The JSON document: an object of sites, named after their keys (see the notes of `SiteRegistry`)
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SiteRegistry, SiteError>` - The sites, or an error if the document can't be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sites::{SiteError, SiteRegistry};
+    ///
+    /// let text = r#"{
+    ///     "wise": {"name": "Wise Observatory", "lat": 30.596, "lon": 34.763, "height": 875, "timezone": "Asia/Jerusalem"},
+    ///     "ohp": {"name": "Observatoire de Haute-Provence", "lat": 43.9308, "lon": 5.7133, "height": 650}
+    /// }"#;
+    /// let registry = SiteRegistry::from_json(text).unwrap();
+    /// assert_eq!(registry.sites.len(), 2);
+    /// assert_eq!(registry.get("OHP").unwrap().timezone, "UTC");
+    ///
+    /// let error = SiteRegistry::from_json(r#"{"wise": {"lat": "north", "lon": 34.763, "height": 875}}"#).unwrap_err();
+    /// assert!(matches!(error, SiteError::Parse(_)));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_json(text: &str) -> Result<SiteRegistry, SiteError> {
+        let sites: Sites = serde_json::from_str(text).map_err(|error| SiteError::Parse(error.to_string()))?;
+        SiteRegistry::from_sites(sites)
+    }
+
+    /// Read sites from a TOML or JSON file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the file, with a `.toml` or `.json` extension
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SiteRegistry, SiteError>` - The sites, or an error if the file can't be read or parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::sites::SiteRegistry;
+    ///
+    /// let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/sites/example.toml");
+    /// let registry = SiteRegistry::from_file(path).unwrap();
+    /// assert!(registry.get("wise").is_some());
+    ///
+    /// let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/sites/example.json");
+    /// let registry = SiteRegistry::from_file(path).unwrap();
+    /// assert!(registry.get("wise").is_some());
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_file(path: &str) -> Result<SiteRegistry, SiteError> {
+        let read = || std::fs::read_to_string(path).map_err(|error| SiteError::Io(error.to_string()));
+        let lower = path.to_lowercase();
+        if lower.ends_with(".toml") {
+            SiteRegistry::from_toml(&read()?)
+        } else if lower.ends_with(".json") {
+            SiteRegistry::from_json(&read()?)
+        } else {
+            Err(SiteError::UnsupportedFormat(path.to_string()))
+        }
+    }
+
+    /// Check the sites read from a file, and name the sites without a name after their key
+    #[cfg(feature = "serde")]
+    fn from_sites(sites: Sites) -> Result<SiteRegistry, SiteError> {
+        let mut sites = sites.0;
+        for site in sites.iter_mut() {
+            site.validate()?;
+            if site.name.is_empty() {
+                site.name = site.key.clone();
+            }
+        }
+        Ok(SiteRegistry { sites })
+    }
+
+    /// Add the sites of another registry, replacing the sites with the same key
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other registry
+    pub fn extend(&mut self, other: SiteRegistry) {
+        for site in other.sites {
+            match self.sites.iter().position(|s| normalize(&s.key) == normalize(&site.key)) {
+                Some(i) => self.sites[i] = site,
+                None => self.sites.push(site),
+            }
+        }
+    }
+
+    /// Get a site by its key or one of its aliases
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The key or an alias of the site, case insensitive, ignoring spaces, punctuation and accents
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Site>` - The site, or None if it is not in the registry
+    pub fn get(&self, name: &str) -> Option<&Site> {
+        let name = normalize(name);
+        self.sites
+            .iter()
+            .find(|site| normalize(&site.key) == name)
+            .or_else(|| self.sites.iter().find(|site| site.aliases.iter().any(|alias| normalize(alias) == name)))
+    }
+}
+
+impl <'a> Observer<'a> {
+    /// Create an Observer at a built-in site
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The key or an alias of the site (see `sites::SiteRegistry::builtin`), case insensitive,
+    ///   ignoring spaces, punctuation and accents
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Observer>` - An Observer at the coordinates and height of the site, named after it,
+    ///   or None if it is not a built-in site (use `sites::SiteRegistry` for sites loaded from files)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::Observer;
+    ///
+    /// let observer = Observer::from_site("ZTF").unwrap();
+    /// assert_eq!(observer.name, Some("Palomar 48-inch (Samuel Oschin) Telescope"));
+    /// assert_eq!((observer.lat, observer.lon, observer.elevation), (33.3633675, -116.8361345, 1870.0));
+    ///
+    /// let observer = Observer::from_site("Cerro Pachón").unwrap();
+    /// assert_eq!(observer.name, Some("Vera C. Rubin Observatory"));
+    ///
+    /// assert!(Observer::from_site("Tatooine").is_none());
+    /// ```
+    pub fn from_site(name: &str) -> Option<Observer<'static>> {
+        find_builtin(name).map(|&(_, name, _, lat, lon, height, _, _)| Observer::new(lat, lon, height, Some(name)))
+    }
+}
+
+/// The sites of a TOML or JSON document, in the order of the document
+#[cfg(feature = "serde")]
+struct Sites(Vec<Site>);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Sites {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Sites, D::Error> {
+        struct SitesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SitesVisitor {
+            type Value = Sites;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a table of sites, named after their keys")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Sites, A::Error> {
+                let mut sites = Vec::new();
+                while let Some((key, site)) = map.next_entry::<String, Site>()? {
+                    sites.push(Site { key, ..site });
+                }
+                Ok(Sites(sites))
+            }
+        }
+
+        deserializer.deserialize_map(SitesVisitor)
+    }
+}
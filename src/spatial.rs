@@ -1,6 +1,8 @@
+use crate::vector::{Rotation3, UnitVector};
+
 pub const DEGRA: f64 = std::f64::consts::PI / 180.0;
 
-const RGE: [[f64; 3]; 3] = [
+pub(crate) const RGE: [[f64; 3]; 3] = [
     [-0.054875539, -0.873437105, -0.483834992],
     [0.494109454, -0.444829594, 0.746982249],
     [-0.867666136, -0.198076390, 0.455983795],
@@ -30,27 +32,99 @@ const RGE: [[f64; 3]; 3] = [
 /// println!("Galactic longitude: {}, Galactic latitude: {}", l, b);
 /// ```
 pub fn radec2lb(ra: f64, dec: f64) -> (f64, f64) {
-    let ra_rad = ra.to_radians();
-    let dec_rad = dec.to_radians();
-    let u = vec![
-        ra_rad.cos() * dec_rad.cos(),
-        ra_rad.sin() * dec_rad.cos(),
-        dec_rad.sin(),
-    ];
-    // next do a dot product of RGE and u
-    let ug = vec![
-        RGE[0][0] * u[0] + RGE[0][1] * u[1] + RGE[0][2] * u[2],
-        RGE[1][0] * u[0] + RGE[1][1] * u[1] + RGE[1][2] * u[2],
-        RGE[2][0] * u[0] + RGE[2][1] * u[1] + RGE[2][2] * u[2],
-    ];
-    let x = ug[0];
-    let y = ug[1];
-    let z = ug[2];
-    let galactic_l = y.atan2(x);
-    let galactic_b = z.atan2((x * x + y * y).sqrt());
+    let ug = Rotation3::icrs_to_galactic().apply(&UnitVector::from_radec(ra, dec)).cartesian();
+    let galactic_l = ug.y.atan2(ug.x);
+    let galactic_b = ug.z.atan2((ug.x * ug.x + ug.y * ug.y).sqrt());
     (galactic_l.to_degrees(), galactic_b.to_degrees())
 }
 
+/// Convert Galactic coordinates to RA and DEC
+/// 
+/// # Arguments
+/// 
+/// * `l` - Galactic longitude in degrees
+/// * `b` - Galactic latitude in degrees
+/// 
+/// # Returns
+/// 
+/// * `(f64, f64)` - Tuple containing Right Ascension (in [0, 360)) and Declination in degrees
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::{lb2radec, radec2lb};
+/// 
+/// // the Galactic center
+/// let (ra, dec) = lb2radec(0.0, 0.0);
+/// assert!((ra - 266.404996).abs() < 1e-5);
+/// assert!((dec + 28.936172).abs() < 1e-5);
+/// 
+/// let (l, b) = radec2lb(45.0, 45.0);
+/// let (ra, dec) = lb2radec(l, b);
+/// assert!((ra - 45.0).abs() < 1e-6 && (dec - 45.0).abs() < 1e-6);
+/// ```
+pub fn lb2radec(l: f64, b: f64) -> (f64, f64) {
+    Rotation3::icrs_to_galactic().inverse().apply_radec(l, b)
+}
+
+/// Convert lists of RA and DEC to Galactic coordinates
+/// 
+/// # Arguments
+/// 
+/// * `ras` - Right Ascensions in degrees
+/// * `decs` - Declinations in degrees
+/// 
+/// # Returns
+/// 
+/// * `Vec<(f64, f64)>` - Galactic longitudes and latitudes in degrees, in the same order as the inputs
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::{radec2lb, radec2lb_many};
+/// 
+/// let lbs = radec2lb_many(&[45.0, 120.0], &[45.0, -30.0]);
+/// assert_eq!(lbs[0], radec2lb(45.0, 45.0));
+/// assert_eq!(lbs[1], radec2lb(120.0, -30.0));
+/// ```
+/// 
+/// # Panics
+/// 
+/// Panics if `ras` and `decs` have different lengths.
+pub fn radec2lb_many(ras: &[f64], decs: &[f64]) -> Vec<(f64, f64)> {
+    assert_eq!(ras.len(), decs.len(), "ras and decs must have the same length");
+    map_indices(ras.len(), |i| radec2lb(ras[i], decs[i]))
+}
+
+/// Convert lists of Galactic coordinates to RA and DEC
+/// 
+/// # Arguments
+/// 
+/// * `ls` - Galactic longitudes in degrees
+/// * `bs` - Galactic latitudes in degrees
+/// 
+/// # Returns
+/// 
+/// * `Vec<(f64, f64)>` - Right Ascensions and Declinations in degrees, in the same order as the inputs
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::{lb2radec, lb2radec_many};
+/// 
+/// let radecs = lb2radec_many(&[0.0, 90.0], &[0.0, 10.0]);
+/// assert_eq!(radecs[0], lb2radec(0.0, 0.0));
+/// assert_eq!(radecs[1], lb2radec(90.0, 10.0));
+/// ```
+/// 
+/// # Panics
+/// 
+/// Panics if `ls` and `bs` have different lengths.
+pub fn lb2radec_many(ls: &[f64], bs: &[f64]) -> Vec<(f64, f64)> {
+    assert_eq!(ls.len(), bs.len(), "ls and bs must have the same length");
+    map_indices(ls.len(), |i| lb2radec(ls[i], bs[i]))
+}
+
 /// Convert degrees to hours, minutes, and seconds
 /// 
 /// # Arguments
@@ -159,6 +233,98 @@ pub fn great_circle_distance(ra1_deg: f64, dec1_deg: f64, ra2_deg: f64, dec2_deg
     distance * 180.0 / std::f64::consts::PI
 }
 
+/// Calculate the great circle distances between a point and a list of other points
+/// 
+/// # Arguments
+/// 
+/// * `ra_deg` - Right Ascension of the reference point in degrees
+/// * `dec_deg` - Declination of the reference point in degrees
+/// * `ras_deg` - Right Ascensions of the other points in degrees
+/// * `decs_deg` - Declinations of the other points in degrees
+/// 
+/// # Returns
+/// 
+/// * `Vec<f64>` - Great circle distances in degrees, in the same order as the other points
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::{great_circle_distance, separations};
+/// 
+/// let distances = separations(45.0, 45.0, &[46.0, 45.0], &[46.0, 47.0]);
+/// assert_eq!(distances, vec![great_circle_distance(45.0, 45.0, 46.0, 46.0), great_circle_distance(45.0, 45.0, 45.0, 47.0)]);
+/// ```
+/// 
+/// # Panics
+/// 
+/// Panics if `ras_deg` and `decs_deg` have different lengths.
+pub fn separations(ra_deg: f64, dec_deg: f64, ras_deg: &[f64], decs_deg: &[f64]) -> Vec<f64> {
+    assert_eq!(ras_deg.len(), decs_deg.len(), "ras and decs must have the same length");
+    map_indices(ras_deg.len(), |i| great_circle_distance(ra_deg, dec_deg, ras_deg[i], decs_deg[i]))
+}
+
+/// Crossmatch a list of positions against a catalog, finding the closest catalog source within a radius
+/// 
+/// # Arguments
+/// 
+/// * `ras_deg` - Right Ascensions of the positions to match (e.g. a batch of alerts) in degrees
+/// * `decs_deg` - Declinations of the positions to match in degrees
+/// * `catalog_ras_deg` - Right Ascensions of the catalog sources in degrees
+/// * `catalog_decs_deg` - Declinations of the catalog sources in degrees
+/// * `radius_deg` - Maximum matching distance in degrees
+/// 
+/// # Returns
+/// 
+/// * `Vec<Option<(usize, f64)>>` - For each position, the index of the closest catalog source and its distance in degrees,
+///   or `None` if no source is within the radius
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::crossmatch;
+/// 
+/// let catalog_ras = [10.0, 10.001, 200.0];
+/// let catalog_decs = [-5.0, -5.0, 30.0];
+/// let matches = crossmatch(&[10.0008, 100.0], &[-5.0, 0.0], &catalog_ras, &catalog_decs, 2.0 / 3600.0);
+/// assert_eq!(matches[0].map(|(index, _)| index), Some(1));
+/// assert!(matches[1].is_none());
+/// ```
+/// 
+/// # Panics
+/// 
+/// Panics if the RA and DEC slices of the positions or of the catalog have different lengths.
+/// 
+/// # Notes
+/// 
+/// This compares every position to every catalog source, which is fine for alert batches against
+/// catalogs of up to a few hundred thousand sources; pre-select the catalog (e.g. with a cone search) for larger ones.
+pub fn crossmatch(
+    ras_deg: &[f64],
+    decs_deg: &[f64],
+    catalog_ras_deg: &[f64],
+    catalog_decs_deg: &[f64],
+    radius_deg: f64,
+) -> Vec<Option<(usize, f64)>> {
+    assert_eq!(ras_deg.len(), decs_deg.len(), "ras and decs must have the same length");
+    assert_eq!(catalog_ras_deg.len(), catalog_decs_deg.len(), "catalog ras and decs must have the same length");
+    let catalog = catalog_ras_deg
+        .iter()
+        .zip(catalog_decs_deg)
+        .map(|(ra, dec)| UnitVector::from_radec(*ra, *dec))
+        .collect::<Vec<UnitVector>>();
+    let cos_radius = (radius_deg * DEGRA).cos();
+    map_indices(ras_deg.len(), |i| {
+        let v = UnitVector::from_radec(ras_deg[i], decs_deg[i]);
+        catalog
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| v.dot(w) >= cos_radius)
+            .map(|(index, w)| (index, v.angle(w)))
+            .filter(|(_, distance)| *distance <= radius_deg)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    })
+}
+
 /// Determine if a point is within an ellipse
 /// 
 /// # Arguments
@@ -194,6 +360,11 @@ pub fn great_circle_distance(ra1_deg: f64, dec1_deg: f64, ra2_deg: f64, dec2_deg
 /// let inside = in_ellipse(alpha, delta0, alpha1, delta01, d0, axis_ratio, pao);
 /// assert_eq!(inside, true);
 /// ```
+/// 
+/// # Notes
+/// 
+/// See `region::SkyEllipse` for a typed ellipse, which also gives the normalized elliptical distance
+/// of a position (e.g. for host galaxy association, see `host::rank_hosts`).
 pub fn in_ellipse(
     alpha: f64,
     delta0: f64,
@@ -270,6 +441,155 @@ pub fn in_ellipse(
     let t61 = e * e;
     let t63 = t60 * t61 + t8 + t57 - t4 - t7 + t56 + t36;
 
-    let inside = t63 > 0.0;
-    inside
+    t63 > 0.0
+}
+
+/// Determine which points of a list are within an ellipse
+/// 
+/// # Arguments
+/// 
+/// * `alphas` - Right Ascensions of the points in degrees
+/// * `deltas` - Declinations of the points in degrees
+/// * `alpha1` - Right Ascension of the center of the ellipse in degrees
+/// * `delta01` - Declination of the center of the ellipse in degrees
+/// * `d0` - Distance from the center of the ellipse to the edge in degrees
+/// * `axis_ratio` - Ratio of the minor axis to the major axis
+/// * `pao` - Position angle of the minor axis in degrees
+/// 
+/// # Returns
+/// 
+/// * `Vec<bool>` - For each point, true if it is within the ellipse, false otherwise
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::in_ellipse_many;
+/// 
+/// let inside = in_ellipse_many(&[45.0, 46.0, 50.0], &[45.0, 46.5, 46.0], 46.0, 46.0, 1.23, 1.0, 0.0);
+/// assert_eq!(inside, vec![true, true, false]);
+/// ```
+/// 
+/// # Panics
+/// 
+/// Panics if `alphas` and `deltas` have different lengths.
+pub fn in_ellipse_many(
+    alphas: &[f64],
+    deltas: &[f64],
+    alpha1: f64,
+    delta01: f64,
+    d0: f64,
+    axis_ratio: f64,
+    pao: f64,
+) -> Vec<bool> {
+    assert_eq!(alphas.len(), deltas.len(), "alphas and deltas must have the same length");
+    map_indices(alphas.len(), |i| in_ellipse(alphas[i], deltas[i], alpha1, delta01, d0, axis_ratio, pao))
+}
+
+/// Calculate the position angle of a point with respect to another, measured East of North
+/// 
+/// # Arguments
+/// 
+/// * `ra1_deg` - Right Ascension of the reference point in degrees
+/// * `dec1_deg` - Declination of the reference point in degrees
+/// * `ra2_deg` - Right Ascension of the other point in degrees
+/// * `dec2_deg` - Declination of the other point in degrees
+/// 
+/// # Returns
+/// 
+/// * `f64` - Position angle of the second point, as seen from the first one, in degrees in [0, 360)
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::position_angle;
+/// 
+/// // a point due North has a position angle of 0 degrees
+/// let pa = position_angle(45.0, 45.0, 45.0, 46.0);
+/// assert!(pa.abs() < 1e-9);
+/// 
+/// // a point due East (larger RA) has a position angle of 90 degrees
+/// let pa = position_angle(45.0, 0.0, 46.0, 0.0);
+/// assert!((pa - 90.0).abs() < 1e-9);
+/// ```
+pub fn position_angle(ra1_deg: f64, dec1_deg: f64, ra2_deg: f64, dec2_deg: f64) -> f64 {
+    let dec1 = dec1_deg * DEGRA;
+    let dec2 = dec2_deg * DEGRA;
+    let delta_ra = (ra2_deg - ra1_deg) * DEGRA;
+    let pa = (delta_ra.sin() * dec2.cos())
+        .atan2(dec1.cos() * dec2.sin() - dec1.sin() * dec2.cos() * delta_ra.cos());
+    (pa / DEGRA).rem_euclid(360.0)
+}
+
+/// Calculate the position of a point offset from another by a given angular distance
+///     along a given position angle
+/// 
+/// # Arguments
+/// 
+/// * `ra_deg` - Right Ascension of the starting point in degrees
+/// * `dec_deg` - Declination of the starting point in degrees
+/// * `pa_deg` - Position angle of the offset (East of North) in degrees
+/// * `distance_deg` - Angular distance of the offset in degrees
+/// 
+/// # Returns
+/// 
+/// * `(f64, f64)` - Tuple containing the Right Ascension (in [0, 360)) and Declination of the new point in degrees
+/// 
+/// # Examples
+/// 
+/// ```
+/// use flare::spatial::{offset_by, great_circle_distance, position_angle};
+/// 
+/// let (ra, dec) = offset_by(359.5, 10.0, 90.0, 1.0);
+/// assert!((great_circle_distance(359.5, 10.0, ra, dec) - 1.0).abs() < 1e-9);
+/// assert!((position_angle(359.5, 10.0, ra, dec) - 90.0).abs() < 1e-9);
+/// assert!(ra < 1.0); // wrapped around RA = 0
+/// ```
+pub fn offset_by(ra_deg: f64, dec_deg: f64, pa_deg: f64, distance_deg: f64) -> (f64, f64) {
+    let dec = dec_deg * DEGRA;
+    let pa = pa_deg * DEGRA;
+    let d = distance_deg * DEGRA;
+    let dec2 = (dec.sin() * d.cos() + dec.cos() * d.sin() * pa.cos()).clamp(-1.0, 1.0).asin();
+    let delta_ra = (pa.sin() * d.sin() * dec.cos()).atan2(d.cos() - dec.sin() * dec2.sin());
+    ((ra_deg + delta_ra / DEGRA).rem_euclid(360.0), dec2 / DEGRA)
+}
+
+/// Project a position onto the plane tangent to the sphere at a reference position (gnomonic projection)
+/// 
+/// Returns the standard coordinates (xi towards the East, eta towards the North) in degrees,
+/// or `None` if the position is more than 90 degrees away from the reference position.
+pub(crate) fn sky2tangent(ra0: f64, dec0: f64, ra: f64, dec: f64) -> Option<(f64, f64)> {
+    let dec0 = dec0 * DEGRA;
+    let dec = dec * DEGRA;
+    let delta_ra = (ra - ra0) * DEGRA;
+    let cos_c = dec0.sin() * dec.sin() + dec0.cos() * dec.cos() * delta_ra.cos();
+    if cos_c <= 0.0 {
+        return None;
+    }
+    let xi = dec.cos() * delta_ra.sin() / cos_c;
+    let eta = (dec0.cos() * dec.sin() - dec0.sin() * dec.cos() * delta_ra.cos()) / cos_c;
+    Some((xi / DEGRA, eta / DEGRA))
+}
+
+/// Inverse of `sky2tangent`: convert standard coordinates (in degrees) on the plane tangent
+/// at a reference position back to RA and DEC in degrees
+pub(crate) fn tangent2sky(ra0: f64, dec0: f64, xi: f64, eta: f64) -> (f64, f64) {
+    let xi = xi * DEGRA;
+    let eta = eta * DEGRA;
+    let distance = (xi * xi + eta * eta).sqrt().atan() / DEGRA;
+    let pa = xi.atan2(eta) / DEGRA;
+    offset_by(ra0, dec0, pa, distance)
+}
+
+/// Evaluate a function for each index in 0..n and collect the results in order,
+/// in parallel over all cores if the `rayon` feature is enabled
+pub(crate) fn map_indices<U: Send>(n: usize, f: impl Fn(usize) -> U + Sync + Send) -> Vec<U> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        (0..n).into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        (0..n).map(f).collect()
+    }
 }
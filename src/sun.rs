@@ -0,0 +1,194 @@
+use crate::ecliptic::{centuries, ecliptic2radec, nutation, true_obliquity, EclipticFrame};
+use crate::observer::Observer;
+use crate::target::SkyObject;
+use crate::spatial::DEGRA;
+use crate::time::Time;
+use crate::vector::{Cartesian3, UnitVector};
+
+/// Astronomical unit, in km
+pub const AU: f64 = 149597870.7;
+
+/// Constant of aberration, in degrees
+const ABERRATION: f64 = 20.4898 / 3600.0;
+
+/// Sun struct
+///
+/// This struct represents the Sun as a `SkyObject`, e.g. to compute its rise & set times with `rise_set`.
+/// Its position is its apparent topocentric position (`topocentric_position`),
+/// and it rises and sets when its upper limb is on the apparent horizon (at a geometric altitude of -50').
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sun;
+
+impl SkyObject for Sun {
+    fn radec(&self, observer: &Observer, time: &Time) -> (f64, f64) {
+        let sun = topocentric_position(observer, time);
+        (sun.ra, sun.dec)
+    }
+
+    fn horizon_altitude(&self, _observer: &Observer, _time: &Time) -> f64 {
+        -50.0 / 60.0
+    }
+}
+
+/// SunPosition struct
+///
+/// This struct represents the position of the Sun.
+///
+/// # Attributes
+///
+/// * `ra` - Right Ascension in degrees, in [0, 360)
+/// * `dec` - Declination in degrees
+/// * `distance` - Distance to the Sun in astronomical units
+#[derive(Debug, Clone, PartialEq)]
+pub struct SunPosition {
+    pub ra: f64,
+    pub dec: f64,
+    pub distance: f64,
+}
+
+/// Apparent ecliptic longitude of the Sun (true equinox of date) in degrees, and its distance in AU
+pub(crate) fn apparent_longitude(time: &Time) -> (f64, f64) {
+    let t = centuries(time);
+    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+    let m = 357.52911 + 35999.05029 * t - 0.0001537 * t * t;
+    let e = 0.016708634 - 0.000042037 * t - 0.0000001267 * t * t;
+    let m_rad = m * DEGRA;
+    let c = (1.914602 - 0.004817 * t - 0.000014 * t * t) * m_rad.sin()
+        + (0.019993 - 0.000101 * t) * (2.0 * m_rad).sin()
+        + 0.000289 * (3.0 * m_rad).sin();
+    let distance = 1.000001018 * (1.0 - e * e) / (1.0 + e * ((m + c) * DEGRA).cos());
+    let lon = l0 + c + nutation(time).0 - ABERRATION / distance;
+    (lon.rem_euclid(360.0), distance)
+}
+
+/// Calculate the apparent geocentric position of the Sun at a given time
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `SunPosition` - The position of the Sun, in the true equator and equinox of date
+///
+/// # Examples
+///
+/// ```
+/// use flare::sun::position;
+/// use flare::Time;
+///
+/// // example 25.a of Meeus
+/// let sun = position(&Time::new(1992, 10, 13, 0, 0, 0));
+/// assert!((sun.ra - 198.38083).abs() < 0.01);
+/// assert!((sun.dec + 7.78507).abs() < 0.01);
+/// assert!((sun.distance - 0.99766).abs() < 1e-4);
+/// ```
+///
+/// # Notes
+///
+/// This uses the low accuracy theory of chapter 25 of "Astronomical Algorithms" 2nd edition by Jean Meeus
+/// (Willmann-Bell, Richmond) 1998, accurate to about 0.01 degree in position and 1e-5 AU in distance.
+/// The difference between TT and UTC (about a minute) is neglected: the Sun moves by 0.001 degree in a minute.
+pub fn position(time: &Time) -> SunPosition {
+    let (lon, distance) = apparent_longitude(time);
+    let obliquity = true_obliquity(time) * DEGRA;
+    let lon = lon * DEGRA;
+    let ra = (obliquity.cos() * lon.sin()).atan2(lon.cos()) / DEGRA;
+    let dec = (obliquity.sin() * lon.sin()).asin() / DEGRA;
+    SunPosition { ra: ra.rem_euclid(360.0), dec, distance }
+}
+
+/// Calculate the geocentric position of the Sun at a given time, in J2000 coordinates
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `SunPosition` - The position of the Sun, in the J2000 equator and equinox
+///
+/// # Examples
+///
+/// ```
+/// use flare::sun::position_j2000;
+/// use flare::Time;
+///
+/// // close to the March equinox, the Sun is close to RA = 0
+/// let sun = position_j2000(&Time::new(2000, 3, 20, 7, 35, 0));
+/// assert!(sun.ra > 359.9 || sun.ra < 0.1);
+/// assert!(sun.dec.abs() < 0.05);
+/// ```
+///
+/// # Notes
+///
+/// Use this position to compare with catalog or alert coordinates, e.g. for the solar elongation.
+pub fn position_j2000(time: &Time) -> SunPosition {
+    let (lon, distance) = apparent_longitude(time);
+    let (ra, dec) = ecliptic2radec(lon, 0.0, &EclipticFrame::TrueOfDate(time.clone()));
+    SunPosition { ra, dec, distance }
+}
+
+/// Calculate the apparent topocentric position of the Sun, seen by an observer at a given time
+///
+/// # Arguments
+///
+/// * `observer` - Observer object representing the observer
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `SunPosition` - The position of the Sun seen by the observer, in the true equator and equinox of date
+///
+/// # Examples
+///
+/// ```
+/// use flare::sun::{position, topocentric_position};
+/// use flare::spatial::great_circle_distance;
+/// use flare::{Observer, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let time = Time::new(2024, 9, 10, 20, 0, 0);
+///
+/// // the parallax of the Sun is at most 8.8"
+/// let geocentric = position(&time);
+/// let topocentric = topocentric_position(&observer, &time);
+/// let parallax = great_circle_distance(geocentric.ra, geocentric.dec, topocentric.ra, topocentric.dec);
+/// assert!(parallax > 0.0 && parallax < 8.8 / 3600.0);
+/// ```
+pub fn topocentric_position(observer: &Observer, time: &Time) -> SunPosition {
+    let sun = position(time);
+    let geocentric = UnitVector::from_radec(sun.ra, sun.dec).cartesian() * (sun.distance * AU);
+    let topocentric = geocentric - observer.geocentric_position(time);
+    to_position(&topocentric)
+}
+
+/// Calculate the distance between the centers of the Earth and of the Sun at a given time
+///
+/// # Arguments
+///
+/// * `time` - Time object
+///
+/// # Returns
+///
+/// * `f64` - The distance in astronomical units
+///
+/// # Examples
+///
+/// ```
+/// use flare::sun::distance;
+/// use flare::Time;
+///
+/// // the Earth is at perihelion in early January, and at aphelion in early July
+/// assert!(distance(&Time::new(2024, 1, 3, 0, 0, 0)) < 0.984);
+/// assert!(distance(&Time::new(2024, 7, 5, 0, 0, 0)) > 1.016);
+/// ```
+pub fn distance(time: &Time) -> f64 {
+    apparent_longitude(time).1
+}
+
+/// Convert a geocentric or topocentric vector (in km) to a SunPosition
+fn to_position(v: &Cartesian3) -> SunPosition {
+    let (ra, dec) = v.direction().expect("the Sun is not at the observer").to_radec();
+    SunPosition { ra, dec, distance: v.norm() / AU }
+}
@@ -1,7 +1,12 @@
+use crate::airmass::AirmassModel;
 use crate::observer::Observer;
-use crate::spatial::{DEGRA, great_circle_distance, radec2lb, deg2dms, deg2hms};
+use crate::spatial::{great_circle_distance, radec2lb, lb2radec, deg2dms, deg2hms, map_indices};
+use crate::galactic::{center_separation, plane_distance};
 use crate::time::Time;
-use crate::corrections::refraction;
+use crate::corrections::{refraction, Atmosphere};
+use crate::altaz::{hadec2altaz, normalize_hour_angle, parallactic_angle, AltAz};
+use crate::ecliptic::{radec2ecliptic, solar_elongation, true_equator_rotation, EclipticFrame};
+use crate::moon::topocentric_position;
 
 /// Target struct
 /// 
@@ -16,13 +21,26 @@ use crate::corrections::refraction;
 /// # Methods
 /// 
 /// * `new` - Create a new Target
+/// * `from_galactic` - Create a new Target from Galactic coordinates
 /// * `altitude` - Calculate the altitude of the target at a given time
+/// * `altaz` - Calculate the altitude and azimuth of the target at a given time
+/// * `altaz_with_atmosphere` - Calculate the altitude and azimuth of the target at a given time, for given atmospheric conditions
+/// * `zenith_distance` - Calculate the zenith distance of the target at a given time
+/// * `hour_angle` - Calculate the hour angle of the target at a given time
+/// * `parallactic_angle` - Calculate the parallactic angle of the target at a given time
 /// * `airmass` - Calculate the airmass of the target at a given time
+/// * `airmass_with_model` - Calculate the airmass of the target at a given time, with a given airmass model
 /// * `separation` - Calculate the separation to another target
 /// * `separations` - Calculate the separations to a list of other targets
 /// * `to_string` - Convert the target to a string
 /// * `radec2hmsdms` - Convert the target to a tuple of strings with RA and DEC in HMS and DMS format
 /// * `radec2lb` - Compute the Galactic coordinates of the target
+/// * `galactic_plane_distance` - Calculate the angular distance between the target and the Galactic plane
+/// * `galactic_center_separation` - Calculate the angular separation between the target and the Galactic center
+/// * `ecliptic` - Compute the ecliptic coordinates (J2000) of the target
+/// * `ecliptic_in_frame` - Compute the ecliptic coordinates of the target in a given ecliptic frame
+/// * `solar_elongation` - Calculate the angular distance between the target and the Sun at a given time
+/// * `moon_separation` - Calculate the angular distance between the target and the Moon, seen by an observer at a given time
 /// 
 /// # Examples
 /// 
@@ -72,7 +90,7 @@ impl <'a> Target<'a> {
     /// assert_eq!(target.name, None);
     /// println!("{}", target.to_string());
     /// ```
-    pub fn new(ra: f64, dec: f64, name: Option<&str>) -> Target {
+    pub fn new(ra: f64, dec: f64, name: Option<&str>) -> Target<'_> {
         Target { name, ra, dec }
     }
 
@@ -98,7 +116,7 @@ impl <'a> Target<'a> {
     /// 
     /// let alt = target.altitude(&observer, &time);
     /// println!("Altitude: {}", alt);
-    /// assert!((alt - 42.893915).abs() < 1e-6);
+    /// assert!((alt - 42.689144).abs() < 1e-6);
     /// ```
     /// 
     /// # Notes
@@ -106,14 +124,167 @@ impl <'a> Target<'a> {
     /// This altitude calculation is quite simple and does not take into account refraction or other atmospheric effects.
     /// For a more accurate calculation, consider using another dedicated library.
     pub fn altitude(&self, observer: &Observer, time: &Time) -> f64 {
-        let (ra, dec) = (self.ra, self.dec);
-    
-        let ha = ((observer.local_sidereal_time(time) - ra) % 360.0) * DEGRA;
-        let lat = observer.lat * DEGRA;
-        let dec = dec * DEGRA;
-    
-        let alt = (dec.sin() * lat.sin() + dec.cos() * lat.cos() * ha.cos()).asin() / DEGRA;
-        alt + refraction(alt)
+        self.altaz(observer, time).alt
+    }
+
+    /// Calculate the altitude and azimuth of the target at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time at which to calculate the position
+    /// 
+    /// # Returns
+    /// 
+    /// * `AltAz` - The apparent altitude (corrected for refraction) and the azimuth (East of North) of the target in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// let altaz = target.altaz(&observer, &time);
+    /// assert!((altaz.alt - 42.689144).abs() < 1e-6);
+    /// // the target is rising, in the East
+    /// assert!(altaz.az > 0.0 && altaz.az < 180.0);
+    /// ```
+    pub fn altaz(&self, observer: &Observer, time: &Time) -> AltAz {
+        let (ha, dec) = self.hadec(observer, time);
+        let (alt, az) = hadec2altaz(ha, dec, observer.lat);
+        AltAz::new(alt + refraction(alt), az)
+    }
+
+    /// Calculate the altitude and azimuth of the target at a given time, for given atmospheric conditions
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time at which to calculate the position
+    /// * `atmosphere` - Atmospheric conditions at the observer, used to compute the refraction
+    /// 
+    /// # Returns
+    /// 
+    /// * `AltAz` - The apparent altitude and the azimuth (East of North) of the target in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// // in the r band, on a cool night
+    /// let atmosphere = observer.atmosphere(5.0, 0.3, 0.62);
+    /// let altaz = target.altaz_with_atmosphere(&observer, &time, &atmosphere);
+    /// // the refraction is smaller at altitude than with the standard conditions at sea level
+    /// assert!(altaz.alt < target.altitude(&observer, &time));
+    /// assert!((altaz.alt - 42.6850).abs() < 1e-4);
+    /// ```
+    pub fn altaz_with_atmosphere(&self, observer: &Observer, time: &Time, atmosphere: &Atmosphere) -> AltAz {
+        let (ha, dec) = self.hadec(observer, time);
+        let (alt, az) = hadec2altaz(ha, dec, observer.lat);
+        AltAz::new(atmosphere.apparent_altitude(alt), az)
+    }
+
+    /// Calculate the zenith distance of the target at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time at which to calculate the zenith distance
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The apparent zenith distance of the target in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// let zd = target.zenith_distance(&observer, &time);
+    /// assert!((zd - (90.0 - 42.689144)).abs() < 1e-6);
+    /// ```
+    pub fn zenith_distance(&self, observer: &Observer, time: &Time) -> f64 {
+        self.altaz(observer, time).zenith_distance()
+    }
+
+    /// Calculate the hour angle of the target at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time at which to calculate the hour angle
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The hour angle of the target in degrees, in [-180, 180), negative East of the meridian
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// let ha = target.hour_angle(&observer, &time);
+    /// assert!((ha + 51.603317).abs() < 1e-6);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// The hour angle is the local sidereal time minus the RA of the target precessed to the date,
+    /// the frame used for the altitude and the rise, set and transit times (`target::SkyObject`).
+    pub fn hour_angle(&self, observer: &Observer, time: &Time) -> f64 {
+        self.hadec(observer, time).0
+    }
+
+    /// Hour angle (in [-180, 180)) and declination of the target in degrees, precessed to the date
+    /// like the local sidereal time and the rise, set and transit times
+    pub(crate) fn hadec(&self, observer: &Observer, time: &Time) -> (f64, f64) {
+        let (ra, dec) = self.radec(observer, time);
+        (normalize_hour_angle(observer.local_sidereal_time(time) - ra), dec)
+    }
+
+    /// Calculate the parallactic angle of the target at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time at which to calculate the parallactic angle
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The parallactic angle of the target in degrees, in [-180, 180]
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 35, 34);
+    /// 
+    /// // East of the meridian, the zenith is towards the West of the target
+    /// let q = target.parallactic_angle(&observer, &time);
+    /// assert!(q < 0.0);
+    /// ```
+    pub fn parallactic_angle(&self, observer: &Observer, time: &Time) -> f64 {
+        let (ha, dec) = self.hadec(observer, time);
+        parallactic_angle(ha, dec, observer.lat)
     }
 
     /// Calculate the airmass of the target at a given time
@@ -138,20 +309,50 @@ impl <'a> Target<'a> {
     /// 
     /// let airmass = target.airmass(&observer, &time);
     /// println!("Airmass: {}", airmass);
-    /// assert!((airmass - 1.476094).abs() < 1e-6);
+    /// assert!((airmass - 1.481845).abs() < 1e-6);
     /// ```
     /// 
     /// # Notes
     /// 
-    /// This airmass calculation is quite simple and does not take into account refraction or other atmospheric effects.
-    /// For a more accurate calculation, consider using another dedicated library.
+    /// This uses the formula of Pickering (2002), see `airmass::AirmassModel` for the other models.
+    /// Below the horizon, the airmass is `f64::INFINITY`.
     pub fn airmass(&self, observer: &Observer, time: &Time) -> f64 {
-        let alt = self.altitude(observer, time);
-        if alt <= 0.0 {
-            return std::f64::NEG_INFINITY;
-        }
-        let sinarg = alt + 244.0 / (165.0 + 47.0 * alt.powf(1.1));
-        1.0 / (sinarg * DEGRA).sin()
+        self.airmass_with_model(observer, time, &AirmassModel::default())
+    }
+
+    /// Calculate the airmass of the target at a given time, with a given airmass model
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object representing the time at which to calculate the airmass
+    /// * `model` - The formula used to compute the airmass from the altitude
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The airmass of the target, or `f64::INFINITY` if it is below the horizon
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::airmass::AirmassModel;
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 8, 24, 6, 34, 0);
+    /// 
+    /// let young = target.airmass_with_model(&observer, &time, &AirmassModel::Young1994);
+    /// assert!((young - target.airmass(&observer, &time)).abs() < 1e-3);
+    /// 
+    /// // twelve hours later, the target is below the horizon
+    /// let time = Time::new(2024, 8, 24, 18, 34, 0);
+    /// assert_eq!(target.airmass_with_model(&observer, &time, &AirmassModel::Young1994), f64::INFINITY);
+    /// ```
+    pub fn airmass_with_model(&self, observer: &Observer, time: &Time, model: &AirmassModel) -> f64 {
+        let (ha, dec) = self.hadec(observer, time);
+        let (alt, _) = hadec2altaz(ha, dec, observer.lat);
+        model.airmass(alt)
     }
 
     /// Calculate the separation to another target
@@ -181,7 +382,7 @@ impl <'a> Target<'a> {
     /// 
     /// # Arguments
     /// 
-    /// * `others` - A vector of other targets
+    /// * `others` - A slice of other targets
     /// 
     /// # Returns
     /// 
@@ -204,12 +405,8 @@ impl <'a> Target<'a> {
     /// assert_eq!((separations[1] - 2.0).abs() < 1e-6, true);
     /// assert_eq!((separations[2] - 3.0).abs() < 1e-6, true);
     /// ```
-    pub fn separations(&self, others: &Vec<Target>) -> Vec<f64> {
-        let mut separations = Vec::new();
-        for other in others {
-            separations.push(self.separation(other));
-        }
-        separations
+    pub fn separations(&self, others: &[Target]) -> Vec<f64> {
+        map_indices(others.len(), |i| self.separation(&others[i]))
     }
 
     /// Convert the target to a string
@@ -235,6 +432,7 @@ impl <'a> Target<'a> {
     /// assert_eq!(target.to_string(), "RA: 6.374817, DEC: 20.242942 (no name)");
     /// println!("{}", target.to_string());
     /// ```
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         if let Some(name) = &self.name {
             return format!("Name: {}, RA: {}, DEC: {}", name, self.ra, self.dec);
@@ -284,6 +482,175 @@ impl <'a> Target<'a> {
         radec2lb(self.ra, self.dec)
     }
 
+    /// Create a new Target from Galactic coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `l` - Galactic longitude of the target in degrees
+    /// * `b` - Galactic latitude of the target in degrees
+    /// * `name` - Optional name of the target
+    /// 
+    /// # Returns
+    /// 
+    /// * `Target` - A new Target
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::Target;
+    /// 
+    /// let target = Target::from_galactic(114.706509, -42.214159, Some("Vega"));
+    /// assert!((target.ra - 6.374817).abs() < 1e-5);
+    /// assert!((target.dec - 20.242942).abs() < 1e-5);
+    /// ```
+    pub fn from_galactic(l: f64, b: f64, name: Option<&'a str>) -> Target<'a> {
+        let (ra, dec) = lb2radec(l, b);
+        Target::new(ra, dec, name)
+    }
+
+    /// Calculate the angular distance between the target and the Galactic plane
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The absolute Galactic latitude of the target in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::Target;
+    /// 
+    /// let target = Target::new(6.374817, 20.242942, Some("Vega"));
+    /// assert!((target.galactic_plane_distance() - 42.214159).abs() < 1e-6);
+    /// ```
+    pub fn galactic_plane_distance(&self) -> f64 {
+        plane_distance(self.ra, self.dec)
+    }
+
+    /// Calculate the angular separation between the target and the Galactic center
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The angular separation in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::Target;
+    /// 
+    /// let target = Target::from_galactic(10.0, 0.0, None);
+    /// assert!((target.galactic_center_separation() - 10.0).abs() < 1e-6);
+    /// ```
+    pub fn galactic_center_separation(&self) -> f64 {
+        center_separation(self.ra, self.dec)
+    }
+
+    /// Compute the ecliptic coordinates of the target, in the mean ecliptic and equinox of J2000
+    /// 
+    /// # Returns
+    /// 
+    /// * (`f64`, `f64`) - The ecliptic longitude and latitude of the target in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::Target;
+    /// 
+    /// let target = Target::new(116.328942, 28.026183, Some("Pollux"));
+    /// let (lon, lat) = target.ecliptic();
+    /// assert!((lon - 113.215630).abs() < 1e-6);
+    /// assert!((lat - 6.684170).abs() < 1e-6);
+    /// ```
+    pub fn ecliptic(&self) -> (f64, f64) {
+        radec2ecliptic(self.ra, self.dec, &EclipticFrame::J2000)
+    }
+
+    /// Compute the ecliptic coordinates of the target in a given ecliptic frame
+    /// 
+    /// # Arguments
+    /// 
+    /// * `frame` - The ecliptic frame (J2000, mean of date or true of date)
+    /// 
+    /// # Returns
+    /// 
+    /// * (`f64`, `f64`) - The ecliptic longitude and latitude of the target in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Target, Time};
+    /// use flare::ecliptic::EclipticFrame;
+    /// 
+    /// let target = Target::new(116.328942, 28.026183, Some("Pollux"));
+    /// let frame = EclipticFrame::TrueOfDate(Time::new(2024, 9, 10, 3, 0, 0));
+    /// let (lon, lat) = target.ecliptic_in_frame(&frame);
+    /// // about 24 years of precession of the equinoxes
+    /// assert!((lon - 113.215630 - 0.3375).abs() < 0.01);
+    /// assert!((lat - 6.684170).abs() < 0.01);
+    /// ```
+    pub fn ecliptic_in_frame(&self, frame: &EclipticFrame) -> (f64, f64) {
+        radec2ecliptic(self.ra, self.dec, frame)
+    }
+
+    /// Calculate the solar elongation of the target (its angular distance to the Sun) at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - Time object
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The solar elongation in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Target, Time};
+    /// 
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// let time = Time::new(2024, 9, 10, 3, 0, 0);
+    /// let elongation = target.solar_elongation(&time);
+    /// assert!(elongation > 120.0 && elongation < 180.0);
+    /// ```
+    pub fn solar_elongation(&self, time: &Time) -> f64 {
+        solar_elongation(self.ra, self.dec, time)
+    }
+
+    /// Calculate the angular distance between the target and the Moon, seen by an observer at a given time
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object
+    /// 
+    /// # Returns
+    /// 
+    /// * `f64` - The angular distance to the center of the Moon in degrees
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use flare::{Observer, Target, Time};
+    /// 
+    /// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+    /// let target = Target::new(6.374817, 20.242942, None);
+    /// 
+    /// // the full moon of 2024 September 18 is about 24 degrees away
+    /// let time = Time::new(2024, 9, 18, 6, 0, 0);
+    /// let separation = target.moon_separation(&observer, &time);
+    /// assert!((separation - 23.88).abs() < 0.05);
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// This uses the topocentric position of the Moon (`moon::topocentric_position`), which can be
+    /// up to a degree away from its geocentric position, and compares it to the position of the target
+    /// precessed to the date.
+    pub fn moon_separation(&self, observer: &Observer, time: &Time) -> f64 {
+        let moon = topocentric_position(observer, time);
+        let (ra, dec) = self.radec(observer, time);
+        great_circle_distance(ra, dec, moon.ra, moon.dec)
+    }
+
 }
 
 impl <'a> std::fmt::Display for Target<'a> {
@@ -295,3 +662,77 @@ impl <'a> std::fmt::Display for Target<'a> {
         }
     }
 }
+
+/// SkyObject trait
+///
+/// This trait represents anything with a position in the sky, that rises and sets for an observer:
+/// a `Target` (fixed in the sky), the Sun (`sun::Sun`) or the Moon (`moon::Moon`).
+/// Positions are in the true equator and equinox of date, the frame of the local sidereal time:
+/// the J2000 coordinates of a `Target` are precessed to the date.
+///
+/// # Methods
+///
+/// * `radec` - Get the position of the object seen by an observer at a given time
+/// * `horizon_altitude` - Get the geometric altitude of the center of the object when it rises or sets
+/// * `search_window` - Get the time span searched for a rise or a set
+///
+/// # Examples
+///
+/// ```
+/// use flare::moon::Moon;
+/// use flare::target::SkyObject;
+/// use flare::{Observer, Target, Time};
+///
+/// let observer = Observer::new(33.3633675, -116.8361345, 1870.0, None);
+/// let time = Time::new(2024, 9, 10, 3, 0, 0);
+///
+/// let objects: Vec<Box<dyn SkyObject>> = vec![Box::new(Target::new(6.374817, 20.242942, None)), Box::new(Moon)];
+/// for object in objects.iter() {
+///     let (ra, dec) = object.radec(&observer, &time);
+///     assert!((0.0..360.0).contains(&ra) && dec.abs() <= 90.0);
+/// }
+/// ```
+pub trait SkyObject {
+    /// Get the position of the object seen by an observer at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - Right Ascension and Declination in degrees
+    fn radec(&self, observer: &Observer, time: &Time) -> (f64, f64);
+
+    /// Get the geometric altitude of the center of the object when it rises or sets
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Observer object representing the observer
+    /// * `time` - Time object
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The altitude in degrees: -34' by default, the refraction at the horizon
+    fn horizon_altitude(&self, _observer: &Observer, _time: &Time) -> f64 {
+        -34.0 / 60.0
+    }
+
+    /// Get the time span searched for a rise or a set, on each side of the reference time
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The span in days: a day and a half by default, enough for objects that rise and set every day.
+    ///   An object that does not cross the horizon within this span stays above (or below) it.
+    fn search_window(&self) -> f64 {
+        1.5
+    }
+}
+
+impl <'a> SkyObject for Target<'a> {
+    fn radec(&self, _observer: &Observer, time: &Time) -> (f64, f64) {
+        // precessed to the date, like the apparent positions of the Sun and the Moon
+        true_equator_rotation(time).apply_radec(self.ra, self.dec)
+    }
+}
@@ -232,9 +232,9 @@ impl Time {
         let year = if month > 2 { c - 4716.0 } else { c - 4715.0 };
 
         let hour = ((f * 24.0) as i32).abs();
-        f = f - (hour as f64 / 24.0);
+        f -= hour as f64 / 24.0;
         let minute = ((f * 1440.0) as i32).abs();
-        f = f - (minute as f64 / 1440.0);
+        f -= minute as f64 / 1440.0;
         let second = ((f * 86400.0) as i32).abs();
 
         Time {
@@ -298,10 +298,9 @@ impl Time {
         let minute = self.minute as f64;
         let second = self.second as f64;
 
-        let jd = 367.0 * year - ((year + ((month + 9.0) / 12.0)).floor() * 7.0 / 4.0).floor()
+        367.0 * year - ((year + ((month + 9.0) / 12.0)).floor() * 7.0 / 4.0).floor()
             + ((275.0 * month) / 9.0).floor() + day + 1721013.5
-            + ((hour + (minute / 60.0) + (second / 3600.0)) / 24.0);
-        jd
+            + ((hour + (minute / 60.0) + (second / 3600.0)) / 24.0)
     }
 
     /// Convert the Time to a Modified Julian Date
@@ -414,18 +413,18 @@ impl Time {
     pub fn to_string(&self, format: Option<&str>) -> String {
         if let Some(format) = format {
             if format == "jd" {
-                return self.to_jd().to_string();
+                self.to_jd().to_string()
             } else if format == "mjd" {
-                return self.to_mjd().to_string();
+                self.to_mjd().to_string()
             } else if format == "utc" {
-                return self.to_utc().to_string();
+                self.to_utc().to_string()
             } else if format == "isot" {
-                return self.to_utc().to_rfc3339();
+                self.to_utc().to_rfc3339()
             } else {
-                return "Invalid format".to_string();
+                "Invalid format".to_string()
             }
         } else {
-            return self.to_utc().to_string();
+            self.to_utc().to_string()
         }
     }
 }
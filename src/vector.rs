@@ -0,0 +1,750 @@
+use crate::spatial::{map_indices, DEGRA, RGE};
+
+/// Cartesian3 struct
+///
+/// This struct represents a vector in 3D Cartesian coordinates, e.g. a position or a velocity.
+///
+/// # Attributes
+///
+/// * `x` - Component along the x axis (towards RA = 0, DEC = 0 for equatorial coordinates)
+/// * `y` - Component along the y axis (towards RA = 90, DEC = 0 for equatorial coordinates)
+/// * `z` - Component along the z axis (towards DEC = 90 for equatorial coordinates)
+///
+/// # Examples
+///
+/// ```
+/// use flare::vector::Cartesian3;
+///
+/// let a = Cartesian3::new(1.0, 0.0, 0.0);
+/// let b = Cartesian3::new(0.0, 2.0, 0.0);
+/// assert_eq!(a.cross(&b), Cartesian3::new(0.0, 0.0, 2.0));
+/// assert_eq!(b.norm(), 2.0);
+///
+/// // vectors add, subtract, negate and scale with the usual operators
+/// assert_eq!(a + b, Cartesian3::new(1.0, 2.0, 0.0));
+/// assert_eq!(a - b * 0.5, Cartesian3::new(1.0, -1.0, 0.0));
+/// assert_eq!(-a, Cartesian3::new(-1.0, 0.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cartesian3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Cartesian3 {
+    /// Create a new Cartesian3 vector
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Component along the x axis
+    /// * `y` - Component along the y axis
+    /// * `z` - Component along the z axis
+    ///
+    /// # Returns
+    ///
+    /// * `Cartesian3` - A new Cartesian3 object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Cartesian3;
+    ///
+    /// let v = Cartesian3::new(1.0, 2.0, 3.0);
+    /// assert_eq!((v.x, v.y, v.z), (1.0, 2.0, 3.0));
+    /// ```
+    pub fn new(x: f64, y: f64, z: f64) -> Cartesian3 {
+        Cartesian3 { x, y, z }
+    }
+
+    /// Create a new Cartesian3 vector from an array of components
+    ///
+    /// # Arguments
+    ///
+    /// * `v` - The x, y and z components
+    ///
+    /// # Returns
+    ///
+    /// * `Cartesian3` - A new Cartesian3 object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Cartesian3;
+    ///
+    /// assert_eq!(Cartesian3::from_array([1.0, 2.0, 3.0]), Cartesian3::new(1.0, 2.0, 3.0));
+    /// ```
+    pub fn from_array(v: [f64; 3]) -> Cartesian3 {
+        Cartesian3 { x: v[0], y: v[1], z: v[2] }
+    }
+
+    /// Get the components of the vector as an array
+    ///
+    /// # Returns
+    ///
+    /// * `[f64; 3]` - The x, y and z components
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Cartesian3;
+    ///
+    /// assert_eq!(Cartesian3::new(1.0, 2.0, 3.0).to_array(), [1.0, 2.0, 3.0]);
+    /// ```
+    pub fn to_array(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Calculate the dot product with another vector
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other vector
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The dot (scalar) product of the two vectors
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Cartesian3;
+    ///
+    /// let a = Cartesian3::new(1.0, 2.0, 3.0);
+    /// let b = Cartesian3::new(4.0, -5.0, 6.0);
+    /// assert_eq!(a.dot(&b), 12.0);
+    /// ```
+    pub fn dot(&self, other: &Cartesian3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Calculate the cross product with another vector
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other vector
+    ///
+    /// # Returns
+    ///
+    /// * `Cartesian3` - The cross (vector) product `self x other`, orthogonal to both vectors
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Cartesian3;
+    ///
+    /// let x = Cartesian3::new(1.0, 0.0, 0.0);
+    /// let y = Cartesian3::new(0.0, 1.0, 0.0);
+    /// assert_eq!(x.cross(&y), Cartesian3::new(0.0, 0.0, 1.0));
+    /// assert_eq!(y.cross(&x), Cartesian3::new(0.0, 0.0, -1.0));
+    /// ```
+    pub fn cross(&self, other: &Cartesian3) -> Cartesian3 {
+        Cartesian3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Calculate the norm (length) of the vector
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The Euclidean norm of the vector
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Cartesian3;
+    ///
+    /// assert_eq!(Cartesian3::new(3.0, 0.0, 4.0).norm(), 5.0);
+    /// ```
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Get the direction of the vector
+    ///
+    /// # Returns
+    ///
+    /// * `Option<UnitVector>` - The direction of the vector, or None for the null vector
+    ///   (or a vector with non-finite components)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Cartesian3;
+    ///
+    /// let (ra, dec) = Cartesian3::new(0.0, 2.0, 0.0).direction().unwrap().to_radec();
+    /// assert_eq!((ra, dec), (90.0, 0.0));
+    /// assert!(Cartesian3::new(0.0, 0.0, 0.0).direction().is_none());
+    /// ```
+    pub fn direction(&self) -> Option<UnitVector> {
+        let norm = self.norm();
+        if norm == 0.0 || !norm.is_finite() {
+            return None;
+        }
+        Some(UnitVector { v: *self * (1.0 / norm) })
+    }
+}
+
+impl std::ops::Add for Cartesian3 {
+    type Output = Cartesian3;
+
+    fn add(self, other: Cartesian3) -> Cartesian3 {
+        Cartesian3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl std::ops::Sub for Cartesian3 {
+    type Output = Cartesian3;
+
+    fn sub(self, other: Cartesian3) -> Cartesian3 {
+        Cartesian3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl std::ops::Mul<f64> for Cartesian3 {
+    type Output = Cartesian3;
+
+    fn mul(self, factor: f64) -> Cartesian3 {
+        Cartesian3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl std::ops::Neg for Cartesian3 {
+    type Output = Cartesian3;
+
+    fn neg(self) -> Cartesian3 {
+        Cartesian3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// UnitVector struct
+///
+/// This struct represents a direction on the sphere, as a vector of norm 1.
+/// It is the representation used to convert positions between frames: a change of frame is a rotation of the vector.
+///
+/// # Examples
+///
+/// ```
+/// use flare::vector::UnitVector;
+///
+/// let u = UnitVector::from_radec(45.0, 30.0);
+/// let (ra, dec) = u.to_radec();
+/// assert!((ra - 45.0).abs() < 1e-12 && (dec - 30.0).abs() < 1e-12);
+///
+/// let pole = UnitVector::from_radec(0.0, 90.0);
+/// assert!((u.angle(&pole) - 60.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitVector {
+    v: Cartesian3,
+}
+
+impl UnitVector {
+    /// Create a new UnitVector from spherical coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Longitude in degrees (e.g. RA, or l for Galactic coordinates)
+    /// * `dec` - Latitude in degrees (e.g. DEC, or b for Galactic coordinates)
+    ///
+    /// # Returns
+    ///
+    /// * `UnitVector` - A new UnitVector object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::UnitVector;
+    ///
+    /// let [x, y, z] = UnitVector::from_radec(90.0, 0.0).to_array();
+    /// assert!(x.abs() < 1e-15 && (y - 1.0).abs() < 1e-15 && z == 0.0);
+    /// ```
+    pub fn from_radec(ra: f64, dec: f64) -> UnitVector {
+        let ra_rad = ra * DEGRA;
+        let dec_rad = dec * DEGRA;
+        UnitVector {
+            v: Cartesian3 {
+                x: ra_rad.cos() * dec_rad.cos(),
+                y: ra_rad.sin() * dec_rad.cos(),
+                z: dec_rad.sin(),
+            },
+        }
+    }
+
+    /// Convert the UnitVector to spherical coordinates
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - The longitude in degrees, in [0, 360), and the latitude in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::UnitVector;
+    ///
+    /// let (ra, dec) = UnitVector::from_radec(-30.0, 45.0).to_radec();
+    /// assert!((ra - 330.0).abs() < 1e-12 && (dec - 45.0).abs() < 1e-12);
+    /// ```
+    pub fn to_radec(&self) -> (f64, f64) {
+        let (x, y, z) = (self.v.x, self.v.y, self.v.z);
+        let ra = y.atan2(x) / DEGRA;
+        let dec = z.atan2((x * x + y * y).sqrt()) / DEGRA;
+        (ra.rem_euclid(360.0), dec)
+    }
+
+    /// Get the vector as a Cartesian3
+    ///
+    /// # Returns
+    ///
+    /// * `Cartesian3` - The vector, of norm 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::UnitVector;
+    ///
+    /// // e.g. to scale a direction by a distance
+    /// let position = UnitVector::from_radec(0.0, 90.0).cartesian() * 2.0;
+    /// assert_eq!(position.z, 2.0);
+    /// ```
+    pub fn cartesian(&self) -> Cartesian3 {
+        self.v
+    }
+
+    /// Get the components of the vector as an array
+    ///
+    /// # Returns
+    ///
+    /// * `[f64; 3]` - The x, y and z components
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::UnitVector;
+    ///
+    /// assert_eq!(UnitVector::from_radec(0.0, 0.0).to_array(), [1.0, 0.0, 0.0]);
+    /// ```
+    pub fn to_array(&self) -> [f64; 3] {
+        self.v.to_array()
+    }
+
+    /// Calculate the dot product with another UnitVector
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other UnitVector
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The cosine of the angle between the two directions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::UnitVector;
+    ///
+    /// let a = UnitVector::from_radec(0.0, 0.0);
+    /// let b = UnitVector::from_radec(60.0, 0.0);
+    /// assert!((a.dot(&b) - 0.5).abs() < 1e-15);
+    /// ```
+    pub fn dot(&self, other: &UnitVector) -> f64 {
+        self.v.dot(&other.v)
+    }
+
+    /// Calculate the angle with another UnitVector
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other UnitVector
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The angle between the two directions in degrees, in [0, 180]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::UnitVector;
+    ///
+    /// // accurate for small angles too, unlike the arc cosine of the dot product
+    /// let a = UnitVector::from_radec(10.0, 20.0);
+    /// let b = UnitVector::from_radec(10.0, 20.0 + 1e-9);
+    /// assert!((a.angle(&b) - 1e-9).abs() < 1e-13);
+    /// ```
+    pub fn angle(&self, other: &UnitVector) -> f64 {
+        self.v.cross(&other.v).norm().atan2(self.dot(other)) / DEGRA
+    }
+
+    /// Calculate the cross product with another UnitVector
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other UnitVector
+    ///
+    /// # Returns
+    ///
+    /// * `Cartesian3` - The cross product `self x other`, normal to the great circle through the two directions,
+    ///   of norm the sine of the angle between them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::UnitVector;
+    ///
+    /// // the pole of the equator
+    /// let a = UnitVector::from_radec(0.0, 0.0);
+    /// let b = UnitVector::from_radec(30.0, 0.0);
+    /// let (_, dec) = a.cross(&b).direction().unwrap().to_radec();
+    /// assert!((dec - 90.0).abs() < 1e-12);
+    /// assert!((a.cross(&b).norm() - 0.5).abs() < 1e-15);
+    /// ```
+    pub fn cross(&self, other: &UnitVector) -> Cartesian3 {
+        self.v.cross(&other.v)
+    }
+}
+
+impl std::ops::Neg for UnitVector {
+    type Output = UnitVector;
+
+    fn neg(self) -> UnitVector {
+        UnitVector { v: -self.v }
+    }
+}
+
+/// Rotation3 struct
+///
+/// This struct represents a rotation of the 3D space, stored as an orthogonal 3x3 matrix.
+/// Rotations are used for frame conversions: applying `Rotation3` to a `UnitVector` expressed in a frame
+/// gives the same direction expressed in another frame.
+///
+/// # Examples
+///
+/// ```
+/// use flare::vector::{Rotation3, UnitVector};
+///
+/// // equatorial (J2000) to ecliptic (J2000): a rotation of the frame around the x axis by the obliquity
+/// let to_ecliptic = Rotation3::about_x(23.4392911);
+/// let u = to_ecliptic.apply(&UnitVector::from_radec(116.328942, 28.026183));
+/// let (lon, lat) = u.to_radec();
+/// assert!((lon - 113.215630).abs() < 1e-6 && (lat - 6.684170).abs() < 1e-6);
+///
+/// // the inverse rotation goes back to equatorial coordinates
+/// let (ra, dec) = to_ecliptic.inverse().apply(&u).to_radec();
+/// assert!((ra - 116.328942).abs() < 1e-9 && (dec - 28.026183).abs() < 1e-9);
+///
+/// // rotations compose: `a.then(&b)` applies `a` first, then `b`
+/// let r = Rotation3::about_z(30.0).then(&Rotation3::about_z(60.0));
+/// let (ra, _) = r.apply_radec(100.0, 0.0);
+/// assert!((ra - 10.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rotation3 {
+    matrix: [[f64; 3]; 3],
+}
+
+impl Rotation3 {
+    /// Create a new Rotation3 from its matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `matrix` - The rotation matrix (rows first), which is expected to be orthogonal
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - A new Rotation3 object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// // a frame where x and y are swapped (and z flipped, to keep it right-handed)
+    /// let r = Rotation3::from_matrix([[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]]);
+    /// let (ra, dec) = r.apply_radec(90.0, 10.0);
+    /// assert!(ra.abs() < 1e-12 && (dec + 10.0).abs() < 1e-12);
+    /// ```
+    pub fn from_matrix(matrix: [[f64; 3]; 3]) -> Rotation3 {
+        Rotation3 { matrix }
+    }
+
+    /// Create the identity rotation
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - The rotation that leaves every vector unchanged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// let r = Rotation3::about_z(30.0);
+    /// assert_eq!(r.then(&Rotation3::identity()), r);
+    /// ```
+    pub fn identity() -> Rotation3 {
+        Rotation3::from_matrix([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Create a rotation of the coordinate frame around its x axis
+    ///
+    /// # Arguments
+    ///
+    /// * `angle` - Rotation angle of the frame in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - The rotation, e.g. from equatorial to ecliptic coordinates for an angle equal to the obliquity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// // the y axis of the rotated frame is 90 degrees from the original y axis
+    /// let (_, dec) = Rotation3::about_x(90.0).apply_radec(90.0, 0.0);
+    /// assert!((dec + 90.0).abs() < 1e-12);
+    /// ```
+    pub fn about_x(angle: f64) -> Rotation3 {
+        let (s, c) = (angle * DEGRA).sin_cos();
+        Rotation3::from_matrix([[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]])
+    }
+
+    /// Create a rotation of the coordinate frame around its y axis
+    ///
+    /// # Arguments
+    ///
+    /// * `angle` - Rotation angle of the frame in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - The rotation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// let (_, dec) = Rotation3::about_y(90.0).apply_radec(0.0, 0.0);
+    /// assert!((dec - 90.0).abs() < 1e-12);
+    /// ```
+    pub fn about_y(angle: f64) -> Rotation3 {
+        let (s, c) = (angle * DEGRA).sin_cos();
+        Rotation3::from_matrix([[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]])
+    }
+
+    /// Create a rotation of the coordinate frame around its z axis
+    ///
+    /// # Arguments
+    ///
+    /// * `angle` - Rotation angle of the frame in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - The rotation, which decreases longitudes by `angle`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// let (ra, dec) = Rotation3::about_z(30.0).apply_radec(100.0, 20.0);
+    /// assert!((ra - 70.0).abs() < 1e-12 && (dec - 20.0).abs() < 1e-12);
+    /// ```
+    pub fn about_z(angle: f64) -> Rotation3 {
+        let (s, c) = (angle * DEGRA).sin_cos();
+        Rotation3::from_matrix([[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Create the rotation from the equatorial (ICRS) to the Galactic frame
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - The rotation from RA/DEC to l/b
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// // the North Galactic pole
+    /// let (_, b) = Rotation3::icrs_to_galactic().apply_radec(192.85948, 27.12825);
+    /// assert!((b - 90.0).abs() < 1e-4);
+    /// ```
+    pub fn icrs_to_galactic() -> Rotation3 {
+        Rotation3::from_matrix(RGE)
+    }
+
+    /// Get the matrix of the rotation
+    ///
+    /// # Returns
+    ///
+    /// * `[[f64; 3]; 3]` - The rotation matrix, rows first
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// assert_eq!(Rotation3::identity().matrix()[1], [0.0, 1.0, 0.0]);
+    /// ```
+    pub fn matrix(&self) -> [[f64; 3]; 3] {
+        self.matrix
+    }
+
+    /// Compose two rotations
+    ///
+    /// # Arguments
+    ///
+    /// * `next` - The rotation to apply after this one
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - The rotation that applies `self` first, then `next`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// let r = Rotation3::about_z(30.0).then(&Rotation3::about_z(60.0));
+    /// let (ra, _) = r.apply_radec(100.0, 0.0);
+    /// assert!((ra - 10.0).abs() < 1e-9);
+    /// ```
+    pub fn then(&self, next: &Rotation3) -> Rotation3 {
+        let mut m = [[0.0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| next.matrix[i][k] * self.matrix[k][j]).sum();
+            }
+        }
+        Rotation3 { matrix: m }
+    }
+
+    /// Get the inverse rotation
+    ///
+    /// # Returns
+    ///
+    /// * `Rotation3` - The inverse rotation (the transpose of the matrix)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// let r = Rotation3::about_x(23.4);
+    /// let (lon, lat) = r.apply_radec(10.0, 20.0);
+    /// let (ra, dec) = r.inverse().apply_radec(lon, lat);
+    /// assert!((ra - 10.0).abs() < 1e-9 && (dec - 20.0).abs() < 1e-9);
+    /// ```
+    pub fn inverse(&self) -> Rotation3 {
+        let m = &self.matrix;
+        Rotation3::from_matrix([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+
+    /// Rotate a Cartesian3 vector
+    ///
+    /// # Arguments
+    ///
+    /// * `v` - The vector, expressed in the original frame
+    ///
+    /// # Returns
+    ///
+    /// * `Cartesian3` - The same vector expressed in the rotated frame (with the same norm)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::{Cartesian3, Rotation3};
+    ///
+    /// let v = Rotation3::about_z(90.0).apply_cartesian(&Cartesian3::new(0.0, 2.0, 1.0));
+    /// assert!((v.x - 2.0).abs() < 1e-12 && v.y.abs() < 1e-12 && v.z == 1.0);
+    /// ```
+    pub fn apply_cartesian(&self, v: &Cartesian3) -> Cartesian3 {
+        Cartesian3 {
+            x: self.matrix[0][0] * v.x + self.matrix[0][1] * v.y + self.matrix[0][2] * v.z,
+            y: self.matrix[1][0] * v.x + self.matrix[1][1] * v.y + self.matrix[1][2] * v.z,
+            z: self.matrix[2][0] * v.x + self.matrix[2][1] * v.y + self.matrix[2][2] * v.z,
+        }
+    }
+
+    /// Rotate a UnitVector
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The direction, expressed in the original frame
+    ///
+    /// # Returns
+    ///
+    /// * `UnitVector` - The same direction expressed in the rotated frame
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::{Rotation3, UnitVector};
+    ///
+    /// let u = Rotation3::about_z(45.0).apply(&UnitVector::from_radec(50.0, 10.0));
+    /// let (ra, dec) = u.to_radec();
+    /// assert!((ra - 5.0).abs() < 1e-12 && (dec - 10.0).abs() < 1e-12);
+    /// ```
+    pub fn apply(&self, u: &UnitVector) -> UnitVector {
+        UnitVector { v: self.apply_cartesian(&u.v) }
+    }
+
+    /// Rotate a position given in spherical coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Longitude in degrees in the original frame
+    /// * `dec` - Latitude in degrees in the original frame
+    ///
+    /// # Returns
+    ///
+    /// * `(f64, f64)` - The longitude (in [0, 360)) and latitude in degrees in the rotated frame
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// let (l, b) = Rotation3::icrs_to_galactic().apply_radec(266.40499, -28.93617);
+    /// assert!((l.min(360.0 - l)).abs() < 1e-3 && b.abs() < 1e-3);
+    /// ```
+    pub fn apply_radec(&self, ra: f64, dec: f64) -> (f64, f64) {
+        self.apply(&UnitVector::from_radec(ra, dec)).to_radec()
+    }
+
+    /// Rotate a list of positions given in spherical coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `ras` - Longitudes in degrees in the original frame
+    /// * `decs` - Latitudes in degrees in the original frame
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(f64, f64)>` - The longitudes (in [0, 360)) and latitudes in degrees in the rotated frame
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::vector::Rotation3;
+    ///
+    /// let positions = Rotation3::about_z(10.0).apply_radec_many(&[20.0, 30.0], &[0.0, 45.0]);
+    /// assert!((positions[1].0 - 20.0).abs() < 1e-12 && (positions[1].1 - 45.0).abs() < 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ras` and `decs` have different lengths.
+    pub fn apply_radec_many(&self, ras: &[f64], decs: &[f64]) -> Vec<(f64, f64)> {
+        assert_eq!(ras.len(), decs.len(), "ras and decs must have the same length");
+        map_indices(ras.len(), |i| self.apply_radec(ras[i], decs[i]))
+    }
+}
@@ -0,0 +1,607 @@
+use std::collections::HashMap;
+
+use crate::projection::{Projection, SphericalRotation};
+use crate::target::Target;
+
+/// WcsError enum
+///
+/// This enum represents the errors that can occur when reading a WCS from a FITS header.
+///
+/// # Variants
+///
+/// * `MissingKeyword` - A required keyword is missing from the header
+/// * `InvalidValue` - A keyword has a value that can't be parsed
+/// * `UnsupportedCtype` - The CTYPE of an axis is not supported (unknown projection, non RA/DEC axes)
+#[derive(Debug, Clone, PartialEq)]
+pub enum WcsError {
+    MissingKeyword(String),
+    InvalidValue(String, String),
+    UnsupportedCtype(String),
+}
+
+impl std::fmt::Display for WcsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WcsError::MissingKeyword(keyword) => write!(f, "Missing keyword: {}", keyword),
+            WcsError::InvalidValue(keyword, value) => write!(f, "Invalid value for {}: {}", keyword, value),
+            WcsError::UnsupportedCtype(ctype) => write!(f, "Unsupported CTYPE: {}", ctype),
+        }
+    }
+}
+
+impl std::error::Error for WcsError {}
+
+/// SipPolynomial struct
+///
+/// This struct represents one of the polynomials of the Simple Imaging Polynomial (SIP) distortion convention.
+///
+/// # Attributes
+///
+/// * `order` - Order of the polynomial
+/// * `coefficients` - Coefficients of the polynomial, with `coefficients[p][q]` the coefficient of `u^p v^q`
+///
+/// # Examples
+///
+/// ```
+/// use flare::wcs::SipPolynomial;
+///
+/// let mut polynomial = SipPolynomial::new(2);
+/// polynomial.coefficients[2][0] = 1e-6;
+/// polynomial.coefficients[1][1] = 2e-6;
+/// assert!((polynomial.evaluate(100.0, 10.0) - 0.012).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SipPolynomial {
+    pub order: usize,
+    pub coefficients: Vec<Vec<f64>>,
+}
+
+impl SipPolynomial {
+    /// Create a new SipPolynomial with all coefficients set to 0
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - Order of the polynomial
+    ///
+    /// # Returns
+    ///
+    /// * `SipPolynomial` - A new SipPolynomial object
+    pub fn new(order: usize) -> SipPolynomial {
+        SipPolynomial { order, coefficients: vec![vec![0.0; order + 1]; order + 1] }
+    }
+
+    /// Evaluate the polynomial
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Pixel offset from the reference pixel along the first axis
+    /// * `v` - Pixel offset from the reference pixel along the second axis
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - Value of the polynomial, in pixels
+    pub fn evaluate(&self, u: f64, v: f64) -> f64 {
+        let mut value = 0.0;
+        let mut u_p = 1.0;
+        for p in 0..=self.order {
+            let mut v_q = 1.0;
+            for q in 0..=(self.order - p) {
+                value += self.coefficients[p][q] * u_p * v_q;
+                v_q *= v;
+            }
+            u_p *= u;
+        }
+        value
+    }
+}
+
+/// Sip struct
+///
+/// This struct represents the SIP distortion of an image (Shupe et al. 2005).
+///
+/// # Attributes
+///
+/// * `a` - Forward (pixel to sky) distortion polynomial along the first axis (A_p_q)
+/// * `b` - Forward (pixel to sky) distortion polynomial along the second axis (B_p_q)
+/// * `ap` - Optional inverse (sky to pixel) polynomial along the first axis (AP_p_q)
+/// * `bp` - Optional inverse (sky to pixel) polynomial along the second axis (BP_p_q)
+///
+/// # Notes
+///
+/// When converting sky coordinates to pixels, the inverse polynomials (if any) are only used as a
+/// starting point, which is then refined iteratively against the forward polynomials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sip {
+    pub a: SipPolynomial,
+    pub b: SipPolynomial,
+    pub ap: Option<SipPolynomial>,
+    pub bp: Option<SipPolynomial>,
+}
+
+impl Sip {
+    /// Apply the forward distortion to pixel offsets from the reference pixel
+    fn distort(&self, u: f64, v: f64) -> (f64, f64) {
+        (u + self.a.evaluate(u, v), v + self.b.evaluate(u, v))
+    }
+
+    /// Invert the forward distortion
+    fn undistort(&self, u: f64, v: f64) -> (f64, f64) {
+        let (mut x, mut y) = match (&self.ap, &self.bp) {
+            (Some(ap), Some(bp)) => (u + ap.evaluate(u, v), v + bp.evaluate(u, v)),
+            _ => (u, v),
+        };
+        for _ in 0..50 {
+            let (du, dv) = self.distort(x, y);
+            let (dx, dy) = (du - u, dv - v);
+            x -= dx;
+            y -= dy;
+            if dx.abs() < 1e-10 && dy.abs() < 1e-10 {
+                break;
+            }
+        }
+        (x, y)
+    }
+}
+
+/// Wcs struct
+///
+/// This struct represents a FITS World Coordinate System, mapping pixel coordinates of an image
+/// to celestial coordinates (RA, DEC).
+///
+/// # Attributes
+///
+/// * `crval` - RA and DEC of the reference point (CRVAL1, CRVAL2) in degrees
+/// * `crpix` - Pixel coordinates of the reference point (CRPIX1, CRPIX2), 1-based as in FITS headers
+/// * `cd` - Linear transformation matrix (CDi_j) from pixel offsets to intermediate world coordinates in degrees
+/// * `projection` - Sky projection
+/// * `lonpole` - Optional native longitude of the celestial pole (LONPOLE) in degrees
+/// * `latpole` - Optional native latitude of the celestial pole (LATPOLE) in degrees
+/// * `sip` - Optional SIP distortion
+///
+/// # Methods
+///
+/// * `new` - Create a new Wcs
+/// * `from_header` - Read a Wcs from a FITS header
+/// * `from_cards` - Read a Wcs from a list of FITS header cards
+/// * `pixel_to_world` - Convert pixel coordinates to RA and DEC
+/// * `world_to_pixel` - Convert RA and DEC to pixel coordinates
+/// * `pixel_to_target` - Convert pixel coordinates to a Target
+/// * `target_to_pixel` - Convert a Target to pixel coordinates
+///
+/// # Notes
+///
+/// Pixel coordinates passed to and returned by the methods are 0-based (the center of the first pixel
+/// is at (0, 0)), like in most image processing libraries and astropy's `pixel_to_world`, while `crpix`
+/// follows the 1-based FITS convention.
+///
+/// # Examples
+///
+/// ```
+/// use flare::projection::Projection;
+/// use flare::wcs::Wcs;
+///
+/// // a North-up, East-left image with 1"/pixel
+/// let scale = 1.0 / 3600.0;
+/// let wcs = Wcs::new((150.0, 2.0), (32.0, 32.0), [[-scale, 0.0], [0.0, scale]], Projection::Tan);
+///
+/// let (ra, dec) = wcs.pixel_to_world(31.0, 31.0).unwrap();
+/// assert!((ra - 150.0).abs() < 1e-12 && (dec - 2.0).abs() < 1e-12);
+///
+/// // one pixel up is one arcsecond North
+/// let (_, dec) = wcs.pixel_to_world(31.0, 32.0).unwrap();
+/// assert!((dec - 2.0 - scale).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wcs {
+    pub crval: (f64, f64),
+    pub crpix: (f64, f64),
+    pub cd: [[f64; 2]; 2],
+    pub projection: Projection,
+    pub lonpole: Option<f64>,
+    pub latpole: Option<f64>,
+    pub sip: Option<Sip>,
+}
+
+impl Wcs {
+    /// Create a new Wcs
+    ///
+    /// # Arguments
+    ///
+    /// * `crval` - RA and DEC of the reference point (CRVAL1, CRVAL2) in degrees
+    /// * `crpix` - Pixel coordinates of the reference point (CRPIX1, CRPIX2), 1-based as in FITS headers
+    /// * `cd` - Linear transformation matrix (CDi_j) in degrees per pixel
+    /// * `projection` - Sky projection
+    ///
+    /// # Returns
+    ///
+    /// * `Wcs` - A new Wcs object, with default LONPOLE and LATPOLE and no distortion
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    /// use flare::wcs::Wcs;
+    ///
+    /// let wcs = Wcs::new((0.0, 0.0), (180.5, 90.5), [[-1.0, 0.0], [0.0, 1.0]], Projection::Car);
+    /// let (ra, dec) = wcs.pixel_to_world(269.5, 89.5).unwrap();
+    /// assert!((ra - 270.0).abs() < 1e-9 && dec.abs() < 1e-9);
+    /// ```
+    pub fn new(crval: (f64, f64), crpix: (f64, f64), cd: [[f64; 2]; 2], projection: Projection) -> Wcs {
+        Wcs { crval, crpix, cd, projection, lonpole: None, latpole: None, sip: None }
+    }
+
+    /// Read a Wcs from a FITS header
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The FITS header, either as raw 80-character cards (as stored in FITS files)
+    ///   or with one card per line (as written by astropy's `Header.tofile(sep="\n")`)
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Wcs, WcsError>` - The Wcs, or an error if the header doesn't describe a supported celestial WCS
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::wcs::Wcs;
+    ///
+    /// // a synthetic ZTF-style cutout header with a TAN-SIP WCS, one card per line
+    /// // (the expected values follow Calabretta & Greisen 2002 and Shupe et al. 2005, computed independently)
+    /// let header = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/headers/ztf_cutout_science.hdr"));
+    /// let wcs = Wcs::from_header(header).unwrap();
+    /// assert!(wcs.sip.is_some());
+    ///
+    /// let (ra, dec) = wcs.pixel_to_world(31.0, 31.0).unwrap();
+    /// assert!((ra - 149.7780243537).abs() < 1e-9);
+    /// assert!((dec - 1.7418515467).abs() < 1e-9);
+    ///
+    /// let (ra, dec) = wcs.pixel_to_world(0.0, 0.0).unwrap();
+    /// assert!((ra - 149.7867177103).abs() < 1e-9);
+    /// assert!((dec - 1.7331161921).abs() < 1e-9);
+    ///
+    /// let (x, y) = wcs.world_to_pixel(149.7693308419, 1.7505868369).unwrap();
+    /// assert!((x - 62.0).abs() < 1e-5 && (y - 62.0).abs() < 1e-5);
+    /// ```
+    ///
+    /// ```
+    /// use flare::wcs::Wcs;
+    ///
+    /// // the matching synthetic reference cutout, stored as raw FITS cards with PCi_j + CDELTi
+    /// let header = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/headers/ztf_cutout_reference.hdr"));
+    /// let wcs = Wcs::from_header(header).unwrap();
+    /// assert!(wcs.sip.is_none());
+    /// assert_eq!(wcs.lonpole, Some(180.0));
+    ///
+    /// let (ra, dec) = wcs.pixel_to_world(31.0, 31.0).unwrap();
+    /// assert!((ra - 149.7780243223).abs() < 1e-9);
+    /// assert!((dec - 1.7418515152).abs() < 1e-9);
+    ///
+    /// let (ra, dec) = wcs.pixel_to_world(62.0, 62.0).unwrap();
+    /// assert!((ra - 149.7693153997).abs() < 1e-9);
+    /// assert!((dec - 1.7505745064).abs() < 1e-9);
+    ///
+    /// // without the SIP distortion, the corner of the science cutout is off by a few hundredths of a pixel
+    /// let (x, y) = wcs.world_to_pixel(149.7693308419, 1.7505868369).unwrap();
+    /// assert!((x - 61.945131).abs() < 1e-5 && (y - 62.043923).abs() < 1e-5);
+    /// ```
+    pub fn from_header(header: &str) -> Result<Wcs, WcsError> {
+        let cards = if header.contains('\n') {
+            header.lines().collect::<Vec<&str>>()
+        } else {
+            header
+                .as_bytes()
+                .chunks(80)
+                .filter_map(|card| std::str::from_utf8(card).ok())
+                .collect::<Vec<&str>>()
+        };
+        Wcs::from_cards(&cards)
+    }
+
+    /// Read a Wcs from a list of FITS header cards
+    ///
+    /// # Arguments
+    ///
+    /// * `cards` - The header cards, e.g. `"CRVAL1  =  150.0 / comment"`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Wcs, WcsError>` - The Wcs, or an error if the cards don't describe a supported celestial WCS
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    /// use flare::wcs::{Wcs, WcsError};
+    ///
+    /// // an old-style header, with CDELTi and CROTA2
+    /// let cards = [
+    ///     "CTYPE1  = 'RA---ZEA'",
+    ///     "CTYPE2  = 'DEC--ZEA'",
+    ///     "CRVAL1  = 266.4",
+    ///     "CRVAL2  = -28.9",
+    ///     "CRPIX1  = 50.5",
+    ///     "CRPIX2  = 50.5",
+    ///     "CDELT1  = -0.1",
+    ///     "CDELT2  = 0.1",
+    ///     "CROTA2  = 90.0",
+    /// ];
+    /// let wcs = Wcs::from_cards(&cards).unwrap();
+    /// assert_eq!(wcs.projection, Projection::Zea);
+    /// assert!((wcs.cd[0][1] + 0.1).abs() < 1e-12);
+    /// assert!((wcs.cd[1][0] + 0.1).abs() < 1e-12);
+    ///
+    /// let error = Wcs::from_cards(&cards[..4]).unwrap_err();
+    /// assert_eq!(error, WcsError::MissingKeyword("CRPIX1".to_string()));
+    /// ```
+    pub fn from_cards(cards: &[&str]) -> Result<Wcs, WcsError> {
+        let keywords = parse_cards(cards);
+        let string = |keyword: &str| {
+            keywords
+                .get(keyword)
+                .cloned()
+                .ok_or_else(|| WcsError::MissingKeyword(keyword.to_string()))
+        };
+        let number = |keyword: &str| {
+            let value = string(keyword)?;
+            value
+                .replace(['D', 'd'], "E")
+                .parse::<f64>()
+                .map_err(|_| WcsError::InvalidValue(keyword.to_string(), value))
+        };
+        let optional = |keyword: &str| match keywords.contains_key(keyword) {
+            true => number(keyword).map(Some),
+            false => Ok(None),
+        };
+
+        let ctype1 = string("CTYPE1")?;
+        let ctype2 = string("CTYPE2")?;
+        let (axis1, projection, sip1) = parse_ctype(&ctype1)?;
+        let (axis2, _, sip2) = parse_ctype(&ctype2)?;
+        if axis1 != "RA" || axis2 != "DEC" {
+            return Err(WcsError::UnsupportedCtype(format!("{}, {}", ctype1, ctype2)));
+        }
+
+        let crval = (number("CRVAL1")?, number("CRVAL2")?);
+        let crpix = (number("CRPIX1")?, number("CRPIX2")?);
+
+        let cd_keywords = ["CD1_1", "CD1_2", "CD2_1", "CD2_2"];
+        let cd = if cd_keywords.iter().any(|keyword| keywords.contains_key(*keyword)) {
+            let mut cd = [[0.0; 2]; 2];
+            for (k, keyword) in cd_keywords.iter().enumerate() {
+                cd[k / 2][k % 2] = optional(keyword)?.unwrap_or(0.0);
+            }
+            cd
+        } else {
+            let cdelt = [number("CDELT1")?, number("CDELT2")?];
+            let pc_keywords = ["PC1_1", "PC1_2", "PC2_1", "PC2_2"];
+            let mut pc = [[1.0, 0.0], [0.0, 1.0]];
+            if pc_keywords.iter().any(|keyword| keywords.contains_key(*keyword)) {
+                for (k, keyword) in pc_keywords.iter().enumerate() {
+                    pc[k / 2][k % 2] = optional(keyword)?.unwrap_or(if k % 3 == 0 { 1.0 } else { 0.0 });
+                }
+            } else if let Some(crota) = optional("CROTA2")? {
+                // AIPS convention
+                let (sin, cos) = crota.to_radians().sin_cos();
+                pc = [[cos, -sin * cdelt[1] / cdelt[0]], [sin * cdelt[0] / cdelt[1], cos]];
+            }
+            [
+                [cdelt[0] * pc[0][0], cdelt[0] * pc[0][1]],
+                [cdelt[1] * pc[1][0], cdelt[1] * pc[1][1]],
+            ]
+        };
+
+        let read_polynomial = |name: &str| -> Result<SipPolynomial, WcsError> {
+            let order_keyword = format!("{}_ORDER", name);
+            let order = number(&order_keyword)?;
+            if order < 0.0 || order.fract() != 0.0 {
+                return Err(WcsError::InvalidValue(order_keyword, order.to_string()));
+            }
+            let mut polynomial = SipPolynomial::new(order as usize);
+            for p in 0..=polynomial.order {
+                for q in 0..=(polynomial.order - p) {
+                    if let Some(value) = optional(&format!("{}_{}_{}", name, p, q))? {
+                        polynomial.coefficients[p][q] = value;
+                    }
+                }
+            }
+            Ok(polynomial)
+        };
+        let sip = if sip1 || sip2 {
+            let inverse = keywords.contains_key("AP_ORDER") && keywords.contains_key("BP_ORDER");
+            Some(Sip {
+                a: read_polynomial("A")?,
+                b: read_polynomial("B")?,
+                ap: if inverse { Some(read_polynomial("AP")?) } else { None },
+                bp: if inverse { Some(read_polynomial("BP")?) } else { None },
+            })
+        } else {
+            None
+        };
+
+        Ok(Wcs {
+            crval,
+            crpix,
+            cd,
+            projection,
+            lonpole: optional("LONPOLE")?,
+            latpole: optional("LATPOLE")?,
+            sip,
+        })
+    }
+
+    fn rotation(&self) -> SphericalRotation {
+        SphericalRotation::new(self.projection, self.crval.0, self.crval.1, self.lonpole, self.latpole)
+    }
+
+    /// Convert pixel offsets from the reference pixel to intermediate world coordinates
+    fn intermediate(&self, dx: f64, dy: f64) -> (f64, f64) {
+        (self.cd[0][0] * dx + self.cd[0][1] * dy, self.cd[1][0] * dx + self.cd[1][1] * dy)
+    }
+
+    /// Convert intermediate world coordinates to pixel offsets from the reference pixel
+    fn pixel_offsets(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let det = self.cd[0][0] * self.cd[1][1] - self.cd[0][1] * self.cd[1][0];
+        if det == 0.0 {
+            return None;
+        }
+        Some(((self.cd[1][1] * x - self.cd[0][1] * y) / det, (self.cd[0][0] * y - self.cd[1][0] * x) / det))
+    }
+
+    /// Convert pixel coordinates to RA and DEC
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - 0-based pixel coordinate along the first axis
+    /// * `y` - 0-based pixel coordinate along the second axis
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(f64, f64)>` - RA and DEC in degrees, or None if the pixel is outside of the projection's boundary
+    pub fn pixel_to_world(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let (mut dx, mut dy) = (x + 1.0 - self.crpix.0, y + 1.0 - self.crpix.1);
+        if let Some(sip) = &self.sip {
+            (dx, dy) = sip.distort(dx, dy);
+        }
+        let (u, v) = self.intermediate(dx, dy);
+        let (phi, theta) = self.projection.deproject(u, v)?;
+        Some(self.rotation().to_celestial(phi, theta))
+    }
+
+    /// Convert RA and DEC to pixel coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `ra` - Right ascension in degrees
+    /// * `dec` - Declination in degrees
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(f64, f64)>` - 0-based pixel coordinates, or None if the position can't be projected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    /// use flare::wcs::Wcs;
+    ///
+    /// let scale = 1.0 / 3600.0;
+    /// let wcs = Wcs::new((359.99, -30.0), (50.5, 50.5), [[-scale, 0.0], [0.0, scale]], Projection::Sin);
+    /// let (x, y) = wcs.world_to_pixel(0.001, -29.99).unwrap();
+    /// let (ra, dec) = wcs.pixel_to_world(x, y).unwrap();
+    /// assert!((ra - 0.001).abs() < 1e-9 && (dec + 29.99).abs() < 1e-9);
+    /// ```
+    pub fn world_to_pixel(&self, ra: f64, dec: f64) -> Option<(f64, f64)> {
+        let (phi, theta) = self.rotation().to_native(ra, dec);
+        let (u, v) = self.projection.project(phi, theta)?;
+        let (mut dx, mut dy) = self.pixel_offsets(u, v)?;
+        if let Some(sip) = &self.sip {
+            (dx, dy) = sip.undistort(dx, dy);
+        }
+        Some((dx + self.crpix.0 - 1.0, dy + self.crpix.1 - 1.0))
+    }
+
+    /// Convert pixel coordinates to a Target
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - 0-based pixel coordinate along the first axis
+    /// * `y` - 0-based pixel coordinate along the second axis
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Target>` - A Target (without name) at the position of the pixel
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    /// use flare::wcs::Wcs;
+    ///
+    /// let wcs = Wcs::new((150.0, 2.0), (1.0, 1.0), [[-0.001, 0.0], [0.0, 0.001]], Projection::Tan);
+    /// let target = wcs.pixel_to_target(0.0, 0.0).unwrap();
+    /// assert!((target.ra - 150.0).abs() < 1e-12);
+    /// ```
+    pub fn pixel_to_target(&self, x: f64, y: f64) -> Option<Target<'static>> {
+        self.pixel_to_world(x, y).map(|(ra, dec)| Target::new(ra, dec, None))
+    }
+
+    /// Convert a Target to pixel coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(f64, f64)>` - 0-based pixel coordinates, or None if the target can't be projected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flare::projection::Projection;
+    /// use flare::wcs::Wcs;
+    /// use flare::Target;
+    ///
+    /// let wcs = Wcs::new((150.0, 2.0), (1.0, 1.0), [[-0.001, 0.0], [0.0, 0.001]], Projection::Tan);
+    /// let target = Target::new(150.0, 2.001, None);
+    /// let (x, y) = wcs.target_to_pixel(&target).unwrap();
+    /// assert!(x.abs() < 1e-6 && (y - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn target_to_pixel(&self, target: &Target) -> Option<(f64, f64)> {
+        self.world_to_pixel(target.ra, target.dec)
+    }
+}
+
+/// Parse FITS header cards into a map of keywords to values (strings are unquoted)
+fn parse_cards(cards: &[&str]) -> HashMap<String, String> {
+    let mut keywords = HashMap::new();
+    for card in cards {
+        let keyword = card.get(..8).unwrap_or(card).trim();
+        if keyword.is_empty() || keyword == "COMMENT" || keyword == "HISTORY" || keyword == "END" {
+            continue;
+        }
+        let Some((keyword, value)) = card.split_once('=') else {
+            continue;
+        };
+        let keyword = keyword.trim();
+        if keyword.is_empty() || keyword.contains(' ') {
+            continue;
+        }
+        let value = value.trim_start();
+        let value = if let Some(quoted) = value.strip_prefix('\'') {
+            // string values end at the first single quote that is not doubled
+            let mut string = String::new();
+            let mut chars = quoted.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '\'' {
+                    if chars.peek() == Some(&'\'') {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                string.push(c);
+            }
+            string.trim_end().to_string()
+        } else {
+            value.split('/').next().unwrap_or("").trim().to_string()
+        };
+        keywords.insert(keyword.to_uppercase(), value);
+    }
+    keywords
+}
+
+/// Parse a CTYPE value (e.g. "RA---TAN-SIP") into its axis name, projection and SIP flag
+fn parse_ctype(ctype: &str) -> Result<(String, Projection, bool), WcsError> {
+    let unsupported = || WcsError::UnsupportedCtype(ctype.to_string());
+    let axis = ctype.get(..4).ok_or_else(unsupported)?.trim_end_matches('-').to_uppercase();
+    let code = ctype.get(5..8).ok_or_else(unsupported)?;
+    let projection = Projection::from_code(code).ok_or_else(unsupported)?;
+    let sip = match ctype.get(8..) {
+        None | Some("") => false,
+        Some(suffix) if suffix.eq_ignore_ascii_case("-SIP") => true,
+        Some(_) => return Err(unsupported()),
+    };
+    Ok((axis, projection, sip))
+}